@@ -3,6 +3,8 @@ use rand::Rng;
 use rand_distr::{ChiSquared, Distribution, Normal};
 use serde::{Deserialize, Serialize};
 
+use crate::{ObjId, Tick};
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum RDistrib {
     MinMax(f32, f32),
@@ -111,6 +113,12 @@ pub fn select_one<R: rand::Rng>(rng: &mut R, candidates: &Vec<f32>) -> Option<us
         .last()
 }
 
+/// Create a seed unique for the object in the current tick, so systems using it take the same
+/// random decisions for the same game state
+pub fn seed_for(obj_id: ObjId, tick: Tick) -> u64 {
+    ((obj_id.as_u32() as u64) << 32) | tick.as_u32() as u64
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -151,6 +159,14 @@ mod test {
         assert!(total_2 > total_1 * 5)
     }
 
+    #[test]
+    fn seed_for_should_be_unique_by_object_and_tick() {
+        let seed = seed_for(ObjId(1), Tick(2));
+        assert_eq!(seed, seed_for(ObjId(1), Tick(2)));
+        assert_ne!(seed, seed_for(ObjId(2), Tick(1)));
+        assert_ne!(seed, seed_for(ObjId(1), Tick(3)));
+    }
+
     #[test]
     fn test_serialize_rdistr() {
         let str = serde_json::to_string(&RDistrib::MinMax(0.0, 10.0)).unwrap();
@@ -4,6 +4,7 @@ use crate::errors::{Error, Result};
 use crate::game::avatars;
 use crate::game::combat::kill_mob;
 use crate::game::container::Container;
+use crate::game::dialogues::Conversation;
 use crate::game::inventory_service::compute_total_weight;
 use crate::game::loader::dto::{ObjData, StaticId};
 use crate::game::loader::Loader;
@@ -16,10 +17,15 @@ use commons::*;
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 
+mod input_handle_auction;
+mod input_handle_bank;
 mod input_handle_command;
 mod input_handle_hire;
 mod input_handle_items;
+mod input_handle_path;
 mod input_handle_space;
+mod input_handle_talk;
+mod input_handle_trade;
 mod input_handle_vendors;
 mod view_admin;
 mod view_login;
@@ -37,12 +43,15 @@ struct ConnectionState {
     pub connection_id: ConnectionId,
     pub player_id: Option<PlayerId>,
     pub view: ConnectionView,
+    /// npc dialogue in progress, numeric inputs are answers to it
+    pub conversation: Option<Conversation>,
 }
 
 pub struct ViewHandleCtx<'a> {
     pub container: &'a mut Container,
     pub mob_id: MobId,
     pub player_id: PlayerId,
+    pub conversation: &'a mut Option<Conversation>,
 }
 
 #[derive(Debug, Clone)]
@@ -79,6 +88,7 @@ impl ConnectionController {
                 connection_id,
                 player_id: None,
                 view: ConnectionView::Login,
+                conversation: None,
             },
         );
 
@@ -127,7 +137,7 @@ impl ConnectionController {
 
         let state = self
             .connections
-            .get(&connection_id)
+            .get_mut(&connection_id)
             .expect(format!("could not found connection {:?}", connection_id).as_str());
 
         let view_action: crate::errors::Result<ConnectionViewAction> = match state.view {
@@ -176,6 +186,7 @@ impl ConnectionController {
                     container: container,
                     mob_id,
                     player_id,
+                    conversation: &mut state.conversation,
                 };
 
                 view_main::handle(ctx, input)
@@ -268,6 +279,7 @@ impl ConnectionController {
 
                 state.view = ConnectionView::Login;
                 state.player_id = None;
+                state.conversation = None;
 
                 if let Some(player_id) = old_player_id {
                     self.connection_id_by_player_id.remove(&player_id);
@@ -0,0 +1,83 @@
+use crate::errors::{Error, Result};
+use crate::game::container::Container;
+use crate::game::item::ItemId;
+use crate::game::mob::MobId;
+use crate::game::prices::Money;
+use crate::game::{actions_auction, comm, inventory_service};
+use crate::utils::strinput::StrInput;
+use commons::ObjId;
+
+pub fn auction(container: &mut Container, mob_id: MobId, input: StrInput) -> Result<()> {
+    let args = StrInput(input.plain_arguments());
+    let arguments = args.parse_arguments();
+
+    match args.first() {
+        "" | "list" => actions_auction::list(container, mob_id),
+        "collect" => actions_auction::collect(container, mob_id),
+        "sell" if arguments.len() >= 2 => {
+            let (price, label) = arguments.split_last().unwrap();
+            let label = label.join(" ");
+            let buyout = parse_money(container, mob_id, price)?;
+
+            let item_id = inventory_service::search_one(
+                &container.labels,
+                &container.locations,
+                &container.items,
+                mob_id,
+                label.as_str(),
+            );
+
+            match item_id {
+                Some(item_id) => actions_auction::sell(container, mob_id, item_id, buyout),
+                None => {
+                    container
+                        .outputs
+                        .private(mob_id, comm::auction_sell_invalid_item(label.as_str()));
+                    Err(Error::InvalidArgumentFailure)
+                }
+            }
+        }
+        "bid" if arguments.len() == 2 => {
+            let item_id = parse_id(container, mob_id, arguments[0])?;
+            let amount = parse_money(container, mob_id, arguments[1])?;
+            actions_auction::bid(container, mob_id, item_id, amount)
+        }
+        "buy" if arguments.len() == 1 => {
+            let item_id = parse_id(container, mob_id, arguments[0])?;
+            actions_auction::buyout(container, mob_id, item_id)
+        }
+        "cancel" if arguments.len() == 1 => {
+            let item_id = parse_id(container, mob_id, arguments[0])?;
+            actions_auction::cancel(container, mob_id, item_id)
+        }
+        _ => {
+            container
+                .outputs
+                .private(mob_id, comm::auction_invalid_command());
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
+/// listing ids are show with # prefix
+fn parse_id(container: &mut Container, mob_id: MobId, value: &str) -> Result<ItemId> {
+    match value.trim_start_matches('#').parse::<u32>() {
+        Ok(id) => Ok(ObjId(id)),
+        Err(_) => {
+            container.outputs.private(mob_id, comm::auction_not_found());
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
+fn parse_money(container: &mut Container, mob_id: MobId, value: &str) -> Result<Money> {
+    match value.parse::<u32>() {
+        Ok(amount) => Ok(Money(amount)),
+        Err(_) => {
+            container
+                .outputs
+                .private(mob_id, comm::auction_invalid_price());
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
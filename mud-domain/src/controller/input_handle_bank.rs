@@ -0,0 +1,88 @@
+use crate::controller::input_handle_items::parse_amount;
+use crate::errors::{Error, Result};
+use crate::game::container::Container;
+use crate::game::mob::MobId;
+use crate::game::prices::Money;
+use crate::game::{actions_bank, comm, inventory_service};
+use crate::utils::strinput::StrInput;
+
+pub fn bank(container: &mut Container, mob_id: MobId, input: StrInput) -> Result<()> {
+    let args = StrInput(input.plain_arguments());
+    let value = args.plain_arguments();
+
+    match args.first() {
+        "" => actions_bank::show(container, mob_id),
+        "deposit" => {
+            let amount = parse_money(container, mob_id, value)?;
+            actions_bank::deposit(container, mob_id, amount)
+        }
+        "withdraw" => {
+            let amount = parse_money(container, mob_id, value)?;
+            actions_bank::withdraw(container, mob_id, amount)
+        }
+        "store" if !value.is_empty() => {
+            let item_id = inventory_service::search_one(
+                &container.labels,
+                &container.locations,
+                &container.items,
+                mob_id,
+                value,
+            );
+
+            match item_id {
+                Some(item_id) => actions_bank::store(container, mob_id, item_id),
+                None => {
+                    container
+                        .outputs
+                        .private(mob_id, comm::bank_store_invalid_item(value));
+                    Err(Error::InvalidArgumentFailure)
+                }
+            }
+        }
+        "retrieve" if !value.is_empty() => {
+            let (amount, label) = parse_amount(value);
+            let storage_id = container
+                .players
+                .find_from_mob(mob_id)
+                .and_then(|player_id| actions_bank::get_storage_id(container, player_id));
+
+            let item_id = storage_id.and_then(|storage_id| {
+                inventory_service::search_one(
+                    &container.labels,
+                    &container.locations,
+                    &container.items,
+                    storage_id,
+                    label,
+                )
+            });
+
+            match item_id {
+                Some(item_id) => actions_bank::retrieve(container, mob_id, item_id, amount),
+                None => {
+                    container
+                        .outputs
+                        .private(mob_id, comm::bank_retrieve_not_found(label));
+                    Err(Error::InvalidArgumentFailure)
+                }
+            }
+        }
+        _ => {
+            container
+                .outputs
+                .private(mob_id, comm::bank_invalid_command());
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
+fn parse_money(container: &mut Container, mob_id: MobId, value: &str) -> Result<Money> {
+    match value.parse::<u32>() {
+        Ok(amount) if amount > 0 => Ok(Money(amount)),
+        _ => {
+            container
+                .outputs
+                .private(mob_id, comm::bank_invalid_amount(value));
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::controller::ViewHandleCtx;
 use crate::errors::*;
-use crate::game::{actions_command, comm};
+use crate::game::actions_command::RequestCommand;
+use crate::game::{actions_command, comm, pathfinding};
 use crate::utils::strinput::StrInput;
 use commons::ObjId;
 
@@ -18,86 +19,143 @@ pub fn list_commands(ctx: &mut ViewHandleCtx) -> Result<()> {
     Ok(())
 }
 
-pub fn list_commands_for(_ctx: &mut ViewHandleCtx, _target: &str) -> Result<()> {
-    // let target_id = match find_command_target(ctx, target) {
-    //     Some(target_id) => target_id,
-    //     None => return Ok(()),
-    // };
-    //
-    // let label = ctx.container.labels.get_label_f(target_id);
-    //
-    // let commands = actions_command::list_commands_for(ctx.container, target_id)?;
-    // ctx.container
-    //     .outputs
-    //     .private(ctx.mob_id, comm::list_commands(label, &commands));
-    // Ok(())
-    Err(Error::NotImplementedException)
+pub fn list_commands_for(ctx: &mut ViewHandleCtx, target: &str) -> Result<()> {
+    let target_id = match find_command_target(ctx, target) {
+        Some(target_id) => target_id,
+        None => return Ok(()),
+    };
+
+    let label = ctx.container.labels.get_label_f(target_id);
+
+    let commands = actions_command::list_commands_for(ctx.container, target_id)?;
+    ctx.container
+        .outputs
+        .private(ctx.mob_id, comm::list_commands(label, &commands));
+    Ok(())
 }
 
-pub fn set_command(_ctx: &mut ViewHandleCtx, _target: &str, _command: StrInput) -> Result<()> {
-    // let target_id = match find_command_target(ctx, target) {
-    //     Some(target_id) => target_id,
-    //     None => return Ok(()),
-    // };
-    //
-    // if command.plain_arguments().has_command("follow me") {
-    //     set_command_follow_me(ctx, target_id)
-    // } else if command.has_command("extract") {
-    //     set_command_extract(ctx, target_id)
-    // } else if command.has_command("haul") {
-    //     set_command_haul(ctx, target_id, command)
-    // } else {
-    //     ctx.container.outputs.private(
-    //         ctx.mob_id,
-    //         comm::command_invalid_for_target(target, command.plain_arguments()),
-    //     );
-    //     Ok(())
-    // }
-    Err(Error::NotImplementedException)
+pub fn set_command(ctx: &mut ViewHandleCtx, target: &str, command: StrInput) -> Result<()> {
+    let target_id = match find_command_target(ctx, target) {
+        Some(target_id) => target_id,
+        None => return Ok(()),
+    };
+
+    let request = if command.has_command("follow") {
+        Some(RequestCommand::FollowMe)
+    } else if command.has_command("extract") {
+        Some(RequestCommand::Extract)
+    } else if command.has_command("haul") {
+        Some(RequestCommand::Haul)
+    } else if command.has_command("guard") {
+        Some(RequestCommand::Guard)
+    } else if command.has_commands(&["wait", "idle", "stop"]) {
+        Some(RequestCommand::Idle)
+    } else {
+        None
+    };
+
+    let available = actions_command::list_commands_for(ctx.container, target_id)?;
+
+    match request {
+        Some(request) if available.contains(&request) => match request {
+            RequestCommand::Idle => set_command_wait(ctx, target_id),
+            RequestCommand::FollowMe => set_command_follow_me(ctx, target_id),
+            RequestCommand::Extract => set_command_extract(ctx, target_id),
+            RequestCommand::Haul => set_command_haul(ctx, target_id, command),
+            RequestCommand::Guard => set_command_guard(ctx, target_id),
+        },
+        _ => {
+            ctx.container.outputs.private(
+                ctx.mob_id,
+                comm::command_invalid_for_target(target, command.as_str()),
+            );
+            Ok(())
+        }
+    }
 }
 
-fn set_command_haul(_ctx: &mut ViewHandleCtx, _target_id: ObjId, _command: StrInput) -> Result<()> {
-    // let args = command.parse_arguments();
-    // if args.len() != 2 {
-    //     ctx.container
-    //         .outputs
-    //         .private(ctx.mob_id, comm::command_move_invalid_argument());
-    //     return Ok(());
-    // }
-    //
-    // let (from_id, to_id) = match (args[0].parse::<u32>(), args[1].parse::<u32>()) {
-    //     (Ok(id_from), Ok(id_to)) => {
-    //         match (
-    //             ctx.container.rooms.exists(id_from.into()),
-    //             ctx.container.rooms.exists(id_to.into()),
-    //         ) {
-    //             (true, true) => (id_from, id_to),
-    //             (true, false) => {
-    //                 ctx.container
-    //                     .outputs
-    //                     .private(ctx.mob_id, comm::command_haul_from_not_found(args[1]));
-    //                 return OK(());
-    //             }
-    //             (false, _) => {
-    //                 ctx.container
-    //                     .outputs
-    //                     .private(ctx.mob_id, comm::command_haul_from_not_found(args[0]));
-    //                 return OK(());
-    //             }
-    //         }
-    //     }
-    //     _ => {
-    //         ctx.container
-    //             .outputs
-    //             .private(ctx.mob_id, comm::command_haul_invalid_argument());
-    //         return OK(());
-    //     }
-    // };
-    //
-    // actions_command::set_command_haul(ctx.container, target_id, from_id, to_id)?;
-    //
-    // Ok(())
-    Err(Error::NotImplementedException)
+fn find_command_target(ctx: &mut ViewHandleCtx, target: &str) -> Option<ObjId> {
+    let candidates = actions_command::find_commandable(ctx.container, ctx.mob_id, target).ok()?;
+    match candidates.first() {
+        Some(target_id) => Some(*target_id),
+        None => {
+            ctx.container
+                .outputs
+                .private(ctx.mob_id, comm::command_target_not_found(target));
+            None
+        }
+    }
+}
+
+fn set_command_haul(ctx: &mut ViewHandleCtx, target_id: ObjId, command: StrInput) -> Result<()> {
+    let args = command.parse_arguments();
+    if args.len() != 2 {
+        ctx.container
+            .outputs
+            .private(ctx.mob_id, comm::command_haul_invalid_argument());
+        return Ok(());
+    }
+
+    let from_id = pathfinding::search_known_rooms(ctx.container, ctx.mob_id, args[0])
+        .first()
+        .cloned();
+    let to_id = pathfinding::search_known_rooms(ctx.container, ctx.mob_id, args[1])
+        .first()
+        .cloned();
+
+    let (from_id, to_id) = match (from_id, to_id) {
+        (Some(from_id), Some(to_id)) => (from_id, to_id),
+        (Some(_), None) => {
+            ctx.container
+                .outputs
+                .private(ctx.mob_id, comm::command_haul_to_not_found(args[1]));
+            return Ok(());
+        }
+        (None, _) => {
+            ctx.container
+                .outputs
+                .private(ctx.mob_id, comm::command_haul_from_not_found(args[0]));
+            return Ok(());
+        }
+    };
+
+    actions_command::set_command_haul(ctx.container, target_id, from_id, to_id)?;
+
+    let label = ctx.container.labels.get_label_f(target_id);
+    let from_label = ctx.container.labels.get_label_f(from_id);
+    let to_label = ctx.container.labels.get_label_f(to_id);
+    ctx.container.outputs.private(
+        ctx.mob_id,
+        comm::command_haul_ack(label, from_label, to_label),
+    );
+    Ok(())
+}
+
+fn set_command_wait(ctx: &mut ViewHandleCtx, target_id: ObjId) -> Result<()> {
+    actions_command::set_command_idle(ctx.container, target_id)?;
+
+    let label = ctx.container.labels.get_label_f(target_id);
+    ctx.container
+        .outputs
+        .private(ctx.mob_id, comm::command_wait_ack(label));
+    Ok(())
+}
+
+fn set_command_guard(ctx: &mut ViewHandleCtx, target_id: ObjId) -> Result<()> {
+    let room_id = ctx
+        .container
+        .locations
+        .get(ctx.mob_id)
+        .as_result_str("mob has no location")?;
+
+    actions_command::set_command_guard(ctx.container, target_id, room_id)?;
+
+    let label = ctx.container.labels.get_label_f(target_id);
+    let room_label = ctx.container.labels.get_label_f(room_id);
+    ctx.container
+        .outputs
+        .private(ctx.mob_id, comm::command_guard_ack(label, room_label));
+    Ok(())
 }
 
 fn set_command_follow_me(ctx: &mut ViewHandleCtx, target_id: ObjId) -> Result<()> {
@@ -1,3 +1,4 @@
+use crate::controller::input_handle_vendors;
 use crate::errors::{AsResult, Error, Result};
 use crate::game::actions_items::*;
 use crate::game::container::Container;
@@ -5,7 +6,8 @@ use crate::game::item::{ItemId, ItemRepository};
 use crate::game::labels::Labels;
 use crate::game::location::Locations;
 use crate::game::mob::MobId;
-use crate::game::{comm, inventory_service};
+use crate::game::needs::ConsumableKind;
+use crate::game::{comm, inventory_service, mob, scripts_service};
 use crate::utils::strinput::StrInput;
 use commons::{ObjId, PlayerId};
 
@@ -20,12 +22,18 @@ pub fn parser_owned_item(
     owner_id: ObjId,
     args: StrInput,
 ) -> std::result::Result<ItemId, ParseItemError> {
-    if args.plain_arguments().is_empty() {
+    parser_owned_item_label(container, owner_id, args.plain_arguments())
+}
+
+pub fn parser_owned_item_label(
+    container: &Container,
+    owner_id: ObjId,
+    item_label: &str,
+) -> std::result::Result<ItemId, ParseItemError> {
+    if item_label.is_empty() {
         return Err(ParseItemError::ItemNotProvided);
     }
 
-    let item_label = args.plain_arguments();
-
     let founds = inventory_service::search(
         &container.labels,
         &container.locations,
@@ -105,13 +113,26 @@ pub fn parse_not_owned_item(
 pub fn pickup(container: &mut Container, mob_id: MobId, args: StrInput) -> Result<()> {
     let room_id = container.locations.get(mob_id).as_result()?;
 
-    match parse_not_owned_item(
-        &container.labels,
-        &container.locations,
-        &container.items,
-        room_id,
-        args,
-    ) {
+    let parse = |location_id: ObjId| {
+        parse_not_owned_item(
+            &container.labels,
+            &container.locations,
+            &container.items,
+            location_id,
+            StrInput(args.0),
+        )
+    };
+
+    // items can be taken from containers carried by the mob
+    let result = match parse(room_id) {
+        Err(err) => match parse(mob_id) {
+            Ok((item_id, Some(storage_id))) => Ok((item_id, Some(storage_id))),
+            _ => Err(err),
+        },
+        other => other,
+    };
+
+    match result {
         Ok((item_id, maybe_container)) => {
             let _ = do_pickup(container, mob_id, item_id, maybe_container);
         }
@@ -142,8 +163,19 @@ pub fn equip(container: &mut Container, mob_id: MobId, args: StrInput) -> Result
     }
 }
 
+/// parse arguments like `5 coin` into the amount and label
+pub fn parse_amount(args: &str) -> (Option<u32>, &str) {
+    let mut parts = args.splitn(2, ' ');
+    match (parts.next().map(|s| s.parse::<u32>()), parts.next()) {
+        (Some(Ok(amount)), Some(label)) if amount > 0 => (Some(amount), label.trim()),
+        _ => (None, args),
+    }
+}
+
 pub fn drop(container: &mut Container, mob_id: MobId, args: StrInput) -> Result<()> {
-    parser_owned_item(&container, mob_id, args)
+    let (amount, label) = parse_amount(args.plain_arguments());
+
+    parser_owned_item_label(&container, mob_id, label)
         .map_err(|err| {
             match err {
                 ParseItemError::ItemNotProvided => container
@@ -156,7 +188,197 @@ pub fn drop(container: &mut Container, mob_id: MobId, args: StrInput) -> Result<
 
             Error::InvalidArgumentFailure
         })
-        .and_then(|item_id| do_drop(container, mob_id, item_id))
+        .and_then(|item_id| do_drop(container, mob_id, item_id, amount))
+}
+
+/// split arguments like `sword to bob` by the first preposition found
+fn split_preposition(args: &str, prepositions: &[&str]) -> Option<(String, String)> {
+    let words: Vec<&str> = args.split_whitespace().collect();
+    let index = words.iter().position(|w| prepositions.contains(w))?;
+    if index == 0 || index + 1 >= words.len() {
+        return None;
+    }
+
+    Some((words[0..index].join(" "), words[index + 1..].join(" ")))
+}
+
+pub fn give(container: &mut Container, mob_id: MobId, args: StrInput) -> Result<()> {
+    let plain = args.plain_arguments();
+
+    // `give <item> to <mob>` or just `give <item> <mob>`
+    let (item_label, target_label) = match split_preposition(plain, &["to"]) {
+        Some(pair) => pair,
+        None => match plain.rfind(' ') {
+            Some(index) => (plain[..index].to_string(), plain[index + 1..].to_string()),
+            None => {
+                container.outputs.private(mob_id, comm::give_what());
+                return Err(Error::InvalidArgumentFailure);
+            }
+        },
+    };
+
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let target_id = mob::search_mobs_at(
+        &container.labels,
+        &container.locations,
+        &container.mobs,
+        room_id,
+        &target_label,
+    )
+    .into_iter()
+    .find(|id| *id != mob_id);
+
+    let target_id = match target_id {
+        Some(target_id) => target_id,
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::give_target_not_found(&target_label));
+            return Err(Error::InvalidArgumentFailure);
+        }
+    };
+
+    let (amount, item_label) = parse_amount(&item_label);
+    match parser_owned_item_label(&container, mob_id, item_label) {
+        Ok(item_id) => do_give(container, mob_id, item_id, target_id, amount),
+        Err(ParseItemError::ItemNotProvided) => {
+            container.outputs.private(mob_id, comm::give_what());
+            Err(Error::InvalidArgumentFailure)
+        }
+        Err(ParseItemError::ItemNotFound { label }) => {
+            container
+                .outputs
+                .private(mob_id, comm::give_item_not_found(label.as_str()));
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
+pub fn put(container: &mut Container, mob_id: MobId, args: StrInput) -> Result<()> {
+    let (item_label, storage_label) =
+        match split_preposition(args.plain_arguments(), &["in", "into", "at"]) {
+            Some(pair) => pair,
+            None => {
+                container.outputs.private(mob_id, comm::put_what());
+                return Err(Error::InvalidArgumentFailure);
+            }
+        };
+
+    // search first in the carried containers and then in the room
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let storage_id = [mob_id, room_id].iter().find_map(|location_id| {
+        inventory_service::search_one(
+            &container.labels,
+            &container.locations,
+            &container.items,
+            *location_id,
+            &storage_label,
+        )
+    });
+
+    let storage_id = match storage_id {
+        Some(storage_id) => storage_id,
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::put_container_not_found(&storage_label));
+            return Err(Error::InvalidArgumentFailure);
+        }
+    };
+
+    let (amount, item_label) = parse_amount(&item_label);
+    match parser_owned_item_label(&container, mob_id, item_label) {
+        Ok(item_id) => do_put(container, mob_id, item_id, storage_id, amount),
+        Err(ParseItemError::ItemNotProvided) => {
+            container.outputs.private(mob_id, comm::put_what());
+            Err(Error::InvalidArgumentFailure)
+        }
+        Err(ParseItemError::ItemNotFound { label }) => {
+            container
+                .outputs
+                .private(mob_id, comm::put_item_not_found(label.as_str()));
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
+pub fn consume(
+    container: &mut Container,
+    mob_id: MobId,
+    args: StrInput,
+    kind: ConsumableKind,
+) -> Result<()> {
+    let verb = get_consume_verb(kind);
+
+    match parser_owned_item(&container, mob_id, args) {
+        Ok(item_id) => do_consume(container, mob_id, item_id, kind),
+        Err(ParseItemError::ItemNotProvided) => {
+            container.outputs.private(mob_id, comm::consume_what(verb));
+            Err(Error::InvalidArgumentFailure)
+        }
+        Err(ParseItemError::ItemNotFound { label }) => {
+            container
+                .outputs
+                .private(mob_id, comm::consume_item_not_found(verb, label.as_str()));
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
+/// Items carried by the mob are preferred over the ones in the room
+pub fn use_item(container: &mut Container, mob_id: MobId, args: StrInput) -> Result<()> {
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let item_label = args.plain_arguments();
+
+    let found = parser_owned_item_label(&container, mob_id, item_label)
+        .or_else(|_| parser_owned_item_label(&container, room_id, item_label));
+
+    match found {
+        Ok(item_id) => scripts_service::use_item(container, mob_id, item_id),
+        Err(ParseItemError::ItemNotProvided) => {
+            container.outputs.private(mob_id, comm::use_what());
+            Err(Error::InvalidArgumentFailure)
+        }
+        Err(ParseItemError::ItemNotFound { label }) => {
+            container
+                .outputs
+                .private(mob_id, comm::use_item_not_found(label.as_str()));
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
+pub fn repair(container: &mut Container, mob_id: MobId, args: StrInput) -> Result<()> {
+    let plain = args.plain_arguments();
+    if plain.is_empty() {
+        container.outputs.private(mob_id, comm::repair_what());
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    // `repair <item> with <kit>` or `repair <item>` at a vendor
+    let (item_label, kit_label) = match split_preposition(plain, &["with"]) {
+        Some((item_label, kit_label)) => (item_label, Some(kit_label)),
+        None => (plain.to_string(), None),
+    };
+
+    let mut find = |label: &str| match parser_owned_item_label(&container, mob_id, label) {
+        Ok(item_id) => Ok(item_id),
+        Err(_) => {
+            container
+                .outputs
+                .private(mob_id, comm::repair_item_not_found(label));
+            Err(Error::InvalidArgumentFailure)
+        }
+    };
+
+    let item_id = find(item_label.as_str())?;
+    match kit_label {
+        Some(kit_label) => {
+            let kit_id = find(kit_label.as_str())?;
+            do_repair_with_kit(container, mob_id, item_id, kit_id)
+        }
+        None => input_handle_vendors::repair(container, mob_id, item_id),
+    }
 }
 
 pub fn strip(container: &mut Container, mob_id: MobId, args: StrInput) -> Result<()> {
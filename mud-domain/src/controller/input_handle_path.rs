@@ -0,0 +1,65 @@
+use crate::errors::{AsResult, Error, Result};
+use crate::game::comm;
+use crate::game::container::Container;
+use crate::game::mob::MobId;
+use crate::game::pathfinding;
+use crate::game::room::RoomId;
+use crate::utils::strinput::StrInput;
+
+pub fn path(container: &mut Container, mob_id: MobId, input: StrInput) -> Result<()> {
+    let room_id = parse_known_room(container, mob_id, input.plain_arguments())?;
+    let from_id = container.locations.get(mob_id).as_result()?;
+    let room_label = container.labels.get_label_f(room_id).to_string();
+
+    match pathfinding::find_path(container, from_id, room_id) {
+        Some(path) if path.is_empty() => {
+            container
+                .outputs
+                .private(mob_id, comm::path_already_there(&room_label));
+            Ok(())
+        }
+        Some(path) => {
+            let steps = path
+                .iter()
+                .map(|step| pathfinding::describe_step(container, step))
+                .collect();
+            container
+                .outputs
+                .private(mob_id, comm::path_show(&room_label, steps));
+            Ok(())
+        }
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::path_not_found(&room_label));
+            Err(Error::NotFoundFailure)
+        }
+    }
+}
+
+pub fn walk(container: &mut Container, mob_id: MobId, input: StrInput) -> Result<()> {
+    let args = input.plain_arguments();
+    let args = args.strip_prefix("to ").unwrap_or(args);
+    let room_id = parse_known_room(container, mob_id, args)?;
+    pathfinding::walk_to(container, mob_id, room_id)
+}
+
+/// players can only find the way into rooms they already visited
+fn parse_known_room(container: &mut Container, mob_id: MobId, args: &str) -> Result<RoomId> {
+    if args.is_empty() {
+        container
+            .outputs
+            .private(mob_id, comm::path_room_not_found(args));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    match pathfinding::search_known_rooms(container, mob_id, args).first() {
+        Some(room_id) => Ok(*room_id),
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::path_room_not_found(args));
+            Err(Error::NotFoundFailure)
+        }
+    }
+}
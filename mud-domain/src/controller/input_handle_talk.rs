@@ -0,0 +1,63 @@
+use crate::controller::ViewHandleCtx;
+use crate::errors::*;
+use crate::game::dialogues::Conversation;
+use crate::game::{actions_dialogue, comm, mob};
+use crate::utils::strinput::StrInput;
+
+/// talk <mob>
+pub fn talk(ctx: &mut ViewHandleCtx, input: &StrInput) -> Result<()> {
+    let target = input.plain_arguments();
+    let room_id = ctx.container.locations.get(ctx.mob_id).as_result()?;
+    let candidates = mob::search_mobs_at(
+        &ctx.container.labels,
+        &ctx.container.locations,
+        &ctx.container.mobs,
+        room_id,
+        target,
+    );
+
+    // prefer who has something to say
+    let mut candidates: Vec<_> = candidates
+        .into_iter()
+        .filter(|id| *id != ctx.mob_id)
+        .collect();
+    candidates.sort_by_key(|id| ctx.container.dialogues.get_talker(*id).is_none());
+
+    let npc_id = match candidates.first().cloned() {
+        Some(npc_id) => npc_id,
+        None => {
+            ctx.container
+                .outputs
+                .private(ctx.mob_id, comm::talk_target_not_found(target));
+            return Ok(());
+        }
+    };
+
+    let result = actions_dialogue::talk(ctx.container, ctx.mob_id, npc_id);
+    update_conversation(ctx, result)
+}
+
+/// numeric input while talking with someone
+pub fn choose(ctx: &mut ViewHandleCtx, choice: usize) -> Result<()> {
+    let conversation = ctx.conversation.clone().as_result()?;
+    let result = actions_dialogue::choose(ctx.container, ctx.mob_id, &conversation, choice);
+    update_conversation(ctx, result)
+}
+
+fn update_conversation(
+    ctx: &mut ViewHandleCtx,
+    result: Result<Option<Conversation>>,
+) -> Result<()> {
+    match result {
+        Ok(conversation) => {
+            *ctx.conversation = conversation;
+            Ok(())
+        }
+        // failures are already reported to the player, like invalid options
+        Err(e) if e.is_failure() => Ok(()),
+        Err(e) => {
+            *ctx.conversation = None;
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::errors::{AsResult, Error, Result};
+use crate::game::container::Container;
+use crate::game::item::ItemId;
+use crate::game::mob::MobId;
+use crate::game::prices::Money;
+use crate::game::{actions_trade, comm, inventory_service, mob};
+use crate::utils::strinput::StrInput;
+
+pub fn trade(container: &mut Container, mob_id: MobId, input: StrInput) -> Result<()> {
+    let args = StrInput(input.plain_arguments());
+
+    match args.first() {
+        "" => actions_trade::show(container, mob_id),
+        "confirm" => actions_trade::confirm(container, mob_id),
+        "cancel" => actions_trade::cancel(container, mob_id),
+        "offer" => {
+            let item_id = parse_item(container, mob_id, args.plain_arguments())?;
+            actions_trade::offer_item(container, mob_id, item_id)
+        }
+        "remove" => {
+            let item_id = parse_item(container, mob_id, args.plain_arguments())?;
+            actions_trade::remove_item(container, mob_id, item_id)
+        }
+        "money" => {
+            let value = args.plain_arguments();
+            match value.parse::<u32>() {
+                Ok(amount) => actions_trade::offer_money(container, mob_id, Money(amount)),
+                Err(_) => {
+                    container
+                        .outputs
+                        .private(mob_id, comm::trade_invalid_money(value));
+                    Err(Error::InvalidArgumentFailure)
+                }
+            }
+        }
+        _ => {
+            let target = args.as_str();
+            let room_id = container.locations.get(mob_id).as_result()?;
+            let candidates = mob::search_mobs_at(
+                &container.labels,
+                &container.locations,
+                &container.mobs,
+                room_id,
+                target,
+            );
+
+            match candidates.into_iter().find(|id| *id != mob_id) {
+                Some(target_id) => actions_trade::open(container, mob_id, target_id),
+                None => {
+                    container
+                        .outputs
+                        .private(mob_id, comm::trade_target_not_found(target));
+                    Err(Error::InvalidArgumentFailure)
+                }
+            }
+        }
+    }
+}
+
+fn parse_item(container: &mut Container, mob_id: MobId, label: &str) -> Result<ItemId> {
+    let found = inventory_service::search_one(
+        &container.labels,
+        &container.locations,
+        &container.items,
+        mob_id,
+        label,
+    );
+
+    found.ok_or_else(|| {
+        container
+            .outputs
+            .private(mob_id, comm::trade_item_not_found(label));
+        Error::InvalidArgumentFailure
+    })
+}
@@ -29,6 +29,13 @@ pub fn buy(container: &mut Container, mob_id: MobId, input: StrInput) -> Result<
         let trade = match parse_vendor_item(container, vendor_id, plain_arguments)? {
             Some(static_id) => static_id,
             None => {
+                // random generated items have no prefab, they are sold from vendor inventory
+                if let Some(item_id) =
+                    parse_vendor_random_item(container, vendor_id, plain_arguments)
+                {
+                    return actions_vendor::buy_item(container, mob_id, vendor_id, item_id);
+                }
+
                 container
                     .outputs
                     .private(mob_id, comm::vendor_buy_item_not_found(plain_arguments));
@@ -58,6 +65,24 @@ pub fn sell(container: &mut Container, mob_id: MobId, input: StrInput) -> Result
     }
 }
 
+pub fn repair(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Result<()> {
+    let location_id = container.locations.get(mob_id).as_result()?;
+    let vendor_id = container
+        .locations
+        .list_at(location_id)
+        .find(|&id| container.vendors.exist(id));
+
+    match vendor_id {
+        Some(vendor_id) => actions_vendor::repair(container, mob_id, item_id, vendor_id),
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::vendor_repair_no_vendor());
+            Err(Error::InvalidStateFailure)
+        }
+    }
+}
+
 fn find_vendor_at_mob_location(container: &mut Container, mob_id: MobId) -> Result<ObjId> {
     let location_id = match container.locations.get(mob_id) {
         Some(location_id) => location_id,
@@ -110,6 +135,23 @@ pub fn parse_vendor_item(
     Ok(found)
 }
 
+/// search by label in the random items sold by the vendor
+pub fn parse_vendor_random_item(
+    container: &Container,
+    vendor_id: ObjId,
+    input: &str,
+) -> Option<ItemId> {
+    actions_vendor::list_random_stock(container, vendor_id)
+        .into_iter()
+        .find(|&item_id| {
+            container
+                .labels
+                .get_label(item_id)
+                .map(|label| text::is_text_eq(label, input))
+                .unwrap_or(false)
+        })
+}
+
 #[cfg(test)]
 mod test {
     use crate::game::container::Container;
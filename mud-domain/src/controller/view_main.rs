@@ -2,21 +2,26 @@ use std::collections::HashSet;
 
 use commons::{ObjId, PlayerId};
 
-use super::{input_handle_items, input_handle_space, input_handle_vendors};
+use super::{
+    input_handle_auction, input_handle_bank, input_handle_items, input_handle_path,
+    input_handle_space, input_handle_talk, input_handle_trade, input_handle_vendors,
+};
 use crate::controller::{
     input_handle_command, input_handle_hire, ConnectionView, ConnectionViewAction, ViewHandleCtx,
 };
 use crate::errors::Error::NotFoundFailure;
 use crate::errors::{AsResult, Error, Result};
 use crate::game::comm;
-use crate::game::comm::{InventoryDesc, InventoryItemDesc};
+use crate::game::comm::{EquipDesc, InventoryDesc, InventoryItemDesc};
 use crate::game::container::Container;
 use crate::game::domain::Dir;
 use crate::game::location::search_at;
 use crate::game::mob::MobId;
+use crate::game::needs::ConsumableKind;
 use crate::game::outputs::Outputs;
+use crate::game::pvp::{self, PvpDenied, PvpRules};
 use crate::game::zone::Zones;
-use crate::game::{actions, location};
+use crate::game::{actions, actions_craft, actions_quest, combat, location};
 use crate::game::{actions_admin, inventory_service, mob};
 use crate::utils::strinput::StrInput;
 use commons::unwrap_or_continue;
@@ -31,6 +36,18 @@ fn get_inventory_desc(container: &Container, obj_id: ObjId) -> InventoryDesc {
 
     let total_weight = inventory_service::compute_total_weight(&items);
 
+    let mut equipments: Vec<EquipDesc> = items
+        .iter()
+        .filter(|item| equip.contains(&item.id))
+        .flat_map(|item| {
+            let label = container.labels.get_label_f(item.id);
+            item.get_equip_slots()
+                .into_iter()
+                .map(move |slot| EquipDesc { slot, label })
+        })
+        .collect();
+    equipments.sort_by_key(|equip| equip.slot);
+
     let items = items
         .into_iter()
         .map(|item| {
@@ -50,6 +67,7 @@ fn get_inventory_desc(container: &Container, obj_id: ObjId) -> InventoryDesc {
         max_weight,
         total_weight: total_weight,
         items: items,
+        equipments,
     }
 }
 
@@ -81,6 +99,14 @@ pub fn handle(mut ctx: ViewHandleCtx, input: &str) -> Result<ConnectionViewActio
         }
     }
 
+    match handle_talk(&mut ctx, &input) {
+        Ok(action) => return Ok(action),
+        Err(NotFoundFailure) => {}
+        Err(other) => {
+            log::warn!("{:?} fail processing command {:?}", ctx.mob_id, other);
+        }
+    }
+
     match handle_command(&mut ctx, &input) {
         Ok(action) => return Ok(action),
         Err(NotFoundFailure) => {}
@@ -96,6 +122,10 @@ pub fn handle(mut ctx: ViewHandleCtx, input: &str) -> Result<ConnectionViewActio
     let command_result = match input.as_str() {
         "l" | "look" => actions::look(container, mob_id),
 
+        "time" => actions::time(container, mob_id),
+
+        "date" => actions::date(container, mob_id),
+
         "n" => actions::move_dir(container, mob_id, Dir::N),
 
         "s" => actions::move_dir(container, mob_id, Dir::S),
@@ -121,13 +151,30 @@ pub fn handle(mut ctx: ViewHandleCtx, input: &str) -> Result<ConnectionViewActio
 
         "stand" => actions::stand(container, mob_id),
 
+        "flee" | "retreat" => combat::flee(container, mob_id, None),
+
+        _ if input.has_commands(&["flee", "retreat"]) => {
+            match Dir::parse(input.plain_arguments()) {
+                Ok(dir) => combat::flee(container, mob_id, Some(dir)),
+                Err(_) => {
+                    container.outputs.private(mob_id, comm::flee_fail_no_exit());
+                    Err(Error::InvalidArgumentFailure)
+                }
+            }
+        }
+
+        _ if input.has_command("wimpy") => input_handle_wimpy(container, mob_id, input),
+
         "stats" | "inv" | "score" => {
             let ctx = container.get_mob_ctx(mob_id).as_result()?;
-            let msg = comm::stats(
+            let mut msg = comm::stats(
                 ctx.mob.xp,
                 &ctx.mob.attributes,
                 &get_inventory_desc(container, ctx.mob.id),
             );
+            if let Some(needs) = &ctx.mob.needs {
+                msg.push_str(&comm::stats_needs(needs));
+            }
             container.outputs.private(mob_id, msg);
             Ok(())
         }
@@ -138,6 +185,24 @@ pub fn handle(mut ctx: ViewHandleCtx, input: &str) -> Result<ConnectionViewActio
 
         _ if input.has_commands(&["drop"]) => input_handle_items::drop(container, mob_id, input),
 
+        _ if input.has_command("eat") => {
+            input_handle_items::consume(container, mob_id, input, ConsumableKind::Food)
+        }
+
+        _ if input.has_command("drink") => {
+            input_handle_items::consume(container, mob_id, input, ConsumableKind::Drink)
+        }
+
+        _ if input.has_command("quaff") => {
+            input_handle_items::consume(container, mob_id, input, ConsumableKind::Potion)
+        }
+
+        _ if input.has_command("use") => input_handle_items::use_item(container, mob_id, input),
+
+        _ if input.has_commands(&["give"]) => input_handle_items::give(container, mob_id, input),
+
+        _ if input.has_commands(&["put"]) => input_handle_items::put(container, mob_id, input),
+
         _ if input.has_commands(&["remove"]) => input_handle_items::strip(container, mob_id, input),
 
         _ if input.has_commands(&["equip"]) => input_handle_items::equip(container, mob_id, input),
@@ -160,15 +225,8 @@ pub fn handle(mut ctx: ViewHandleCtx, input: &str) -> Result<ConnectionViewActio
 
             let candidate = mobs.first();
             match candidate {
-                Some(&target_mob_id) if !container.mobs.is_avatar(target_mob_id) => {
-                    let _ = actions::attack(container, mob_id, target_mob_id);
-                    Ok(())
-                }
-                Some(_) => {
-                    container
-                        .outputs
-                        .private(mob_id, comm::kill_can_not_kill_players(&target));
-                    Err(Error::InvalidArgumentFailure)
+                Some(&target_mob_id) => {
+                    input_handle_attack(container, mob_id, target, target_mob_id)
                 }
                 None => {
                     container
@@ -179,6 +237,8 @@ pub fn handle(mut ctx: ViewHandleCtx, input: &str) -> Result<ConnectionViewActio
             }
         }
 
+        _ if input.has_command("shoot") => input_handle_shoot(container, mob_id, input),
+
         _ if input.has_command("say") => {
             let msg = input.plain_arguments();
             actions::say(container, mob_id, msg)
@@ -205,7 +265,7 @@ pub fn handle(mut ctx: ViewHandleCtx, input: &str) -> Result<ConnectionViewActio
                         room_id,
                         comm::admin_suicide_others(mob_label),
                     );
-                    crate::game::combat::kill_mob(container, target_mob_id)
+                    crate::game::combat::kill_mob(container, target_mob_id, None)
                 }
                 _ => {
                     container
@@ -220,6 +280,10 @@ pub fn handle(mut ctx: ViewHandleCtx, input: &str) -> Result<ConnectionViewActio
 
         "map" => actions::show_map(container, mob_id),
 
+        _ if input.has_command("path") => input_handle_path::path(container, mob_id, input),
+
+        _ if input.has_command("walk") => input_handle_path::walk(container, mob_id, input),
+
         "move" => input_handle_space::move_list_targets(container, mob_id),
 
         _ if input.has_command("move") => input_handle_space::move_to(container, mob_id, &input),
@@ -234,12 +298,28 @@ pub fn handle(mut ctx: ViewHandleCtx, input: &str) -> Result<ConnectionViewActio
 
         _ if input.has_command("sell") => input_handle_vendors::sell(container, mob_id, input),
 
+        _ if input.has_command("repair") => input_handle_items::repair(container, mob_id, input),
+
         _ if input.has_command("hire") => input_handle_hire::hire(container, mob_id, input),
 
+        _ if input.has_command("trade") => input_handle_trade::trade(container, mob_id, input),
+
+        _ if input.has_command("auction") => {
+            input_handle_auction::auction(container, mob_id, input)
+        }
+
+        _ if input.has_command("bank") => input_handle_bank::bank(container, mob_id, input),
+
         _ if input.has_command("extract") => input_handle_extract(container, mob_id, input),
 
+        _ if input.has_command("craft") => input_handle_craft(container, mob_id, input),
+
         _ if input.has_command("travel") => input_handle_travel(container, mob_id, input),
 
+        "quests" => actions_quest::list(container, mob_id),
+
+        _ if input.has_command("quest") => input_handle_quest(container, mob_id, input),
+
         _ => {
             container
                 .outputs
@@ -347,6 +427,11 @@ pub fn handle_general(ctx: &mut ViewHandleCtx, input: &StrInput) -> Result<Conne
             Ok(ConnectionViewAction::None)
         }
 
+        "pvp" => {
+            input_handle_pvp(ctx, input)?;
+            Ok(ConnectionViewAction::None)
+        }
+
         "uptime" => {
             ctx.container
                 .outputs
@@ -369,6 +454,145 @@ pub fn handle_command(ctx: &mut ViewHandleCtx, input: &StrInput) -> Result<Conne
     }
 }
 
+pub fn handle_talk(ctx: &mut ViewHandleCtx, input: &StrInput) -> Result<ConnectionViewAction> {
+    match input.first() {
+        "talk" => {
+            input_handle_talk::talk(ctx, input)?;
+            Ok(ConnectionViewAction::None)
+        }
+
+        // numbers are answers while talking with someone
+        first if ctx.conversation.is_some() => match first.parse::<usize>() {
+            Ok(choice) => {
+                input_handle_talk::choose(ctx, choice)?;
+                Ok(ConnectionViewAction::None)
+            }
+            Err(_) => Err(NotFoundFailure),
+        },
+
+        _ => Err(NotFoundFailure),
+    }
+}
+
+pub fn input_handle_pvp(ctx: &mut ViewHandleCtx, input: &StrInput) -> Result<()> {
+    match input.plain_arguments() {
+        "on" => actions::set_pvp(ctx.container, ctx.player_id, ctx.mob_id, true),
+        "off" => actions::set_pvp(ctx.container, ctx.player_id, ctx.mob_id, false),
+        "" => {
+            let enabled = ctx.container.players.get(ctx.player_id).as_result()?.pvp;
+            let rules = ctx
+                .container
+                .locations
+                .get(ctx.mob_id)
+                .map(|location_id| pvp::resolve_rules(ctx.container, location_id))
+                .unwrap_or(PvpRules::new());
+
+            let msg = comm::pvp_show(enabled, rules.policy);
+            ctx.container.outputs.private(ctx.mob_id, msg);
+            Ok(())
+        }
+        _ => {
+            ctx.container
+                .outputs
+                .private(ctx.mob_id, comm::pvp_invalid_argument());
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
+fn input_handle_attack(
+    container: &mut Container,
+    mob_id: MobId,
+    target: &str,
+    target_mob_id: MobId,
+) -> Result<()> {
+    let msg = match pvp::check_attack(container, mob_id, target_mob_id) {
+        Ok(()) => {
+            let _ = actions::attack(container, mob_id, target_mob_id);
+            return Ok(());
+        }
+        Err(PvpDenied::Disabled) => comm::kill_can_not_kill_players(target),
+        Err(PvpDenied::Safe) => comm::kill_can_not_kill_safe_zone(target),
+        Err(PvpDenied::NotFlagged) => comm::kill_can_not_kill_pvp_not_flagged(target),
+    };
+
+    container.outputs.private(mob_id, msg);
+    Err(Error::InvalidArgumentFailure)
+}
+
+/// shoot <dir> <target>
+pub fn input_handle_shoot(container: &mut Container, mob_id: MobId, input: StrInput) -> Result<()> {
+    let args = input.parse_arguments();
+    let (dir, target) = match (args.get(0).map(|s| Dir::parse(s)), args.get(1)) {
+        (Some(Ok(dir)), Some(&target)) => (dir, target),
+        _ => {
+            container
+                .outputs
+                .private(mob_id, comm::kill_target_not_found(input.plain_arguments()));
+            return Err(Error::InvalidArgumentFailure);
+        }
+    };
+
+    let has_ranged_weapon = mob::get_weapon(container, mob_id)
+        .map(|weapon| weapon.range > 0)
+        .unwrap_or(false);
+
+    if !has_ranged_weapon {
+        container
+            .outputs
+            .private(mob_id, comm::shoot_no_ranged_weapon());
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    let target_room_id = container
+        .locations
+        .get(mob_id)
+        .and_then(|room_id| container.rooms.get(room_id))
+        .and_then(|room| room.get_exit(&dir));
+
+    let candidate = target_room_id.and_then(|room_id| {
+        mob::search_mobs_at(
+            &container.labels,
+            &container.locations,
+            &container.mobs,
+            room_id,
+            target,
+        )
+        .into_iter()
+        .next()
+    });
+
+    match candidate {
+        Some(target_mob_id) => input_handle_attack(container, mob_id, target, target_mob_id),
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::kill_target_not_found(target));
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
+pub fn input_handle_wimpy(container: &mut Container, mob_id: MobId, input: StrInput) -> Result<()> {
+    match input.plain_arguments() {
+        "" => {
+            let wimpy = container.mobs.get(mob_id).as_result()?.wimpy;
+            container.outputs.private(mob_id, comm::wimpy_set(wimpy));
+            Ok(())
+        }
+        "off" => actions::set_wimpy(container, mob_id, None),
+        value => match value.parse::<u32>() {
+            Ok(value) => actions::set_wimpy(container, mob_id, Some(value)),
+            Err(_) => {
+                container
+                    .outputs
+                    .private(mob_id, comm::wimpy_invalid_argument());
+                Err(Error::InvalidArgumentFailure)
+            }
+        },
+    }
+}
+
 pub fn input_handle_extract(
     container: &mut Container,
     mob_id: MobId,
@@ -401,6 +625,23 @@ pub fn input_handle_extract(
     }
 }
 
+pub fn input_handle_craft(container: &mut Container, mob_id: MobId, args: StrInput) -> Result<()> {
+    let label = args.plain_arguments();
+    if label.is_empty() {
+        return actions_craft::list(container, mob_id);
+    }
+
+    match actions_craft::find_recipe(container, label) {
+        Some(recipe_id) => actions_craft::craft(container, mob_id, recipe_id),
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::craft_invalid_recipe(label));
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
 pub fn input_handle_travel(container: &mut Container, mob_id: MobId, args: StrInput) -> Result<()> {
     let location_id = container.locations.get(mob_id).as_result()?;
     let zone_id = container.find_zone(location_id).as_result()?;
@@ -452,3 +693,28 @@ pub fn input_handle_travel(container: &mut Container, mob_id: MobId, args: StrIn
 
     actions::travel(container, mob_id, founds.first().unwrap().clone())
 }
+
+/// quest abandon <quest>
+pub fn input_handle_quest(container: &mut Container, mob_id: MobId, args: StrInput) -> Result<()> {
+    let plain_args = args.plain_arguments();
+    let label = match plain_args.strip_prefix("abandon ") {
+        Some(label) if !label.is_empty() => label,
+        _ => {
+            container
+                .outputs
+                .private(mob_id, comm::quest_invalid_command());
+            return Err(Error::InvalidArgumentFailure);
+        }
+    };
+
+    let player_id = container.players.find_from_mob(mob_id).as_result()?;
+    match actions_quest::find_active(container, player_id, label) {
+        Some(quest_id) => actions_quest::abandon(container, mob_id, quest_id),
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::quest_not_found(label));
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
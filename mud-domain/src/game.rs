@@ -13,20 +13,29 @@ use serde::Deserialize;
 
 pub mod actions;
 pub mod actions_admin;
+pub mod actions_auction;
+pub mod actions_bank;
 pub mod actions_command;
+pub mod actions_craft;
+pub mod actions_dialogue;
 pub mod actions_hire;
 pub mod actions_items;
+pub mod actions_quest;
 pub mod actions_ships;
+pub mod actions_trade;
 pub mod actions_vendor;
 pub mod ai;
 pub mod astro_bodies;
+pub mod auctions;
 pub mod avatars;
 pub mod builder;
+pub mod calendar;
 pub mod combat;
 pub mod comm;
 pub mod config;
 pub mod container;
 pub mod corpse;
+pub mod dialogues;
 pub mod domain;
 pub mod equip;
 pub mod extractable;
@@ -41,16 +50,25 @@ pub mod main_loop;
 pub mod market;
 pub mod memory;
 pub mod mob;
+pub mod needs;
 pub mod obj;
+pub mod orders;
 pub mod outputs;
 pub mod ownership;
+pub mod pathfinding;
 pub mod player;
 pub mod pos;
+pub mod pvp;
+pub mod quests;
 pub mod prices;
+pub mod random_items;
 pub mod random_rooms;
+pub mod recipes;
 pub mod repo;
 pub mod room;
 pub mod rooms_zones;
+pub mod scripts;
+pub mod scripts_service;
 pub mod ships;
 pub mod space_utils;
 pub mod spawn;
@@ -60,8 +78,11 @@ pub mod system;
 pub mod tags;
 pub mod template;
 pub mod timer;
+pub mod trades;
 pub mod triggers;
+pub mod vaults;
 pub mod vendors;
+pub mod weather;
 pub mod zone;
 pub mod travel;
 // EOF pub mods
@@ -130,7 +151,7 @@ impl Game {
 
         let mob_id = self.container.players.get_mob(player_id).as_result()?;
 
-        crate::game::combat::kill_mob(&mut self.container, mob_id)
+        crate::game::combat::kill_mob(&mut self.container, mob_id, None)
     }
 }
 
@@ -4,13 +4,18 @@ use super::domain::*;
 use super::mob::*;
 use crate::errors::Error::NotFoundFailure;
 use crate::errors::{AsResult, Error, Result};
+use crate::game::calendar::{self, DayPeriod};
 use crate::game::comm::{RoomMap, RoomMapCell};
 use crate::game::item::ItemId;
 use crate::game::loader::dto::StaticId;
 use crate::game::location::LocationId;
 use crate::game::memory::Memories;
+use crate::game::orders;
+use crate::game::pvp;
 use crate::game::room::RoomRepository;
+use crate::game::scripts_service;
 use crate::game::space_utils;
+use crate::game::weather;
 use commons::{ObjId, PlayerId};
 
 use crate::game::zone::ZoneId;
@@ -100,6 +105,14 @@ fn look_description(container: &Container, mob_id: MobId) -> Result<String> {
     let room = container.rooms.get(room_id).as_result()?;
     let room_label = container.labels.get(room_id).as_result()?;
     let can_exit = super::actions::can_out(container, mob_id);
+    let period = calendar::get_outdoor_period(container, room_id);
+    let weather = weather::get_outdoor_weather(container, room_id);
+
+    // outdoor rooms can have a different description during the night
+    let room_desc = match (period, &room.night_desc) {
+        (Some(DayPeriod::Night), Some(night_desc)) => night_desc.as_str(),
+        _ => room_label.desc.as_str(),
+    };
 
     let visible_objects = comm::get_visible_objects(container, mob_id, room.id);
     let visible_labels = visible_objects
@@ -110,10 +123,12 @@ fn look_description(container: &Container, mob_id: MobId) -> Result<String> {
     comm::look_description(
         room_id.as_u32(),
         &room_label.label,
-        &room_label.desc,
+        room_desc,
         room.exits.iter().cloned().map(|(dir, _)| dir).collect(),
         can_exit,
         visible_labels,
+        period,
+        weather,
     )
 }
 
@@ -124,6 +139,22 @@ pub fn look(container: &mut Container, mob_id: MobId) -> Result<()> {
     Ok(())
 }
 
+pub fn time(container: &mut Container, mob_id: MobId) -> Result<()> {
+    let calendar = &container.config.calendar;
+    let date = calendar.date(container.time.total);
+    let msg = comm::calendar_time(&date, calendar.period(container.time.total));
+    container.outputs.private(mob_id, msg);
+    Ok(())
+}
+
+pub fn date(container: &mut Container, mob_id: MobId) -> Result<()> {
+    let calendar = &container.config.calendar;
+    let date = calendar.date(container.time.total);
+    let msg = comm::calendar_date(&date, calendar.season_name(&date));
+    container.outputs.private(mob_id, msg);
+    Ok(())
+}
+
 // TODO: do not allow to say empty
 pub fn say(container: &mut Container, mob_id: MobId, msg: &str) -> Result<()> {
     let room_id = container.locations.get(mob_id).as_result()?;
@@ -134,7 +165,8 @@ pub fn say(container: &mut Container, mob_id: MobId, msg: &str) -> Result<()> {
     container.outputs.private(mob_id, player_msg);
     container.outputs.broadcast(Some(mob_id), room_id, room_msg);
 
-    Ok(())
+    scripts_service::on_say(container, mob_id, msg);
+    orders::hear(container, mob_id, msg)
 }
 
 pub fn move_dir(container: &mut Container, mob_id: MobId, dir: Dir) -> Result<()> {
@@ -215,6 +247,29 @@ pub fn attack(container: &mut Container, mob_id: MobId, target_mob_id: MobId) ->
         .set_mob_attack_target(mob_id, target_mob_id)
         .unwrap();
 
+    pvp::on_attack(container, mob_id, target_mob_id);
+
+    Ok(())
+}
+
+pub fn set_pvp(
+    container: &mut Container,
+    player_id: PlayerId,
+    mob_id: MobId,
+    enabled: bool,
+) -> Result<()> {
+    container
+        .players
+        .update(player_id, |player| player.pvp = enabled)?;
+
+    container.outputs.private(mob_id, comm::pvp_set(enabled));
+
+    Ok(())
+}
+
+pub fn set_wimpy(container: &mut Container, mob_id: MobId, wimpy: Option<u32>) -> Result<()> {
+    container.mobs.update(mob_id, |mob| mob.wimpy = wimpy)?;
+    container.outputs.private(mob_id, comm::wimpy_set(wimpy));
     Ok(())
 }
 
@@ -677,6 +732,7 @@ mod test {
     use crate::game::domain::Dir;
     use crate::game::memory::Memories;
     use crate::game::mob::MobId;
+    use crate::game::pvp;
     use crate::game::room::{Room, RoomRepository};
     use commons::ObjId;
 
@@ -0,0 +1,539 @@
+use crate::errors::{AsResult, Error, Result};
+use crate::game::auctions::{AuctionBid, AuctionListing, AuctionLot};
+use crate::game::comm::AuctionListDesc;
+use crate::game::container::Container;
+use crate::game::item::{ItemId, Weight};
+use crate::game::loader::Loader;
+use crate::game::market::MarketAuction;
+use crate::game::mob::MobId;
+use crate::game::prices::Money;
+use crate::game::{comm, inventory_service};
+use commons::PlayerId;
+
+/// Find auction fees from any vendor in the mob room that use a market with auction
+pub fn find_auction(container: &Container, mob_id: MobId) -> Option<MarketAuction> {
+    let room_id = container.locations.get(mob_id)?;
+    container
+        .locations
+        .list_at(room_id)
+        .flat_map(|id| container.vendors.get(id))
+        .flat_map(|vendor| vendor.market_id)
+        .flat_map(|market_id| container.markets.get(market_id))
+        .flat_map(|market| market.auction.clone())
+        .next()
+}
+
+pub fn list(container: &mut Container, mob_id: MobId) -> Result<()> {
+    let player_id = get_player_or_fail(container, mob_id)?;
+    get_auction_or_fail(container, mob_id)?;
+
+    let total_time = container.time.total;
+    let listings = container
+        .auctions
+        .list_listed()
+        .into_iter()
+        .flat_map(|lot| {
+            let listing = lot.listing.as_ref()?;
+            Some(AuctionListDesc {
+                id: lot.id.as_u32(),
+                label: container.labels.get_label_f(lot.id),
+                bid: listing.bid.as_ref().map(|bid| bid.amount),
+                buyout: listing.buyout,
+                expire_in: listing.expire.sub(total_time),
+                is_owner: lot.owner_id == player_id,
+            })
+        })
+        .collect();
+
+    let to_collect = container.auctions.list_to_collect(player_id).len();
+
+    let msg = comm::auction_list(&listings, to_collect);
+    container.outputs.private(mob_id, msg);
+    Ok(())
+}
+
+pub fn sell(
+    container: &mut Container,
+    mob_id: MobId,
+    item_id: ItemId,
+    buyout: Money,
+) -> Result<()> {
+    let player_id = get_player_or_fail(container, mob_id)?;
+    let auction = get_auction_or_fail(container, mob_id)?;
+    let item_label = container.labels.get_label_f(item_id).to_string();
+
+    let is_money = container
+        .items
+        .get(item_id)
+        .map(|item| item.flags.is_money)
+        .unwrap_or(true);
+
+    if is_money
+        || container.locations.get(item_id) != Some(mob_id)
+        || container.equips.is_equipped(mob_id, item_id)
+        || container.trades.is_offered(item_id)
+    {
+        container
+            .outputs
+            .private(mob_id, comm::auction_sell_invalid_item(&item_label));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    if buyout.as_u32() == 0 {
+        container
+            .outputs
+            .private(mob_id, comm::auction_invalid_price());
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    if auction.listing_fee.as_u32() > 0 {
+        let money = inventory_service::get_money(container, mob_id)?;
+        if money.as_u32() < auction.listing_fee.as_u32() {
+            container
+                .outputs
+                .private(mob_id, comm::auction_not_enough_money(auction.listing_fee));
+            return Err(Error::InvalidStateFailure);
+        }
+
+        inventory_service::remove_money(container, mob_id, auction.listing_fee)?;
+    }
+
+    container.locations.remove(item_id);
+    inventory_service::update_inventory_weight(container, mob_id)?;
+
+    container.auctions.add(AuctionLot {
+        id: item_id,
+        owner_id: player_id,
+        listing: Some(AuctionListing {
+            buyout,
+            bid: None,
+            expire: container.time.total + auction.duration,
+            sale_fee: auction.sale_fee,
+        }),
+    })?;
+
+    container.outputs.private(
+        mob_id,
+        comm::auction_listed(&item_label, buyout, auction.listing_fee),
+    );
+    Ok(())
+}
+
+pub fn bid(container: &mut Container, mob_id: MobId, item_id: ItemId, amount: Money) -> Result<()> {
+    let player_id = get_player_or_fail(container, mob_id)?;
+    get_auction_or_fail(container, mob_id)?;
+    let listing = get_listing_or_fail(container, mob_id, player_id, item_id)?;
+
+    if amount.as_u32() >= listing.buyout.as_u32() {
+        return buyout(container, mob_id, item_id);
+    }
+
+    let min_bid = listing.min_bid();
+    if amount.as_u32() < min_bid.as_u32() {
+        container
+            .outputs
+            .private(mob_id, comm::auction_bid_too_low(min_bid));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    pay(container, mob_id, amount)?;
+    refund_bid(container, item_id)?;
+
+    let listing = container
+        .auctions
+        .get_mut(item_id)
+        .and_then(|lot| lot.listing.as_mut())
+        .as_result()?;
+
+    listing.bid = Some(AuctionBid {
+        bidder_id: player_id,
+        amount,
+    });
+
+    let item_label = container.labels.get_label_f(item_id);
+    container
+        .outputs
+        .private(mob_id, comm::auction_bid(item_label, amount));
+    Ok(())
+}
+
+pub fn buyout(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Result<()> {
+    let player_id = get_player_or_fail(container, mob_id)?;
+    get_auction_or_fail(container, mob_id)?;
+    let listing = get_listing_or_fail(container, mob_id, player_id, item_id)?;
+
+    pay(container, mob_id, listing.buyout)?;
+    refund_bid(container, item_id)?;
+    complete_sale(container, item_id, player_id, listing.buyout)
+}
+
+/// Seller can cancel a listing that still has no bids
+pub fn cancel(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Result<()> {
+    let player_id = get_player_or_fail(container, mob_id)?;
+    get_auction_or_fail(container, mob_id)?;
+
+    let lot = match container.auctions.get_mut(item_id) {
+        Some(lot) if lot.owner_id == player_id && lot.is_listed() => lot,
+        _ => {
+            container.outputs.private(mob_id, comm::auction_not_found());
+            return Err(Error::InvalidArgumentFailure);
+        }
+    };
+
+    if lot.listing.as_ref().and_then(|l| l.bid.as_ref()).is_some() {
+        container
+            .outputs
+            .private(mob_id, comm::auction_cancel_has_bids());
+        return Err(Error::InvalidStateFailure);
+    }
+
+    lot.listing = None;
+
+    let item_label = container.labels.get_label_f(item_id);
+    container
+        .outputs
+        .private(mob_id, comm::auction_cancelled(item_label));
+    Ok(())
+}
+
+/// Move all sold items, proceeds and expired items owned by the player into the mob inventory
+pub fn collect(container: &mut Container, mob_id: MobId) -> Result<()> {
+    let player_id = get_player_or_fail(container, mob_id)?;
+    get_auction_or_fail(container, mob_id)?;
+
+    let lots = container.auctions.list_to_collect(player_id);
+    if lots.is_empty() {
+        container
+            .outputs
+            .private(mob_id, comm::auction_nothing_to_collect());
+        return Ok(());
+    }
+
+    for item_id in lots {
+        let item = container.items.get(item_id).as_result()?;
+        let item_label = container.labels.get_label_f(item_id).to_string();
+
+        if item.flags.is_money {
+            let amount = Money(item.amount);
+            container.auctions.remove(item_id);
+            inventory_service::add(container, item_id, mob_id)?;
+            container
+                .outputs
+                .private(mob_id, comm::auction_collect_money(amount));
+            continue;
+        }
+
+        let weight = get_deep_weight(container, item_id);
+        if !inventory_service::can_add_weight(container, mob_id, weight)? {
+            container
+                .outputs
+                .private(mob_id, comm::auction_collect_too_heavy(&item_label));
+            continue;
+        }
+
+        container.auctions.remove(item_id);
+        inventory_service::add(container, item_id, mob_id)?;
+        inventory_service::update_inventory_weight(container, mob_id)?;
+        container
+            .outputs
+            .private(mob_id, comm::auction_collect_item(&item_label));
+    }
+
+    Ok(())
+}
+
+/// Close a expired listing, the item is sold to the highest bidder or return to the seller
+pub fn expire(container: &mut Container, item_id: ItemId) -> Result<()> {
+    let lot = container.auctions.get(item_id).as_result()?;
+    let seller_id = lot.owner_id;
+    let listing = lot.listing.clone().as_result()?;
+
+    match listing.bid {
+        Some(bid) => complete_sale(container, item_id, bid.bidder_id, bid.amount),
+        None => {
+            container.auctions.get_mut(item_id).as_result()?.listing = None;
+            let item_label = container.labels.get_label_f(item_id).to_string();
+            notify(container, seller_id, comm::auction_expired(&item_label));
+            Ok(())
+        }
+    }
+}
+
+fn complete_sale(
+    container: &mut Container,
+    item_id: ItemId,
+    buyer_id: PlayerId,
+    price: Money,
+) -> Result<()> {
+    let lot = container.auctions.get_mut(item_id).as_result()?;
+    let listing = lot.listing.take().as_result()?;
+    let seller_id = lot.owner_id;
+    lot.owner_id = buyer_id;
+
+    let fee = listing.compute_fee(price);
+    let proceeds = Money(price.as_u32() - fee.as_u32());
+    if proceeds.as_u32() > 0 {
+        add_money_lot(container, seller_id, proceeds)?;
+    }
+
+    let item_label = container.labels.get_label_f(item_id).to_string();
+    notify(
+        container,
+        seller_id,
+        comm::auction_sold(&item_label, price, fee),
+    );
+    notify(container, buyer_id, comm::auction_won(&item_label, price));
+    Ok(())
+}
+
+fn refund_bid(container: &mut Container, item_id: ItemId) -> Result<()> {
+    let bid = container
+        .auctions
+        .get_mut(item_id)
+        .and_then(|lot| lot.listing.as_mut())
+        .and_then(|listing| listing.bid.take());
+
+    if let Some(bid) = bid {
+        add_money_lot(container, bid.bidder_id, bid.amount)?;
+        let item_label = container.labels.get_label_f(item_id).to_string();
+        notify(container, bid.bidder_id, comm::auction_outbid(&item_label));
+    }
+
+    Ok(())
+}
+
+fn add_money_lot(container: &mut Container, owner_id: PlayerId, amount: Money) -> Result<()> {
+    let money_id = container.config.money_id.as_result()?;
+    let item_id = Loader::instantiate(container, money_id)?;
+    container.items.get_mut(item_id).as_result()?.amount = amount.as_u32();
+    container.auctions.add(AuctionLot {
+        id: item_id,
+        owner_id,
+        listing: None,
+    })
+}
+
+fn pay(container: &mut Container, mob_id: MobId, amount: Money) -> Result<()> {
+    let money = inventory_service::get_money(container, mob_id)?;
+    if money.as_u32() < amount.as_u32() {
+        container
+            .outputs
+            .private(mob_id, comm::auction_not_enough_money(amount));
+        return Err(Error::InvalidStateFailure);
+    }
+
+    inventory_service::remove_money(container, mob_id, amount)?;
+    Ok(())
+}
+
+fn notify(container: &mut Container, player_id: PlayerId, msg: String) {
+    if let Some(mob_id) = container.players.get_mob(player_id) {
+        container.outputs.private(mob_id, msg);
+    }
+}
+
+fn get_deep_weight(container: &Container, item_id: ItemId) -> Weight {
+    let mut ids = container.locations.list_deep_at(item_id);
+    ids.push(item_id);
+    ids.into_iter()
+        .flat_map(|id| container.items.get(id).and_then(|item| item.weight))
+        .sum()
+}
+
+fn get_listing_or_fail(
+    container: &mut Container,
+    mob_id: MobId,
+    player_id: PlayerId,
+    item_id: ItemId,
+) -> Result<AuctionListing> {
+    match container.auctions.get(item_id) {
+        Some(lot) if lot.owner_id == player_id && lot.is_listed() => {
+            container.outputs.private(mob_id, comm::auction_own_item());
+            Err(Error::InvalidArgumentFailure)
+        }
+        Some(AuctionLot {
+            listing: Some(listing),
+            ..
+        }) => Ok(listing.clone()),
+        _ => {
+            container.outputs.private(mob_id, comm::auction_not_found());
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
+fn get_player_or_fail(container: &mut Container, mob_id: MobId) -> Result<PlayerId> {
+    container.players.find_from_mob(mob_id).ok_or_else(|| {
+        container
+            .outputs
+            .private(mob_id, comm::auction_not_available());
+        Error::InvalidStateFailure
+    })
+}
+
+fn get_auction_or_fail(container: &mut Container, mob_id: MobId) -> Result<MarketAuction> {
+    find_auction(container, mob_id).ok_or_else(|| {
+        container
+            .outputs
+            .private(mob_id, comm::auction_not_available());
+        Error::InvalidStateFailure
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::builder;
+    use crate::game::inventory::Inventory;
+    use commons::{DeltaTime, ObjId};
+
+    const SCENERY: &str = r#"
+cfg {
+  avatar_mob: 2
+  initial_room: 0
+  money_id: 1
+}
+objects.0 {
+  id: 0
+  label: "auction house"
+  room {}
+}
+objects.1 {
+  id: 1
+  label: "auctioneer"
+  parent: 0
+  vendor.market_id: 2
+}
+objects.2 {
+  id: 2
+  label: "market"
+  market {
+    trades: []
+    auction {
+      listing_fee: 1
+      sale_fee: 0.1
+      duration: 10.0
+    }
+  }
+}
+prefabs.1 {
+  id: 1
+  label: "gold"
+  item {
+    amount: 1
+    flags.money = true
+  }
+}
+"#;
+
+    struct Scenery {
+        container: Container,
+        seller_id: MobId,
+        buyer_id: MobId,
+        item_id: ItemId,
+    }
+
+    fn add_player(container: &mut Container, login: &str) -> MobId {
+        let mob_id = builder::add_mob(container, login, ObjId(0));
+        container.inventories.add(Inventory::new(mob_id)).unwrap();
+        let player_id = container.objects.create();
+        container
+            .players
+            .create(player_id, login.to_string(), mob_id);
+        inventory_service::add_money(container, mob_id, Money(100)).unwrap();
+        mob_id
+    }
+
+    fn setup() -> Scenery {
+        let mut container = Container::new();
+        Loader::load_hocon(&mut container, SCENERY).unwrap();
+
+        let seller_id = add_player(&mut container, "seller");
+        let buyer_id = add_player(&mut container, "buyer");
+        let item_id = builder::add_item(&mut container, "sword", seller_id);
+
+        Scenery {
+            container,
+            seller_id,
+            buyer_id,
+            item_id,
+        }
+    }
+
+    fn get_money(container: &Container, mob_id: MobId) -> u32 {
+        inventory_service::get_money(container, mob_id)
+            .unwrap()
+            .as_u32()
+    }
+
+    #[test]
+    fn test_auction_buyout_and_collect() {
+        let Scenery {
+            mut container,
+            seller_id,
+            buyer_id,
+            item_id,
+        } = setup();
+
+        sell(&mut container, seller_id, item_id, Money(50)).unwrap();
+        assert_eq!(99, get_money(&container, seller_id));
+        assert_eq!(None, container.locations.get(item_id));
+
+        // can not buy own item
+        assert!(buyout(&mut container, seller_id, item_id).is_err());
+
+        buyout(&mut container, buyer_id, item_id).unwrap();
+        assert_eq!(50, get_money(&container, buyer_id));
+
+        collect(&mut container, buyer_id).unwrap();
+        assert_eq!(Some(buyer_id), container.locations.get(item_id));
+
+        collect(&mut container, seller_id).unwrap();
+        assert_eq!(99 + 45, get_money(&container, seller_id));
+    }
+
+    #[test]
+    fn test_auction_outbid_should_refund_and_expire_should_sell_to_bidder() {
+        let Scenery {
+            mut container,
+            seller_id,
+            buyer_id,
+            item_id,
+        } = setup();
+
+        let other_id = add_player(&mut container, "other");
+
+        sell(&mut container, seller_id, item_id, Money(50)).unwrap();
+        bid(&mut container, other_id, item_id, Money(10)).unwrap();
+        assert!(bid(&mut container, buyer_id, item_id, Money(10)).is_err());
+        bid(&mut container, buyer_id, item_id, Money(20)).unwrap();
+
+        collect(&mut container, other_id).unwrap();
+        assert_eq!(100, get_money(&container, other_id));
+
+        container.time.total = container.time.total + DeltaTime(11.0);
+        crate::game::system::auction_system::run(&mut container);
+
+        collect(&mut container, buyer_id).unwrap();
+        assert_eq!(Some(buyer_id), container.locations.get(item_id));
+        assert_eq!(80, get_money(&container, buyer_id));
+    }
+
+    #[test]
+    fn test_auction_expire_without_bids_should_return_to_seller() {
+        let Scenery {
+            mut container,
+            seller_id,
+            item_id,
+            ..
+        } = setup();
+
+        sell(&mut container, seller_id, item_id, Money(50)).unwrap();
+        container.time.total = container.time.total + DeltaTime(11.0);
+        crate::game::system::auction_system::run(&mut container);
+
+        collect(&mut container, seller_id).unwrap();
+        assert_eq!(Some(seller_id), container.locations.get(item_id));
+        assert!(container.auctions.get(item_id).is_none());
+    }
+}
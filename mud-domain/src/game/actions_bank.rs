@@ -0,0 +1,331 @@
+use crate::errors::{Error, Result};
+use crate::game::actions_items::get_amount_label;
+use crate::game::container::Container;
+use crate::game::item::ItemId;
+use crate::game::mob::MobId;
+use crate::game::prices::Money;
+use crate::game::vaults::VAULT_MAX_ITEMS;
+use crate::game::{comm, inventory_service};
+use commons::{ObjId, PlayerId};
+
+/// true if the mob is in a room with bank
+pub fn is_at_bank(container: &Container, mob_id: MobId) -> bool {
+    container
+        .locations
+        .get(mob_id)
+        .and_then(|room_id| container.rooms.get(room_id))
+        .map(|room| room.is_bank)
+        .unwrap_or(false)
+}
+
+/// object where the player vault items are located
+pub fn get_storage_id(container: &Container, player_id: PlayerId) -> Option<ObjId> {
+    container
+        .vaults
+        .get(player_id)
+        .and_then(|vault| vault.storage_id)
+}
+
+/// items stored in the player vault
+pub fn list_items(container: &Container, player_id: PlayerId) -> Vec<ItemId> {
+    let storage_id = match get_storage_id(container, player_id) {
+        Some(storage_id) => storage_id,
+        None => return vec![],
+    };
+
+    let mut items: Vec<ItemId> = container
+        .locations
+        .list_at(storage_id)
+        .filter(|id| container.items.exists(*id))
+        .collect();
+    items.sort();
+    items
+}
+
+fn get_or_create_storage(container: &mut Container, player_id: PlayerId) -> ObjId {
+    if let Some(storage_id) = get_storage_id(container, player_id) {
+        return storage_id;
+    }
+
+    let storage_id = container.objects.create();
+    container.vaults.get_or_create(player_id).storage_id = Some(storage_id);
+    storage_id
+}
+
+pub fn show(container: &mut Container, mob_id: MobId) -> Result<()> {
+    let player_id = get_player_at_bank_or_fail(container, mob_id)?;
+
+    let balance = container
+        .vaults
+        .get(player_id)
+        .map(|vault| vault.money)
+        .unwrap_or(Money(0));
+
+    let items = list_items(container, player_id)
+        .into_iter()
+        .map(|id| get_amount_label(container, id))
+        .collect();
+
+    let msg = comm::bank_show(balance, &items, VAULT_MAX_ITEMS);
+    container.outputs.private(mob_id, msg);
+    Ok(())
+}
+
+pub fn deposit(container: &mut Container, mob_id: MobId, amount: Money) -> Result<()> {
+    let player_id = get_player_at_bank_or_fail(container, mob_id)?;
+
+    if inventory_service::get_money(container, mob_id)?.as_u32() < amount.as_u32() {
+        container
+            .outputs
+            .private(mob_id, comm::bank_not_enough_money(amount));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    inventory_service::remove_money(container, mob_id, amount)?;
+    inventory_service::update_inventory_weight(container, mob_id)?;
+    container.vaults.deposit(player_id, amount);
+
+    let balance = container.vaults.get_or_create(player_id).money;
+    container
+        .outputs
+        .private(mob_id, comm::bank_deposit(amount, balance));
+    Ok(())
+}
+
+pub fn withdraw(container: &mut Container, mob_id: MobId, amount: Money) -> Result<()> {
+    let player_id = get_player_at_bank_or_fail(container, mob_id)?;
+
+    if container.vaults.withdraw(player_id, amount).is_err() {
+        container
+            .outputs
+            .private(mob_id, comm::bank_not_enough_balance(amount));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    inventory_service::add_money(container, mob_id, amount)?;
+    inventory_service::update_inventory_weight(container, mob_id)?;
+
+    let balance = container.vaults.get_or_create(player_id).money;
+    container
+        .outputs
+        .private(mob_id, comm::bank_withdraw(amount, balance));
+    Ok(())
+}
+
+/// move a item from the mob inventory into the player vault
+pub fn store(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Result<()> {
+    let player_id = get_player_at_bank_or_fail(container, mob_id)?;
+    let item_label = get_amount_label(container, item_id);
+
+    // money should be deposit
+    let is_money = container
+        .items
+        .get(item_id)
+        .map(|item| item.flags.is_money)
+        .unwrap_or(true);
+
+    if is_money
+        || container.locations.get(item_id) != Some(mob_id)
+        || container.trades.is_offered(item_id)
+    {
+        container
+            .outputs
+            .private(mob_id, comm::bank_store_invalid_item(&item_label));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    let stored = list_items(container, player_id);
+    let storage_id = get_or_create_storage(container, player_id);
+    let can_merge = inventory_service::find_stack(container, item_id, storage_id).is_some();
+    if stored.len() >= VAULT_MAX_ITEMS && !can_merge {
+        container.outputs.private(mob_id, comm::bank_vault_full());
+        return Err(Error::InvalidStateFailure);
+    }
+
+    let _ = container.equips.strip(mob_id, item_id);
+    inventory_service::add_or_merge(container, item_id, storage_id)?;
+    inventory_service::update_inventory_weight(container, mob_id)?;
+
+    container
+        .outputs
+        .private(mob_id, comm::bank_store(&item_label));
+    Ok(())
+}
+
+/// move a item from the player vault into the mob inventory, when amount is provided only that
+/// amount is split from the stack
+pub fn retrieve(
+    container: &mut Container,
+    mob_id: MobId,
+    item_id: ItemId,
+    amount: Option<u32>,
+) -> Result<()> {
+    let player_id = get_player_at_bank_or_fail(container, mob_id)?;
+    let storage_id = get_storage_id(container, player_id);
+
+    if storage_id.is_none() || container.locations.get(item_id) != storage_id {
+        let item_label = container.labels.get_label_f(item_id);
+        container
+            .outputs
+            .private(mob_id, comm::bank_retrieve_not_found(item_label));
+        return Err(Error::NotFoundFailure);
+    }
+
+    let item_id = match amount {
+        Some(amount) => inventory_service::split(container, item_id, amount)?,
+        None => item_id,
+    };
+
+    let item_label = get_amount_label(container, item_id);
+    let weight = inventory_service::get_deep_weight(container, item_id);
+
+    if !inventory_service::can_add_weight(container, mob_id, weight)? {
+        // put back any split amount
+        let storage_id = get_or_create_storage(container, player_id);
+        inventory_service::add_or_merge(container, item_id, storage_id)?;
+
+        container
+            .outputs
+            .private(mob_id, comm::bank_retrieve_too_heavy(&item_label));
+        return Err(Error::InvalidStateFailure);
+    }
+
+    inventory_service::add_or_merge(container, item_id, mob_id)?;
+    inventory_service::update_inventory_weight(container, mob_id)?;
+
+    container
+        .outputs
+        .private(mob_id, comm::bank_retrieve(&item_label));
+    Ok(())
+}
+
+fn get_player_at_bank_or_fail(container: &mut Container, mob_id: MobId) -> Result<PlayerId> {
+    match container.players.find_from_mob(mob_id) {
+        Some(player_id) if is_at_bank(container, mob_id) => Ok(player_id),
+        _ => {
+            container
+                .outputs
+                .private(mob_id, comm::bank_not_available());
+            Err(Error::InvalidStateFailure)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::inventory::Inventory;
+    use crate::game::loader::Loader;
+    use crate::game::{avatars, builder};
+    use commons::ObjId;
+
+    const SCENERY: &str = r#"
+cfg {
+  avatar_mob: 2
+  initial_room: 0
+  money_id: 1
+}
+objects.0 {
+  id: 0
+  label: "bank"
+  room {
+    bank: true
+  }
+}
+prefabs.1 {
+  id: 1
+  label: "gold"
+  item {
+    amount: 1
+    flags.money = true
+  }
+}
+prefabs.2 {
+  id: 2
+  label: "avatar"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 10, pv_max: 10, xp: 0 }
+  inventory {}
+}
+"#;
+
+    fn add_avatar(container: &mut Container, player_id: PlayerId, room_id: ObjId) -> MobId {
+        let mob_id = builder::add_mob(container, "avatar", room_id);
+        container.inventories.add(Inventory::new(mob_id)).unwrap();
+        container.players.set_mob(player_id, mob_id).unwrap();
+        mob_id
+    }
+
+    fn setup() -> (Container, PlayerId, MobId) {
+        let mut container = Container::new();
+        Loader::load_hocon(&mut container, SCENERY).unwrap();
+
+        let mob_id = builder::add_mob(&mut container, "avatar", ObjId(0));
+        container.inventories.add(Inventory::new(mob_id)).unwrap();
+        let player_id = container.objects.create();
+        container
+            .players
+            .create(player_id, "player".to_string(), mob_id);
+        inventory_service::add_money(&mut container, mob_id, Money(100)).unwrap();
+
+        (container, player_id, mob_id)
+    }
+
+    #[test]
+    fn test_bank_deposit_and_withdraw() -> Result<()> {
+        let (mut container, player_id, mob_id) = setup();
+
+        assert!(deposit(&mut container, mob_id, Money(101)).is_err());
+        deposit(&mut container, mob_id, Money(60))?;
+        assert_eq!(Money(40), inventory_service::get_money(&container, mob_id)?);
+        assert_eq!(Money(60), container.vaults.get(player_id).unwrap().money);
+
+        assert!(withdraw(&mut container, mob_id, Money(61)).is_err());
+        withdraw(&mut container, mob_id, Money(10))?;
+        assert_eq!(Money(50), inventory_service::get_money(&container, mob_id)?);
+        assert_eq!(Money(50), container.vaults.get(player_id).unwrap().money);
+
+        // bank is only accessible in bank rooms
+        container
+            .rooms
+            .update(ObjId(0), |room| room.is_bank = false)?;
+        assert!(deposit(&mut container, mob_id, Money(10)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bank_stored_items_should_be_kept_after_avatar_is_removed() -> Result<()> {
+        let (mut container, player_id, mob_id) = setup();
+        let item_id = builder::add_item(&mut container, "sword", mob_id);
+
+        store(&mut container, mob_id, item_id)?;
+        assert_eq!(vec![item_id], list_items(&container, player_id));
+
+        container.remove(mob_id);
+        let mob_id = add_avatar(&mut container, player_id, ObjId(0));
+
+        retrieve(&mut container, mob_id, item_id, None)?;
+        assert_eq!(Some(mob_id), container.locations.get(item_id));
+        assert!(list_items(&container, player_id).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bank_stored_items_should_be_kept_in_vault_after_avatar_respawn() -> Result<()> {
+        let (mut container, player_id, mob_id) = setup();
+        let item_id = builder::add_item(&mut container, "sword", mob_id);
+        store(&mut container, mob_id, item_id)?;
+
+        avatars::on_avatar_killed(&mut container, player_id, mob_id)?;
+        container.remove(mob_id);
+        avatars::respawn_avatar(&mut container, player_id)?;
+
+        let new_mob_id = container.players.get_mob(player_id).unwrap();
+        assert_ne!(mob_id, new_mob_id);
+        assert_eq!(vec![item_id], list_items(&container, player_id));
+        assert_ne!(Some(new_mob_id), container.locations.get(item_id));
+
+        Ok(())
+    }
+}
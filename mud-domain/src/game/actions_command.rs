@@ -3,12 +3,16 @@ use crate::game::ai::{Ai, AiCommand};
 use crate::game::container::Container;
 use crate::game::location::LocationId;
 use crate::game::mob::MobRepository;
+use crate::game::room::RoomId;
 use commons::ObjId;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RequestCommand {
     Idle,
     FollowMe,
     Extract,
+    Haul,
+    Guard,
 }
 
 pub fn list_commandable(container: &Container, obj_id: ObjId) -> Result<Vec<ObjId>> {
@@ -34,12 +38,51 @@ pub fn find_commandable(container: &Container, obj_id: ObjId, label: &str) -> Re
     Ok(container.labels.search(&ids, label))
 }
 
-pub fn list_commands_for(_container: &Container, _obj_id: ObjId) -> Result<Vec<RequestCommand>> {
-    Ok(vec![
-        RequestCommand::Idle,
-        RequestCommand::FollowMe,
-        RequestCommand::Extract,
-    ])
+pub fn list_commands_for(container: &Container, obj_id: ObjId) -> Result<Vec<RequestCommand>> {
+    let mut commands = vec![RequestCommand::Idle, RequestCommand::FollowMe];
+
+    // only who can carry things can extract or haul
+    if container.inventories.get(obj_id).is_some() {
+        commands.push(RequestCommand::Extract);
+        commands.push(RequestCommand::Haul);
+    }
+
+    if container.mobs.exists(obj_id) {
+        commands.push(RequestCommand::Guard);
+    }
+
+    Ok(commands)
+}
+
+pub fn set_command_idle(container: &mut Container, obj_id: ObjId) -> Result<()> {
+    let ai = container
+        .ai
+        .get_mut(obj_id)
+        .as_result_string(|| format!("{:?} has no ai to be commanded", obj_id))?;
+
+    clear_ai_command(ai, &mut container.mobs)?;
+    log::info!("{:?} command idle", obj_id);
+    stop_following_owner(container, obj_id)?;
+
+    Ok(())
+}
+
+/// Stay in the room attacking any enemy, if out of the room, it will walk back
+pub fn set_command_guard(container: &mut Container, obj_id: ObjId, room_id: RoomId) -> Result<()> {
+    let ai = container
+        .ai
+        .get_mut(obj_id)
+        .as_result_string(|| format!("{:?} has no ai to be commanded", obj_id))?;
+
+    clear_ai_command(ai, &mut container.mobs)?;
+
+    log::info!("{:?} command guard {:?}", obj_id, room_id);
+    ai.command = AiCommand::AggressivePatrolHome { distance: 0 };
+    ai.home_id = Some(room_id);
+
+    stop_following_owner(container, obj_id)?;
+
+    Ok(())
 }
 
 pub fn set_command_follow(
@@ -83,11 +126,23 @@ pub fn set_command_extract(
         from: extractable_id,
     };
 
+    stop_following_owner(container, obj_id)?;
+
     super::actions::extract(container, obj_id, location_id, extractable_id)?;
 
     Ok(())
 }
 
+/// hired mobs follow the owner without a command, any other command should stop it
+fn stop_following_owner(container: &mut Container, obj_id: ObjId) -> Result<()> {
+    match container.ownership.get_owner(obj_id) {
+        Some(owner_id) if container.mobs.exists(owner_id) => {
+            container.mobs.remove_follower(owner_id, obj_id)
+        }
+        _ => Ok(()),
+    }
+}
+
 fn clear_ai_command(ai: &mut Ai, mobs: &mut MobRepository) -> Result<()> {
     match ai.command {
         AiCommand::FollowAndProtect { target_id } => {
@@ -125,5 +180,7 @@ pub fn set_command_haul(
         to: to_id,
     };
 
+    stop_following_owner(container, obj_id)?;
+
     Ok(())
 }
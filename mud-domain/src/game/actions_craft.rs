@@ -0,0 +1,403 @@
+use crate::errors::{AsResult, Error, Result};
+use crate::game::actions_items::get_amount_label;
+use crate::game::container::Container;
+use crate::game::item::ItemId;
+use crate::game::loader::Loader;
+use crate::game::mob::MobId;
+use crate::game::recipes::{Recipe, RecipeId, RecipeInputKind};
+use crate::game::tags::TagId;
+use crate::game::{comm, inventory_service};
+use std::collections::HashMap;
+
+/// search a recipe by its label
+pub fn find_recipe(container: &Container, label: &str) -> Option<RecipeId> {
+    let candidates = container.recipes.list_ids();
+    container
+        .labels
+        .search(&candidates, label)
+        .into_iter()
+        .next()
+}
+
+/// true if any object in the mob room has the tag
+pub fn has_workstation(container: &Container, mob_id: MobId, tag_id: TagId) -> bool {
+    container
+        .locations
+        .get(mob_id)
+        .map(|room_id| {
+            container
+                .locations
+                .list_at(room_id)
+                .any(|id| container.tags.has(id, tag_id))
+        })
+        .unwrap_or(false)
+}
+
+/// true if any item in the mob inventory has the tag
+pub fn has_tool(container: &Container, mob_id: MobId, tag_id: TagId) -> bool {
+    container
+        .locations
+        .list_at(mob_id)
+        .any(|id| container.items.exists(id) && container.tags.has(id, tag_id))
+}
+
+/// find the items and amounts from mob inventory that will be consumed by the recipe, returns
+/// None if mob has not enough items
+pub fn find_inputs(
+    container: &Container,
+    mob_id: MobId,
+    recipe: &Recipe,
+) -> Option<Vec<(ItemId, u32)>> {
+    let mut items: Vec<ItemId> = container
+        .locations
+        .list_at(mob_id)
+        .filter(|id| container.items.exists(*id))
+        .collect();
+    items.sort();
+
+    let mut reserved: HashMap<ItemId, u32> = HashMap::new();
+
+    for input in &recipe.inputs {
+        let mut required = input.amount;
+
+        for item_id in &items {
+            if required == 0 {
+                break;
+            }
+
+            let matches = match input.kind {
+                RecipeInputKind::Prefab(prefab_id) => {
+                    container.objects.get_prefab_id(*item_id) == Some(prefab_id)
+                }
+                RecipeInputKind::Tag(tag_id) => container.tags.has(*item_id, tag_id),
+            };
+
+            if !matches {
+                continue;
+            }
+
+            let amount = container.items.get(*item_id)?.amount;
+            let used = reserved.entry(*item_id).or_insert(0);
+            let take = amount.saturating_sub(*used).min(required);
+            *used += take;
+            required -= take;
+        }
+
+        if required > 0 {
+            return None;
+        }
+    }
+
+    let mut result: Vec<(ItemId, u32)> = reserved
+        .into_iter()
+        .filter(|(_, amount)| *amount > 0)
+        .collect();
+    result.sort();
+    Some(result)
+}
+
+/// check if mob can craft the recipe, sending the failure message to the mob
+fn check_requirements(
+    container: &mut Container,
+    mob_id: MobId,
+    recipe_id: RecipeId,
+) -> Result<Vec<(ItemId, u32)>> {
+    let recipe = container.recipes.get(recipe_id).as_result()?;
+    let recipe_label = container.labels.get_label_f(recipe_id);
+
+    let msg = if let Some(tag_id) = recipe
+        .workstation
+        .filter(|tag_id| !has_workstation(container, mob_id, *tag_id))
+    {
+        let tag = container.tags.get_str(tag_id).unwrap_or("???");
+        comm::craft_fail_workstation(recipe_label, tag)
+    } else if let Some(tag_id) = recipe
+        .tool
+        .filter(|tag_id| !has_tool(container, mob_id, *tag_id))
+    {
+        let tag = container.tags.get_str(tag_id).unwrap_or("???");
+        comm::craft_fail_tool(recipe_label, tag)
+    } else {
+        match find_inputs(container, mob_id, recipe) {
+            Some(inputs) => return Ok(inputs),
+            None => comm::craft_fail_inputs(recipe_label),
+        }
+    };
+
+    container.outputs.private(mob_id, msg);
+    Err(Error::InvalidStateFailure)
+}
+
+pub fn list(container: &mut Container, mob_id: MobId) -> Result<()> {
+    let recipes: Vec<comm::RecipeDesc> = container
+        .recipes
+        .list_ids()
+        .into_iter()
+        .flat_map(|recipe_id| {
+            let recipe = container.recipes.get(recipe_id)?;
+            Some(get_recipe_desc(container, mob_id, recipe))
+        })
+        .collect();
+
+    let msg = comm::craft_list(&recipes);
+    container.outputs.private(mob_id, msg);
+    Ok(())
+}
+
+fn get_recipe_desc(container: &Container, mob_id: MobId, recipe: &Recipe) -> comm::RecipeDesc {
+    let tag_label = |tag_id: TagId| container.tags.get_str(tag_id).unwrap_or("???").to_string();
+
+    comm::RecipeDesc {
+        label: container.labels.get_label_f(recipe.id).to_string(),
+        inputs: recipe
+            .inputs
+            .iter()
+            .map(|input| {
+                let label = match input.kind {
+                    RecipeInputKind::Prefab(prefab_id) => {
+                        container.loader.get_prefab_labelf(prefab_id).to_string()
+                    }
+                    RecipeInputKind::Tag(tag_id) => format!("any {}", tag_label(tag_id)),
+                };
+                (input.amount, label)
+            })
+            .collect(),
+        outputs: recipe
+            .outputs
+            .iter()
+            .map(|output| {
+                let label = container.loader.get_prefab_labelf(output.prefab_id);
+                (output.amount, label.to_string())
+            })
+            .collect(),
+        workstation: recipe.workstation.map(tag_label),
+        tool: recipe.tool.map(tag_label),
+        time: recipe.time,
+        can_craft: find_inputs(container, mob_id, recipe).is_some(),
+    }
+}
+
+/// start to craft a recipe, it will be complete after the recipe time
+pub fn craft(container: &mut Container, mob_id: MobId, recipe_id: RecipeId) -> Result<()> {
+    check_requirements(container, mob_id, recipe_id)?;
+
+    let time = container.recipes.get(recipe_id).as_result()?.time;
+    let complete = container.time.total + time;
+    let room_id = container.locations.get(mob_id).as_result()?;
+
+    if container
+        .mobs
+        .get_mut(mob_id)
+        .as_result()?
+        .set_action_craft(recipe_id, complete)
+        .is_err()
+    {
+        container.outputs.private(mob_id, comm::craft_busy());
+        return Err(Error::InvalidStateFailure);
+    }
+
+    log::info!("{:?} start to craft {:?}", mob_id, recipe_id);
+
+    let mob_label = container.labels.get_label_f(mob_id);
+    let recipe_label = container.labels.get_label_f(recipe_id);
+    container
+        .outputs
+        .message(mob_id, room_id, comm::craft_start(mob_label, recipe_label));
+
+    Ok(())
+}
+
+/// check if the craft is complete, consuming inputs and producing outputs. Craft is cancelled if
+/// requirements are not fulfilled anymore
+pub fn tick_craft(container: &mut Container, mob_id: MobId, recipe_id: RecipeId) -> Result<bool> {
+    let inputs = match check_requirements(container, mob_id, recipe_id) {
+        Ok(inputs) => inputs,
+        Err(_) => {
+            container.mobs.cancel_command(mob_id)?;
+            return Ok(false);
+        }
+    };
+
+    let complete = container.mobs.get(mob_id).as_result()?.state.craft_complete;
+    if !container.time.total.is_after(complete) {
+        return Ok(false);
+    }
+
+    container.mobs.cancel_command(mob_id)?;
+
+    for (item_id, amount) in inputs {
+        let _ = container.equips.strip(mob_id, item_id);
+        inventory_service::consume(container, item_id, amount)?;
+    }
+
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let outputs = container
+        .recipes
+        .get(recipe_id)
+        .as_result()?
+        .outputs
+        .clone();
+
+    for output in outputs {
+        let weight = container
+            .loader
+            .get_prefab_weight(output.prefab_id)
+            .unwrap_or(0.0)
+            * output.amount as f32;
+
+        // what can not be carried is placed in the floor
+        let can_carry = inventory_service::can_add_weight(container, mob_id, weight)?;
+        let location_id = if can_carry { mob_id } else { room_id };
+
+        let item_id = Loader::spawn_at(container, output.prefab_id, location_id)?;
+        container.items.get_mut(item_id).as_result()?.amount = output.amount;
+        let item_label = get_amount_label(container, item_id);
+        inventory_service::add_or_merge(container, item_id, location_id)?;
+        inventory_service::update_inventory_weight(container, mob_id)?;
+
+        let mob_label = container.labels.get_label_f(mob_id);
+        let msg = if can_carry {
+            comm::craft_complete(mob_label, &item_label)
+        } else {
+            comm::craft_complete_floor(mob_label, &item_label)
+        };
+        container.outputs.message(mob_id, room_id, msg);
+
+        log::info!("{:?} crafted {:?}", mob_id, item_id);
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::builder;
+    use crate::game::inventory::Inventory;
+    use crate::game::loader::dto::StaticId;
+    use crate::game::mob::MobAction;
+    use commons::{ObjId, TotalTime};
+
+    const SCENERY: &str = r#"
+cfg {
+  avatar_mob: 2
+  initial_room: 0
+  money_id: 1
+}
+objects.0 {
+  id: 0
+  label: "smithy"
+  room {}
+}
+objects.1 {
+  id: 1
+  label: "forge"
+  parent: 0
+  tags.values: ["forge"]
+}
+objects.2 {
+  id: 2
+  label: "sword"
+  recipe {
+    inputs: [
+      { prefab_id: 3, amount: 2 },
+      { tag: "wood" }
+    ]
+    outputs: [{ prefab_id: 5 }]
+    time: 10.0
+    workstation: "forge"
+    tool: "hammer"
+  }
+}
+prefabs.3 {
+  id: 3
+  label: "iron"
+  item {
+    flags.stackable: true
+  }
+}
+prefabs.4 {
+  id: 4
+  label: "oak"
+  tags.values: ["wood"]
+  item {}
+}
+prefabs.5 {
+  id: 5
+  label: "sword"
+  item {}
+}
+prefabs.6 {
+  id: 6
+  label: "hammer"
+  tags.values: ["hammer"]
+  item {}
+}
+"#;
+
+    fn setup() -> (Container, MobId, RecipeId) {
+        let mut container = Container::new();
+        Loader::load_hocon(&mut container, SCENERY).unwrap();
+
+        let mob_id = builder::add_mob(&mut container, "smith", ObjId(0));
+        container.inventories.add(Inventory::new(mob_id)).unwrap();
+
+        let recipe_id = find_recipe(&container, "sword").unwrap();
+        (container, mob_id, recipe_id)
+    }
+
+    #[test]
+    fn test_craft_should_consume_inputs_after_time() -> Result<()> {
+        let (mut container, mob_id, recipe_id) = setup();
+
+        // missing tool and materials
+        assert!(craft(&mut container, mob_id, recipe_id).is_err());
+        Loader::spawn_at(&mut container, StaticId(6), mob_id)?;
+        assert!(craft(&mut container, mob_id, recipe_id).is_err());
+
+        let iron_id = Loader::spawn_at(&mut container, StaticId(3), mob_id)?;
+        container.items.get_mut(iron_id).unwrap().amount = 3;
+        let oak_id = Loader::spawn_at(&mut container, StaticId(4), mob_id)?;
+
+        craft(&mut container, mob_id, recipe_id)?;
+        assert_eq!(
+            MobAction::Crafting,
+            container.mobs.get(mob_id).unwrap().state.action
+        );
+
+        container.time.total = TotalTime(5.0);
+        assert!(!tick_craft(&mut container, mob_id, recipe_id)?);
+
+        container.time.total = TotalTime(11.0);
+        assert!(tick_craft(&mut container, mob_id, recipe_id)?);
+
+        assert_eq!(1, container.items.get(iron_id).unwrap().amount);
+        assert!(!container.items.exists(oak_id));
+        assert!(inventory_service::find_by_prefab(&container, mob_id, StaticId(5)).is_some());
+        assert!(container.mobs.get(mob_id).unwrap().is_idle());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_craft_should_be_cancelled_when_leaving_workstation() -> Result<()> {
+        let (mut container, mob_id, recipe_id) = setup();
+
+        Loader::spawn_at(&mut container, StaticId(6), mob_id)?;
+        let iron_id = Loader::spawn_at(&mut container, StaticId(3), mob_id)?;
+        container.items.get_mut(iron_id).unwrap().amount = 2;
+        Loader::spawn_at(&mut container, StaticId(4), mob_id)?;
+
+        craft(&mut container, mob_id, recipe_id)?;
+
+        let other_room_id = builder::add_room(&mut container, "street");
+        container.locations.set(mob_id, other_room_id);
+
+        container.time.total = TotalTime(11.0);
+        assert!(!tick_craft(&mut container, mob_id, recipe_id)?);
+        assert!(container.mobs.get(mob_id).unwrap().is_idle());
+        assert_eq!(2, container.items.get(iron_id).unwrap().amount);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,359 @@
+use crate::errors::{AsResult, Error, Result};
+use crate::game::container::Container;
+use crate::game::dialogues::{
+    Conversation, DialogueCondition, DialogueEffect, DialogueNode, DialogueOption,
+};
+use crate::game::item::Weight;
+use crate::game::loader::Loader;
+use crate::game::mob::MobId;
+use crate::game::prices::Money;
+use crate::game::{actions_quest, comm, inventory_service};
+use commons::ObjId;
+
+/// Start a conversation with the npc, returns None when the dialogue ends in the first node
+pub fn talk(
+    container: &mut Container,
+    mob_id: MobId,
+    npc_id: ObjId,
+) -> Result<Option<Conversation>> {
+    let npc_label = container.labels.get_label_f(npc_id);
+
+    let dialogue_id = match container.dialogues.get_talker(npc_id) {
+        Some(dialogue_id) => dialogue_id,
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::talk_nothing_to_say(npc_label));
+            return Err(Error::InvalidArgumentFailure);
+        }
+    };
+
+    let dialogue = container.dialogues.get(dialogue_id).as_result()?;
+    let conversation = Conversation {
+        npc_id,
+        dialogue_id,
+        node_id: dialogue.start.clone(),
+    };
+
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let mob_label = container.labels.get_label_f(mob_id);
+    container.outputs.broadcast(
+        Some(mob_id),
+        room_id,
+        comm::talk_start_others(mob_label, npc_label),
+    );
+
+    show(container, mob_id, conversation)
+}
+
+/// Select a option by its number as show to the player, start from 1
+pub fn choose(
+    container: &mut Container,
+    mob_id: MobId,
+    conversation: &Conversation,
+    choice: usize,
+) -> Result<Option<Conversation>> {
+    let npc_label = container.labels.get_label_f(conversation.npc_id);
+
+    let room_id = container.locations.get(mob_id).as_result()?;
+    if container.locations.get(conversation.npc_id) != Some(room_id) {
+        container
+            .outputs
+            .private(mob_id, comm::talk_npc_gone(npc_label));
+        return Ok(None);
+    }
+
+    let node = get_node(container, conversation)?;
+    let option = match list_options(container, mob_id, node).get(choice.wrapping_sub(1)) {
+        Some(option) => (*option).clone(),
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::talk_invalid_option(choice));
+            return Err(Error::InvalidArgumentFailure);
+        }
+    };
+
+    log::debug!(
+        "{:?} choose {:?} talking with {:?}",
+        mob_id,
+        option.text,
+        conversation.npc_id
+    );
+
+    if !can_apply_effects(container, mob_id, &option.effects) {
+        return Err(Error::InvalidStateFailure);
+    }
+
+    for effect in &option.effects {
+        apply_effect(container, mob_id, effect)?;
+    }
+
+    match option.next {
+        Some(node_id) => {
+            let conversation = Conversation {
+                node_id,
+                ..conversation.clone()
+            };
+            show(container, mob_id, conversation)
+        }
+        None => {
+            let npc_label = container.labels.get_label_f(conversation.npc_id);
+            container.outputs.private(mob_id, comm::talk_end(npc_label));
+            Ok(None)
+        }
+    }
+}
+
+fn get_node<'a>(container: &'a Container, conversation: &Conversation) -> Result<&'a DialogueNode> {
+    container
+        .dialogues
+        .get(conversation.dialogue_id)
+        .and_then(|dialogue| dialogue.get_node(&conversation.node_id))
+        .ok_or_else(|| {
+            log::warn!("{:?} dialogue node not found", conversation);
+            Error::InvalidStateFailure
+        })
+}
+
+fn show(
+    container: &mut Container,
+    mob_id: MobId,
+    conversation: Conversation,
+) -> Result<Option<Conversation>> {
+    let npc_label = container.labels.get_label_f(conversation.npc_id);
+    let node = get_node(container, &conversation)?;
+    let options = list_options(container, mob_id, node);
+    let msg = comm::talk_node(
+        npc_label,
+        &node.text,
+        options.iter().map(|option| option.text.as_str()).collect(),
+    );
+    let is_end = options.is_empty();
+
+    container.outputs.private(mob_id, msg);
+
+    if is_end {
+        Ok(None)
+    } else {
+        Ok(Some(conversation))
+    }
+}
+
+/// options where all conditions are satisfied by the mob
+fn list_options<'a>(
+    container: &Container,
+    mob_id: MobId,
+    node: &'a DialogueNode,
+) -> Vec<&'a DialogueOption> {
+    node.options
+        .iter()
+        .filter(|option| {
+            option
+                .conditions
+                .iter()
+                .all(|condition| check_condition(container, mob_id, condition))
+        })
+        .collect()
+}
+
+fn check_condition(container: &Container, mob_id: MobId, condition: &DialogueCondition) -> bool {
+    match condition {
+        DialogueCondition::Tag(tag_id) => container.tags.has(mob_id, *tag_id),
+        DialogueCondition::NotTag(tag_id) => !container.tags.has(mob_id, *tag_id),
+        DialogueCondition::Money(money) => inventory_service::get_money(container, mob_id)
+            .map(|current| current.as_u32() >= money.as_u32())
+            .unwrap_or(false),
+        DialogueCondition::Item(prefab_id) => container
+            .locations
+            .list_at(mob_id)
+            .any(|id| container.objects.get_prefab_id(id) == Some(*prefab_id)),
+    }
+}
+
+/// Check that the mob can pay and carry everything before any effect is applied, so a option is
+/// never half done
+fn can_apply_effects(container: &mut Container, mob_id: MobId, effects: &[DialogueEffect]) -> bool {
+    let to_pay: u32 = effects
+        .iter()
+        .map(|effect| match effect {
+            DialogueEffect::TakeMoney(money) => money.as_u32(),
+            _ => 0,
+        })
+        .sum();
+
+    let money = inventory_service::get_money(container, mob_id)
+        .map(|money| money.as_u32())
+        .unwrap_or(0);
+
+    if money < to_pay {
+        container
+            .outputs
+            .private(mob_id, comm::talk_not_enough_money(Money(to_pay)));
+        return false;
+    }
+
+    let weight: Weight = effects
+        .iter()
+        .flat_map(|effect| match effect {
+            DialogueEffect::GiveItem(prefab_id) => container.loader.get_prefab_weight(*prefab_id),
+            _ => None,
+        })
+        .sum();
+
+    let can_carry = weight <= 0.0
+        || container
+            .inventories
+            .get(mob_id)
+            .map(|inventory| inventory.can_add(weight))
+            .unwrap_or(true);
+
+    if !can_carry {
+        container
+            .outputs
+            .private(mob_id, comm::talk_can_not_carry());
+        return false;
+    }
+
+    true
+}
+
+fn apply_effect(container: &mut Container, mob_id: MobId, effect: &DialogueEffect) -> Result<()> {
+    match effect {
+        DialogueEffect::GiveItem(prefab_id) => {
+            let item_id = Loader::spawn_at(container, *prefab_id, mob_id)?;
+            let item_label = container.labels.get_label_f(item_id);
+            container
+                .outputs
+                .private(mob_id, comm::talk_receive_item(item_label));
+        }
+        DialogueEffect::GiveMoney(money) => {
+            inventory_service::add_money(container, mob_id, *money)?;
+            container
+                .outputs
+                .private(mob_id, comm::talk_receive_money(*money));
+        }
+        DialogueEffect::TakeMoney(money) => {
+            inventory_service::remove_money(container, mob_id, *money)?;
+            container
+                .outputs
+                .private(mob_id, comm::talk_pay_money(*money));
+        }
+        DialogueEffect::SetFlag(tag_id) => {
+            container.tags.add(mob_id, *tag_id);
+        }
+        DialogueEffect::StartQuest(quest_id) => {
+            // already active or completed quests are reported to the player
+            match actions_quest::start(container, mob_id, *quest_id) {
+                Err(e) if e.is_failure() => {}
+                other => other?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::builder;
+    use crate::game::inventory::Inventory;
+
+    const SCENERY: &str = r#"
+cfg {
+  avatar_mob: 0
+  initial_room: 0
+  money_id: 1
+}
+objects.0 {
+  id: 0
+  label: "village"
+  room {}
+}
+objects.10 {
+  id: 10
+  label: "merchant"
+  parent: 0
+  mob { attack: 1, damage_max: 1, damage_min: 1, defense: 1, pv: 10, pv_max: 10, xp: 0 }
+  dialogue_id: 11
+}
+objects.11 {
+  id: 11
+  label: "merchant dialogue"
+  dialogue {
+    start: "hello"
+    nodes {
+      hello {
+        text: "Want a sword?"
+        options: [
+          {
+            text: "Yes."
+            effects: [{give_item: 2}, {take_money: 10}]
+          }
+        ]
+      }
+    }
+  }
+}
+prefabs.1 {
+  id: 1
+  label: "gold"
+  item {
+    amount: 1
+    flags.money = true
+  }
+}
+prefabs.2 {
+  id: 2
+  label: "sword"
+  item.weight: 1.0
+}
+"#;
+
+    fn setup() -> (Container, MobId, Conversation) {
+        let mut container = Container::new();
+        let lc = Loader::load_hocon(&mut container, SCENERY).unwrap();
+        let mob_id = builder::add_mob(&mut container, "player", lc.get(0));
+        container.inventories.add(Inventory::new(mob_id)).unwrap();
+
+        let conversation = talk(&mut container, mob_id, lc.get(10)).unwrap().unwrap();
+        (container, mob_id, conversation)
+    }
+
+    fn count_items(container: &Container, mob_id: MobId) -> usize {
+        container
+            .locations
+            .list_at(mob_id)
+            .filter(|id| container.items.exists(*id))
+            .count()
+    }
+
+    #[test]
+    fn test_choose_should_not_apply_effects_when_player_can_not_pay() {
+        let (mut container, mob_id, conversation) = setup();
+        inventory_service::add_money(&mut container, mob_id, Money(5)).unwrap();
+
+        assert!(choose(&mut container, mob_id, &conversation, 1).is_err());
+        assert_eq!(1, count_items(&container, mob_id));
+        assert_eq!(
+            Money(5),
+            inventory_service::get_money(&container, mob_id).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_choose_should_apply_all_effects() {
+        let (mut container, mob_id, conversation) = setup();
+        inventory_service::add_money(&mut container, mob_id, Money(15)).unwrap();
+
+        assert!(choose(&mut container, mob_id, &conversation, 1)
+            .unwrap()
+            .is_none());
+        assert_eq!(2, count_items(&container, mob_id));
+        assert_eq!(
+            Money(5),
+            inventory_service::get_money(&container, mob_id).unwrap()
+        );
+    }
+}
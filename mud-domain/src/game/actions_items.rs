@@ -2,7 +2,10 @@ use super::container::Container;
 use super::item::*;
 use super::mob::*;
 use crate::errors::{AsResult, Error, Result};
+use crate::game::equip::EquipSlot;
+use crate::game::needs::{ConsumableKind, MobEffect};
 use crate::game::outputs::Outputs;
+use crate::game::triggers::Event;
 use crate::game::{comm, inventory_service};
 use commons::PlayerId;
 
@@ -41,8 +44,12 @@ pub fn do_pickup(
         return Err(PickUpError::Stuck);
     }
 
+    // items inside containers already carried by the mob are already part of its weight
+    let is_carried = container.locations.list_parents(item_id).contains(&mob_id);
+
     log::debug!("{:?} pick up {:?}, starting", mob_id, item_id);
-    if let Some(weight) = item.weight {
+    if item.weight.is_some() && !is_carried {
+        let weight = item.total_weight();
         let can_add_weight = inventory_service::can_add_weight(container, mob_id, weight)
             .map_err(|e| PickUpError::Other(e))?;
 
@@ -113,7 +120,7 @@ pub fn do_pickup(
     // update inventory of new and old location of the item
     inventory_service::update_inventory_weight(container, mob_id)?;
     if let Some(item_location_id) = item_location_id {
-        inventory_service::update_inventory_weight(container, item_location_id)?;
+        inventory_service::update_inventory_weight_with_parents(container, item_location_id)?;
     }
 
     Ok(())
@@ -121,8 +128,7 @@ pub fn do_pickup(
 
 /// As a humanoid entity in mud, try to equip a item
 pub fn do_equip(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Result<()> {
-    let _item = container.items.get(item_id).as_result()?;
-    let item_label = container.labels.get_label_f(item_id);
+    let item_label = container.labels.get_label_f(item_id).to_string();
 
     // check if mob own the item
     let has_item = container
@@ -145,26 +151,72 @@ pub fn do_equip(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Re
     if !can_be_equipped {
         container
             .outputs
-            .private(mob_id, comm::equip_item_invalid(item_label));
+            .private(mob_id, comm::equip_item_invalid(&item_label));
         return Err(Error::InvalidArgumentFailure);
     }
 
-    // TODO: remove old equip in same place?
+    let slots = item.get_equip_slots();
+
+    // remove items that are using the same slots
+    for conflict_id in find_equip_conflicts(container, mob_id, item_id, &slots) {
+        do_strip(container, mob_id, conflict_id)?;
+    }
+
     container.equips.add(mob_id, item_id);
 
     let mob_label = container.labels.get_label(mob_id).as_result()?;
     let room_id = container.locations.get(mob_id).as_result()?;
     container
         .outputs
-        .private(mob_id, comm::equip_player_from_room(item_label));
+        .private(mob_id, comm::equip_player_from_room(&item_label));
     container.outputs.broadcast(
         Some(mob_id),
         room_id,
-        comm::equip_from_room(mob_label, item_label),
+        comm::equip_from_room(mob_label, &item_label),
     );
     Ok(())
 }
 
+/// List equipped items that need to be removed to free the slots
+pub fn find_equip_conflicts(
+    container: &Container,
+    mob_id: MobId,
+    item_id: ItemId,
+    slots: &Vec<EquipSlot>,
+) -> Vec<ItemId> {
+    let mut equipped: Vec<ItemId> = container
+        .equips
+        .get(mob_id)
+        .into_iter()
+        .filter(|id| *id != item_id)
+        .collect();
+    equipped.sort();
+
+    let mut conflicts = vec![];
+
+    for slot in slots {
+        let required = slots.iter().filter(|i| *i == slot).count();
+
+        let occupied: Vec<ItemId> = equipped
+            .iter()
+            .filter(|id| !conflicts.contains(*id))
+            .filter(|id| {
+                container
+                    .items
+                    .get(**id)
+                    .map(|item| item.get_equip_slots().contains(slot))
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect();
+
+        let overflow = (occupied.len() + required).saturating_sub(slot.capacity());
+        conflicts.extend(occupied.into_iter().take(overflow));
+    }
+
+    conflicts
+}
+
 pub fn do_strip(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Result<()> {
     let mob_label = container.labels.get_label(mob_id).as_result()?;
     let room_id = container.locations.get(mob_id).as_result()?;
@@ -184,15 +236,28 @@ pub fn do_strip(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Re
     Ok(())
 }
 
-pub fn do_drop(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Result<()> {
-    let mob_label = container.labels.get_label(mob_id).as_result()?;
+/// Drop a item in the room, when amount is provided only that amount is split from the stack
+pub fn do_drop(
+    container: &mut Container,
+    mob_id: MobId,
+    item_id: ItemId,
+    amount: Option<u32>,
+) -> Result<()> {
     let room_id = container.locations.get(mob_id).as_result()?;
-    let item_label = container.labels.get_label(item_id).as_result()?;
     let item_location_id = container.locations.get(item_id);
 
+    let item_id = match amount {
+        Some(amount) => inventory_service::split(container, item_id, amount)?,
+        None => item_id,
+    };
+
     // strip if is in use
     let _ = container.equips.strip(mob_id, item_id);
-    container.locations.set(item_id, room_id);
+    let item_label = get_amount_label(container, item_id);
+    inventory_service::add_or_merge(container, item_id, room_id)?;
+
+    let mob_label = container.labels.get_label(mob_id).as_result()?;
+    let item_label = item_label.as_str();
 
     container
         .outputs
@@ -214,16 +279,355 @@ pub fn do_drop(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Res
     Ok(())
 }
 
+/// Give a owned item to other mob in the same room, the receiver must be able to carry it
+pub fn do_give(
+    container: &mut Container,
+    mob_id: MobId,
+    item_id: ItemId,
+    target_id: MobId,
+    amount: Option<u32>,
+) -> Result<()> {
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let target_label = container.labels.get_label_f(target_id);
+
+    if target_id == mob_id || container.locations.get(target_id) != Some(room_id) {
+        container
+            .outputs
+            .private(mob_id, comm::give_target_not_found(target_label));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    if container.locations.get(item_id) != Some(mob_id) {
+        let item_label = container.labels.get_label_f(item_id);
+        container
+            .outputs
+            .private(mob_id, comm::give_item_not_found(item_label));
+        return Err(Error::NotFoundFailure);
+    }
+
+    let weight = inventory_service::get_deep_weight(container, item_id);
+    let weight = match (amount, container.items.get(item_id)) {
+        (Some(amount), Some(item)) if amount < item.amount => {
+            item.weight.unwrap_or(0.0) * amount as f32
+        }
+        _ => weight,
+    };
+
+    if !inventory_service::can_add_weight(container, target_id, weight)? {
+        let item_label = container.labels.get_label_f(item_id);
+        container.outputs.private(
+            mob_id,
+            comm::give_target_can_not_carry(target_label, item_label),
+        );
+        return Err(Error::InvalidStateFailure);
+    }
+
+    let item_id = match amount {
+        Some(amount) => inventory_service::split(container, item_id, amount)?,
+        None => item_id,
+    };
+
+    // strip if is in use
+    let _ = container.equips.strip(mob_id, item_id);
+    let item_label = get_amount_label(container, item_id);
+    let item = container.items.get(item_id).as_result()?;
+    let is_money = item.flags.is_money;
+    let given_amount = item.amount;
+
+    // money is always merged, the given item can be removed
+    let received_id = if is_money {
+        inventory_service::add(container, item_id, target_id)?;
+        inventory_service::get_money_id(container, target_id).as_result()?
+    } else {
+        inventory_service::add_or_merge(container, item_id, target_id)?
+    };
+
+    inventory_service::update_inventory_weight(container, mob_id)?;
+    inventory_service::update_inventory_weight(container, target_id)?;
+
+    let mob_label = container.labels.get_label_f(mob_id);
+    let target_label = container.labels.get_label_f(target_id);
+    let item_label = item_label.as_str();
+
+    container
+        .outputs
+        .private(mob_id, comm::give_item(item_label, target_label));
+    container.outputs.broadcast(
+        Some(mob_id),
+        room_id,
+        comm::give_item_others(mob_label, item_label, target_label),
+    );
+
+    container.triggers.push(Event::Give {
+        obj_id: target_id,
+        from_id: mob_id,
+        item_id: received_id,
+        amount: given_amount,
+    });
+
+    Ok(())
+}
+
+/// Put a owned item inside a container, the container can be in the room or carried by the mob
+pub fn do_put(
+    container: &mut Container,
+    mob_id: MobId,
+    item_id: ItemId,
+    storage_id: ItemId,
+    amount: Option<u32>,
+) -> Result<()> {
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let storage_label = container.labels.get_label_f(storage_id);
+
+    if container.locations.get(item_id) != Some(mob_id) {
+        let item_label = container.labels.get_label_f(item_id);
+        container
+            .outputs
+            .private(mob_id, comm::put_item_not_found(item_label));
+        return Err(Error::NotFoundFailure);
+    }
+
+    let is_inventory = container
+        .items
+        .get(storage_id)
+        .map(|item| item.flags.is_inventory)
+        .unwrap_or(false);
+
+    // a container can not be put inside itself or any of its contents
+    let is_inside_item = container
+        .locations
+        .list_parents_inclusive(storage_id)
+        .contains(&item_id);
+
+    if !is_inventory || is_inside_item {
+        container
+            .outputs
+            .private(mob_id, comm::put_invalid_container(storage_label));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    let weight = match (amount, container.items.get(item_id)) {
+        (Some(amount), Some(item)) if amount < item.amount => {
+            item.weight.unwrap_or(0.0) * amount as f32
+        }
+        _ => inventory_service::get_deep_weight(container, item_id),
+    };
+
+    // containers without inventory have no weight limit
+    let can_add_weight = container
+        .inventories
+        .get(storage_id)
+        .map(|inventory| inventory.can_add(weight))
+        .unwrap_or(true);
+
+    if !can_add_weight {
+        let item_label = container.labels.get_label_f(item_id);
+        container
+            .outputs
+            .private(mob_id, comm::put_container_full(storage_label, item_label));
+        return Err(Error::InvalidStateFailure);
+    }
+
+    let item_id = match amount {
+        Some(amount) => inventory_service::split(container, item_id, amount)?,
+        None => item_id,
+    };
+
+    // strip if is in use
+    let _ = container.equips.strip(mob_id, item_id);
+    let item_label = get_amount_label(container, item_id);
+    inventory_service::add(container, item_id, storage_id)?;
+
+    inventory_service::update_inventory_weight(container, mob_id)?;
+    inventory_service::update_inventory_weight_with_parents(container, storage_id)?;
+
+    let mob_label = container.labels.get_label_f(mob_id);
+    let storage_label = container.labels.get_label_f(storage_id);
+    let item_label = item_label.as_str();
+
+    container
+        .outputs
+        .private(mob_id, comm::put_item(item_label, storage_label));
+    container.outputs.broadcast(
+        Some(mob_id),
+        room_id,
+        comm::put_item_others(mob_label, item_label, storage_label),
+    );
+
+    Ok(())
+}
+
+/// Eat, drink or quaff a owned item, the consumable kind must match
+pub fn do_consume(
+    container: &mut Container,
+    mob_id: MobId,
+    item_id: ItemId,
+    kind: ConsumableKind,
+) -> Result<()> {
+    let verb = get_consume_verb(kind);
+    let item_label = container.labels.get_label_f(item_id).to_string();
+
+    let consumable = container
+        .items
+        .get(item_id)
+        .filter(|_| container.locations.get(item_id) == Some(mob_id))
+        .and_then(|item| item.consumable.clone())
+        .filter(|consumable| consumable.kind == kind);
+
+    let consumable = match consumable {
+        Some(consumable) => consumable,
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::consume_invalid_item(verb, &item_label));
+            return Err(Error::InvalidArgumentFailure);
+        }
+    };
+
+    let total_time = container.time.total;
+    let mob = container.mobs.get_mut(mob_id).as_result()?;
+    mob.attributes.pv.current =
+        (mob.attributes.pv.current + consumable.heal as i32).min(mob.attributes.pv.max as i32);
+
+    if let Some(needs) = mob.needs.as_mut() {
+        needs.restore(consumable.hunger, consumable.thirst);
+    }
+
+    if let Some(effect) = &consumable.effect {
+        // same effect is refreshed instead of stacked
+        mob.effects.retain(|other| other.kind != effect.kind);
+        mob.effects.push(MobEffect {
+            kind: effect.kind,
+            amount: effect.amount,
+            expire: total_time + effect.duration,
+        });
+    }
+
+    inventory_service::consume_one(container, item_id)?;
+
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let mob_label = container.labels.get_label_f(mob_id);
+    container.outputs.message(
+        mob_id,
+        room_id,
+        comm::consume_item(mob_label, verb, &item_label),
+    );
+
+    Ok(())
+}
+
+/// Check that the item is owned by the mob and is damaged, returning its durability
+pub fn check_repairable(
+    container: &mut Container,
+    mob_id: MobId,
+    item_id: ItemId,
+) -> Result<Durability> {
+    let item_label = container.labels.get_label_f(item_id).to_string();
+
+    let durability = container
+        .items
+        .get(item_id)
+        .filter(|_| container.locations.get(item_id) == Some(mob_id))
+        .and_then(|item| item.durability);
+
+    match durability {
+        Some(durability) if durability.missing() > 0 => Ok(durability),
+        Some(_) => {
+            container
+                .outputs
+                .private(mob_id, comm::repair_not_needed(&item_label));
+            Err(Error::InvalidStateFailure)
+        }
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::repair_invalid_item(&item_label));
+            Err(Error::InvalidArgumentFailure)
+        }
+    }
+}
+
+/// Restore the item durability consuming one repair kit
+pub fn do_repair_with_kit(
+    container: &mut Container,
+    mob_id: MobId,
+    item_id: ItemId,
+    kit_id: ItemId,
+) -> Result<()> {
+    check_repairable(container, mob_id, item_id)?;
+
+    let kit_label = container.labels.get_label_f(kit_id).to_string();
+    let points = container
+        .items
+        .get(kit_id)
+        .filter(|_| container.locations.get(kit_id) == Some(mob_id))
+        .and_then(|kit| kit.repair_kit);
+
+    let points = match points {
+        Some(points) if kit_id != item_id => points,
+        _ => {
+            container
+                .outputs
+                .private(mob_id, comm::repair_invalid_kit(&kit_label));
+            return Err(Error::InvalidArgumentFailure);
+        }
+    };
+
+    let durability = container
+        .items
+        .get_mut(item_id)
+        .and_then(|item| item.durability.as_mut())
+        .as_result()?;
+    durability.repair(points);
+    let durability = *durability;
+
+    inventory_service::consume_one(container, kit_id)?;
+
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let mob_label = container.labels.get_label_f(mob_id);
+    let item_label = container.labels.get_label_f(item_id);
+    container.outputs.message(
+        mob_id,
+        room_id,
+        comm::repair_with_kit(mob_label, item_label, &kit_label, durability),
+    );
+
+    Ok(())
+}
+
+pub fn get_consume_verb(kind: ConsumableKind) -> &'static str {
+    match kind {
+        ConsumableKind::Food => "eat",
+        ConsumableKind::Drink => "drink",
+        ConsumableKind::Potion => "quaff",
+    }
+}
+
+/// label prefixed with amount for stacks
+pub fn get_amount_label(container: &Container, item_id: ItemId) -> String {
+    let label = container.labels.get_label_f(item_id);
+    match container.items.get(item_id) {
+        Some(item) if item.amount > 1 => format!("{} {}", item.amount, label),
+        _ => label.to_string(),
+    }
+}
+
 #[cfg(test)]
 pub mod test {
     use super::*;
     use crate::game::builder;
     use crate::game::container::Container;
+    use crate::game::domain::Modifier;
     use crate::game::item::ItemId;
     use crate::game::loader::dto::{ItemData, ItemFlagsData, ObjData, StaticId};
+    use crate::game::loader::Loader;
     use crate::game::mob::MobId;
+    use crate::game::needs::{Consumable, Effect, EffectKind, Needs};
     use crate::game::obj::Obj;
+    use crate::game::prices::{Money, Price};
     use crate::game::room::RoomId;
+    use crate::game::triggers::EventKind;
+    use commons::DeltaTime;
 
     pub struct TestScenery {
         pub container: Container,
@@ -331,7 +735,7 @@ pub mod test {
         builder::set_item_weight(&mut scenery.container, item_id, 5.0);
         do_pickup(&mut scenery.container, scenery.mob_id, item_id, None).unwrap();
 
-        super::do_drop(&mut scenery.container, scenery.mob_id, item_id)?;
+        super::do_drop(&mut scenery.container, scenery.mob_id, item_id, None)?;
 
         let inventory_list = inventory_service::get_inventory_list(
             &scenery.container.locations,
@@ -346,4 +750,397 @@ pub mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_give_should_move_item_to_target_and_trigger_event() -> Result<()> {
+        let mut scenery = setup();
+        let container = &mut scenery.container;
+        let target_id = builder::add_mob(container, "target", scenery.room_id);
+        builder::set_mob_max_carry_weight(container, target_id, 10.0);
+
+        let heavy_id = builder::add_item(container, "heavy", scenery.mob_id);
+        builder::set_item_weight(container, heavy_id, 20.0);
+        assert!(do_give(container, scenery.mob_id, heavy_id, target_id, None).is_err());
+        assert_eq!(Some(scenery.mob_id), container.locations.get(heavy_id));
+
+        let item_id = builder::add_item(container, "item", scenery.mob_id);
+        builder::set_item_weight(container, item_id, 2.0);
+        do_give(container, scenery.mob_id, item_id, target_id, None)?;
+        assert_eq!(Some(target_id), container.locations.get(item_id));
+
+        let inventory = container.inventories.get(target_id).unwrap();
+        assert_eq!(inventory.current_weight, Some(2.0));
+
+        let events: Vec<_> = container.triggers.list(EventKind::Give).collect();
+        match events.as_slice() {
+            [Event::Give {
+                obj_id,
+                from_id,
+                item_id: given_id,
+                amount,
+            }] => {
+                assert_eq!(*obj_id, target_id);
+                assert_eq!(*from_id, scenery.mob_id);
+                assert_eq!(*given_id, item_id);
+                assert_eq!(*amount, 1);
+            }
+            other => panic!("unexpected events {:?}", other),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_put_should_allow_nested_containers() -> Result<()> {
+        let mut scenery = setup();
+        let container = &mut scenery.container;
+        builder::set_mob_max_carry_weight(container, scenery.mob_id, 10.0);
+
+        let bag_id = builder::add_container(container, "bag", scenery.mob_id, false);
+        builder::set_item_weight(container, bag_id, 1.0);
+        let pouch_id = builder::add_container(container, "pouch", scenery.mob_id, false);
+        let item_id = builder::add_item(container, "item", scenery.mob_id);
+        builder::set_item_weight(container, item_id, 2.0);
+
+        do_put(container, scenery.mob_id, item_id, pouch_id, None)?;
+        do_put(container, scenery.mob_id, pouch_id, bag_id, None)?;
+        assert_eq!(Some(pouch_id), container.locations.get(item_id));
+        assert_eq!(Some(bag_id), container.locations.get(pouch_id));
+
+        // bag can not be put inside its own contents
+        do_pickup(container, scenery.mob_id, pouch_id, Some(bag_id)).unwrap();
+        do_put(container, scenery.mob_id, pouch_id, bag_id, None)?;
+        assert!(do_put(container, scenery.mob_id, bag_id, pouch_id, None).is_err());
+        assert!(do_put(container, scenery.mob_id, bag_id, bag_id, None).is_err());
+
+        // not a container
+        let other_id = builder::add_item(container, "other", scenery.mob_id);
+        assert!(do_put(container, scenery.mob_id, other_id, item_id, None).is_err());
+
+        let inventory = container.inventories.get(scenery.mob_id).unwrap();
+        assert_eq!(inventory.current_weight, Some(3.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_put_should_respect_container_max_weight() -> Result<()> {
+        let mut scenery = setup();
+        let container = &mut scenery.container;
+
+        let chest_id = builder::add_container(container, "chest", scenery.room_id, true);
+        builder::set_mob_max_carry_weight(container, chest_id, 5.0);
+
+        let item1_id = builder::add_item(container, "item", scenery.mob_id);
+        builder::set_item_weight(container, item1_id, 4.0);
+        let item2_id = builder::add_item(container, "item", scenery.mob_id);
+        builder::set_item_weight(container, item2_id, 4.0);
+
+        do_put(container, scenery.mob_id, item1_id, chest_id, None)?;
+        assert!(do_put(container, scenery.mob_id, item2_id, chest_id, None).is_err());
+        assert_eq!(Some(scenery.mob_id), container.locations.get(item2_id));
+
+        let inventory = container.inventories.get(chest_id).unwrap();
+        assert_eq!(inventory.current_weight, Some(4.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_broken_item_should_not_give_bonus_until_repaired() -> Result<()> {
+        let mut scenery = setup();
+        let container = &mut scenery.container;
+        let mob_id = scenery.mob_id;
+
+        let shield_id = builder::add_item(container, "shield", mob_id);
+        let mut armor = Armor::new();
+        armor.defense = Modifier(4);
+        let item = container.items.get_mut(shield_id).unwrap();
+        item.armor = Some(armor);
+        item.durability = Some(Durability {
+            current: 0,
+            max: 10,
+        });
+
+        let kit_id = builder::add_item(container, "kit", mob_id);
+        container.items.get_mut(kit_id).unwrap().repair_kit = Some(4);
+
+        let defense = get_attributes_with_bonus(container, mob_id)?.defense;
+        do_equip(container, mob_id, shield_id)?;
+        assert_eq!(
+            defense,
+            get_attributes_with_bonus(container, mob_id)?.defense
+        );
+
+        // kit can not be used as a item to be repaired
+        assert!(do_repair_with_kit(container, mob_id, kit_id, shield_id).is_err());
+
+        // worn items give half bonus
+        do_repair_with_kit(container, mob_id, shield_id, kit_id)?;
+        assert!(!container.items.exists(kit_id));
+        assert_eq!(
+            defense + 2,
+            get_attributes_with_bonus(container, mob_id)?.defense
+        );
+
+        container
+            .items
+            .get_mut(shield_id)
+            .unwrap()
+            .durability
+            .as_mut()
+            .unwrap()
+            .repair(10);
+        assert_eq!(
+            defense + 4,
+            get_attributes_with_bonus(container, mob_id)?.defense
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_consume_should_restore_needs_and_apply_effect() -> Result<()> {
+        let mut scenery = setup();
+        let container = &mut scenery.container;
+        let mob_id = scenery.mob_id;
+
+        let mut needs = Needs::new();
+        needs.hunger = 10.0;
+        container.mobs.get_mut(mob_id).unwrap().needs = Some(needs);
+        container
+            .mobs
+            .get_mut(mob_id)
+            .unwrap()
+            .attributes
+            .pv
+            .current = 1;
+
+        let bread_id = builder::add_item(container, "bread", mob_id);
+        let item = container.items.get_mut(bread_id).unwrap();
+        item.amount = 2;
+        item.consumable = Some(Consumable {
+            kind: ConsumableKind::Food,
+            heal: 2,
+            hunger: 30.0,
+            thirst: 0.0,
+            effect: Some(Effect {
+                kind: EffectKind::Attack,
+                amount: 3,
+                duration: DeltaTime(10.0),
+            }),
+        });
+
+        // bread can not be drunk
+        assert!(do_consume(container, mob_id, bread_id, ConsumableKind::Drink).is_err());
+
+        let attack = get_attributes_with_bonus(container, mob_id)?.attack;
+        do_consume(container, mob_id, bread_id, ConsumableKind::Food)?;
+
+        let mob = container.mobs.get(mob_id).unwrap();
+        assert_eq!(40.0, mob.needs.as_ref().unwrap().hunger);
+        assert_eq!(3, mob.attributes.pv.current);
+        assert_eq!(1, container.items.get(bread_id).unwrap().amount);
+        assert_eq!(
+            attack + 3,
+            get_attributes_with_bonus(container, mob_id)?.attack
+        );
+
+        Ok(())
+    }
+
+    fn add_equipment(
+        scenery: &mut TestScenery,
+        label: &str,
+        slots: Vec<EquipSlot>,
+        weapon: bool,
+    ) -> ItemId {
+        let item_id = builder::add_item(&mut scenery.container, label, scenery.mob_id);
+        let item = scenery.container.items.get_mut(item_id).unwrap();
+        if weapon {
+            item.weapon = Some(Weapon::new());
+        } else {
+            item.armor = Some(Armor::new());
+        }
+        item.slots = slots;
+        item_id
+    }
+
+    #[test]
+    fn test_equip_should_swap_item_in_same_slot() -> Result<()> {
+        let mut scenery = setup();
+        let sword1_id = add_equipment(&mut scenery, "sword1", vec![], true);
+        let sword2_id = add_equipment(&mut scenery, "sword2", vec![], true);
+        let armor_id = add_equipment(&mut scenery, "armor", vec![], false);
+
+        do_equip(&mut scenery.container, scenery.mob_id, sword1_id)?;
+        do_equip(&mut scenery.container, scenery.mob_id, armor_id)?;
+        do_equip(&mut scenery.container, scenery.mob_id, sword2_id)?;
+
+        let equips = &scenery.container.equips;
+        assert!(!equips.is_equipped(scenery.mob_id, sword1_id));
+        assert!(equips.is_equipped(scenery.mob_id, sword2_id));
+        assert!(equips.is_equipped(scenery.mob_id, armor_id));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_equip_two_handed_should_remove_both_hands() -> Result<()> {
+        let mut scenery = setup();
+        let sword_id = add_equipment(&mut scenery, "sword", vec![], true);
+        let shield_id = add_equipment(&mut scenery, "shield", vec![EquipSlot::OffHand], false);
+        let axe_id = add_equipment(
+            &mut scenery,
+            "axe",
+            vec![EquipSlot::MainHand, EquipSlot::OffHand],
+            true,
+        );
+
+        do_equip(&mut scenery.container, scenery.mob_id, sword_id)?;
+        do_equip(&mut scenery.container, scenery.mob_id, shield_id)?;
+        assert!(scenery
+            .container
+            .equips
+            .is_equipped(scenery.mob_id, sword_id));
+        assert!(scenery
+            .container
+            .equips
+            .is_equipped(scenery.mob_id, shield_id));
+
+        do_equip(&mut scenery.container, scenery.mob_id, axe_id)?;
+        let equips = &scenery.container.equips;
+        assert!(!equips.is_equipped(scenery.mob_id, sword_id));
+        assert!(!equips.is_equipped(scenery.mob_id, shield_id));
+        assert!(equips.is_equipped(scenery.mob_id, axe_id));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_equip_rings_should_allow_two() -> Result<()> {
+        let mut scenery = setup();
+        let ring1_id = add_equipment(&mut scenery, "ring1", vec![EquipSlot::Ring], false);
+        let ring2_id = add_equipment(&mut scenery, "ring2", vec![EquipSlot::Ring], false);
+        let ring3_id = add_equipment(&mut scenery, "ring3", vec![EquipSlot::Ring], false);
+
+        do_equip(&mut scenery.container, scenery.mob_id, ring1_id)?;
+        do_equip(&mut scenery.container, scenery.mob_id, ring2_id)?;
+        assert_eq!(2, scenery.container.equips.get(scenery.mob_id).len());
+
+        do_equip(&mut scenery.container, scenery.mob_id, ring3_id)?;
+        let equips = &scenery.container.equips;
+        assert_eq!(2, equips.get(scenery.mob_id).len());
+        assert!(equips.is_equipped(scenery.mob_id, ring3_id));
+
+        Ok(())
+    }
+
+    fn add_stackable_prefab(container: &mut Container) -> StaticId {
+        let mut flags = ItemFlagsData::new();
+        flags.stackable = Some(true);
+
+        let mut item = ItemData::new();
+        item.weight = Some(1.0);
+        item.flags = Some(flags);
+
+        let mut data = ObjData::new();
+        data.label = Some("arrow".to_string());
+        data.item = Some(item);
+
+        container.loader.add_prefab(data).unwrap()
+    }
+
+    #[test]
+    fn test_stackable_items_should_merge_on_pickup_and_split_on_drop() -> Result<()> {
+        let mut scenery = setup();
+        builder::set_mob_max_carry_weight(&mut scenery.container, scenery.mob_id, 10.0);
+        let prefab_id = add_stackable_prefab(&mut scenery.container);
+
+        for _ in 0..3 {
+            let item_id = Loader::spawn_at(&mut scenery.container, prefab_id, scenery.room_id)?;
+            do_pickup(&mut scenery.container, scenery.mob_id, item_id, None).unwrap();
+        }
+
+        let stacks: Vec<&Item> = inventory_service::get_inventory_list(
+            &scenery.container.locations,
+            &scenery.container.items,
+            scenery.mob_id,
+        )
+        .collect();
+        assert_eq!(1, stacks.len());
+        assert_eq!(3, stacks[0].amount);
+        assert_eq!(3.0, inventory_service::compute_total_weight(&stacks));
+        let stack_id = stacks[0].id;
+
+        do_drop(&mut scenery.container, scenery.mob_id, stack_id, Some(2))?;
+        assert_eq!(1, scenery.container.items.get(stack_id).unwrap().amount);
+        let inventory = scenery.container.inventories.get(scenery.mob_id).unwrap();
+        assert_eq!(Some(1.0), inventory.current_weight);
+
+        let dropped_id =
+            inventory_service::find_stack(&scenery.container, stack_id, scenery.room_id).unwrap();
+        assert_eq!(2, scenery.container.items.get(dropped_id).unwrap().amount);
+
+        // dropping the rest should merge into the stack in the room
+        do_drop(&mut scenery.container, scenery.mob_id, stack_id, None)?;
+        assert!(!scenery.container.items.exists(stack_id));
+        assert_eq!(3, scenery.container.items.get(dropped_id).unwrap().amount);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_item_without_prefab_should_keep_instance_state() -> Result<()> {
+        let mut scenery = setup();
+        let stack_id = builder::add_item(&mut scenery.container, "arrow", scenery.mob_id);
+        {
+            let item = scenery.container.items.get_mut(stack_id).unwrap();
+            item.flags.is_stackable = true;
+            item.flags.is_random = true;
+            item.amount = 3;
+            item.durability = Some(Durability {
+                current: 4,
+                max: 10,
+            });
+        }
+        scenery.container.prices.add(Price::new(stack_id, Money(7)));
+
+        do_drop(&mut scenery.container, scenery.mob_id, stack_id, Some(2))?;
+        assert_eq!(1, scenery.container.items.get(stack_id).unwrap().amount);
+
+        let dropped_id = scenery
+            .container
+            .labels
+            .search(
+                &scenery
+                    .container
+                    .locations
+                    .list_at(scenery.room_id)
+                    .collect(),
+                "arrow",
+            )
+            .into_iter()
+            .next()
+            .expect("dropped stack not found");
+        assert_ne!(stack_id, dropped_id);
+
+        let dropped = scenery.container.items.get(dropped_id).unwrap();
+        assert_eq!(2, dropped.amount);
+        assert!(dropped.flags.is_stackable);
+        assert!(dropped.flags.is_random);
+        assert_eq!(
+            Some(Durability {
+                current: 4,
+                max: 10
+            }),
+            dropped.durability
+        );
+        assert_eq!(
+            Money(7),
+            scenery.container.prices.get(dropped_id).unwrap().price
+        );
+
+        Ok(())
+    }
 }
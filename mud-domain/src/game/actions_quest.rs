@@ -0,0 +1,182 @@
+use crate::errors::{AsResult, Error, Result};
+use crate::game::container::Container;
+use crate::game::loader::Loader;
+use crate::game::mob::MobId;
+use crate::game::quests::{QuestId, QuestObjective, QuestProgress};
+use crate::game::{comm, inventory_service};
+use commons::PlayerId;
+
+pub fn start(container: &mut Container, mob_id: MobId, quest_id: QuestId) -> Result<()> {
+    let player_id = container.players.find_from_mob(mob_id).as_result()?;
+    let quest = container.quests.get(quest_id).as_result()?;
+    let quest_label = container.labels.get_label_f(quest_id);
+
+    let log = container.quests.get_log(player_id);
+    if log.map(|log| log.is_active(quest_id)).unwrap_or(false) {
+        container
+            .outputs
+            .private(mob_id, comm::quest_already_active(quest_label));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    if log.map(|log| log.is_completed(quest_id)).unwrap_or(false) {
+        container
+            .outputs
+            .private(mob_id, comm::quest_already_completed(quest_label));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    let progress = QuestProgress::new(quest);
+    let objectives = describe_objectives(container, &progress);
+    container
+        .outputs
+        .private(mob_id, comm::quest_started(quest_label, objectives));
+
+    log::info!("{:?} start quest {:?}", player_id, quest_id);
+    container
+        .quests
+        .get_or_create_log(player_id)
+        .active
+        .push(progress);
+
+    Ok(())
+}
+
+pub fn list(container: &mut Container, mob_id: MobId) -> Result<()> {
+    let player_id = container.players.find_from_mob(mob_id).as_result()?;
+
+    let quests = container
+        .quests
+        .get_log(player_id)
+        .map(|log| {
+            log.active
+                .iter()
+                .map(|progress| {
+                    (
+                        container.labels.get_label_f(progress.quest_id),
+                        describe_objectives(container, progress),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let msg = comm::quest_list(quests);
+    container.outputs.private(mob_id, msg);
+    Ok(())
+}
+
+/// Search a active quest of the player by label
+pub fn find_active(container: &Container, player_id: PlayerId, label: &str) -> Option<QuestId> {
+    let candidates: Vec<QuestId> = container
+        .quests
+        .get_log(player_id)?
+        .active
+        .iter()
+        .map(|progress| progress.quest_id)
+        .collect();
+
+    container.labels.search(&candidates, label).first().cloned()
+}
+
+pub fn abandon(container: &mut Container, mob_id: MobId, quest_id: QuestId) -> Result<()> {
+    let player_id = container.players.find_from_mob(mob_id).as_result()?;
+    let log = container.quests.get_log_mut(player_id).as_result()?;
+    log.active.retain(|progress| progress.quest_id != quest_id);
+
+    log::info!("{:?} abandon quest {:?}", player_id, quest_id);
+
+    let quest_label = container.labels.get_label_f(quest_id);
+    container
+        .outputs
+        .private(mob_id, comm::quest_abandon(quest_label));
+    Ok(())
+}
+
+/// Move quest into completed and give the rewards to the player avatar
+pub fn complete(container: &mut Container, player_id: PlayerId, quest_id: QuestId) -> Result<()> {
+    let log = container.quests.get_log_mut(player_id).as_result()?;
+    log.active.retain(|progress| progress.quest_id != quest_id);
+    log.completed.push(quest_id);
+
+    log::info!("{:?} complete quest {:?}", player_id, quest_id);
+
+    let mob_id = container.players.get_mob(player_id).as_result()?;
+    let reward = container.quests.get(quest_id).as_result()?.reward.clone();
+    let quest_label = container.labels.get_label_f(quest_id);
+    container
+        .outputs
+        .private(mob_id, comm::quest_completed(quest_label));
+
+    if reward.xp > 0 {
+        container.mobs.update(mob_id, |mob| mob.xp += reward.xp)?;
+        container
+            .outputs
+            .private(mob_id, comm::quest_reward_xp(reward.xp));
+    }
+
+    if reward.money.as_u32() > 0 {
+        inventory_service::add_money(container, mob_id, reward.money)?;
+        container
+            .outputs
+            .private(mob_id, comm::quest_reward_money(reward.money));
+    }
+
+    for prefab_id in reward.items {
+        let item_id = Loader::spawn_at(container, prefab_id, mob_id)?;
+        let item_label = container.labels.get_label_f(item_id);
+        container
+            .outputs
+            .private(mob_id, comm::quest_reward_item(item_label));
+    }
+
+    inventory_service::update_inventory_weight(container, mob_id)?;
+
+    Ok(())
+}
+
+pub fn describe_objectives(container: &Container, progress: &QuestProgress) -> Vec<String> {
+    let quest = match container.quests.get(progress.quest_id) {
+        Some(quest) => quest,
+        None => return vec![],
+    };
+
+    quest
+        .objectives
+        .iter()
+        .zip(progress.progress.iter())
+        .map(|(objective, current)| describe_objective(container, objective, *current))
+        .collect()
+}
+
+pub fn describe_objective(
+    container: &Container,
+    objective: &QuestObjective,
+    current: u32,
+) -> String {
+    match objective {
+        QuestObjective::Kill { prefab_id, amount } => comm::quest_objective_kill(
+            container.loader.get_prefab_labelf(*prefab_id),
+            current,
+            *amount,
+        ),
+        QuestObjective::Deliver {
+            prefab_id,
+            target_id,
+            amount,
+        } => comm::quest_objective_deliver(
+            container.loader.get_prefab_labelf(*prefab_id),
+            container.labels.get_label_f(*target_id),
+            current,
+            *amount,
+        ),
+        QuestObjective::Reach { room_id } => {
+            comm::quest_objective_reach(container.labels.get_label_f(*room_id), current > 0)
+        }
+        QuestObjective::Extract { prefab_id, amount } => comm::quest_objective_extract(
+            container.loader.get_prefab_labelf(*prefab_id),
+            current,
+            *amount,
+        ),
+    }
+}
@@ -0,0 +1,463 @@
+use crate::errors::{AsResult, Error, Result};
+use crate::game::comm;
+use crate::game::container::Container;
+use crate::game::inventory_service;
+use crate::game::item::{ItemId, Weight};
+use crate::game::mob::MobId;
+use crate::game::prices::Money;
+use crate::game::trades::TradeOffer;
+
+/// Open a trade window between two players in the same room
+pub fn open(container: &mut Container, mob_id: MobId, target_id: MobId) -> Result<()> {
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let target_label = container.labels.get_label_f(target_id).to_string();
+
+    if mob_id == target_id
+        || container.players.find_from_mob(target_id).is_none()
+        || container.locations.get(target_id) != Some(room_id)
+    {
+        container
+            .outputs
+            .private(mob_id, comm::trade_invalid_target(&target_label));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    if container.trades.get(mob_id).is_some() {
+        container
+            .outputs
+            .private(mob_id, comm::trade_already_trading());
+        return Err(Error::InvalidStateFailure);
+    }
+
+    if container.trades.get(target_id).is_some() {
+        container
+            .outputs
+            .private(mob_id, comm::trade_target_busy(&target_label));
+        return Err(Error::InvalidStateFailure);
+    }
+
+    container.trades.open(mob_id, target_id, room_id)?;
+
+    let mob_label = container.labels.get_label_f(mob_id);
+    container
+        .outputs
+        .private(mob_id, comm::trade_opened(&target_label));
+    container
+        .outputs
+        .private(target_id, comm::trade_opened_partner(mob_label));
+
+    Ok(())
+}
+
+pub fn offer_item(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Result<()> {
+    let offer = get_offer_or_fail(container, mob_id)?;
+    let partner_id = offer.partner_id;
+    let item_label = container.labels.get_label_f(item_id).to_string();
+
+    let is_money = container
+        .items
+        .get(item_id)
+        .map(|item| item.flags.is_money)
+        .unwrap_or(false);
+
+    if is_money || container.locations.get(item_id) != Some(mob_id) {
+        container
+            .outputs
+            .private(mob_id, comm::trade_item_not_found(&item_label));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    if container.equips.is_equipped(mob_id, item_id) {
+        container
+            .outputs
+            .private(mob_id, comm::trade_item_equipped(&item_label));
+        return Err(Error::InvalidStateFailure);
+    }
+
+    if offer.items.contains(&item_id) {
+        container
+            .outputs
+            .private(mob_id, comm::trade_item_already_offered(&item_label));
+        return Err(Error::InvalidStateFailure);
+    }
+
+    container
+        .trades
+        .update(mob_id, |offer| offer.items.push(item_id))?;
+
+    let mob_label = container.labels.get_label_f(mob_id);
+    container
+        .outputs
+        .private(mob_id, comm::trade_item_offered(&item_label));
+    container.outputs.private(
+        partner_id,
+        comm::trade_item_offered_partner(mob_label, &item_label),
+    );
+
+    Ok(())
+}
+
+pub fn remove_item(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Result<()> {
+    let offer = get_offer_or_fail(container, mob_id)?;
+    let partner_id = offer.partner_id;
+    let item_label = container.labels.get_label_f(item_id).to_string();
+
+    if !offer.items.contains(&item_id) {
+        container
+            .outputs
+            .private(mob_id, comm::trade_item_not_found(&item_label));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    container
+        .trades
+        .update(mob_id, |offer| offer.items.retain(|id| *id != item_id))?;
+
+    let mob_label = container.labels.get_label_f(mob_id);
+    container
+        .outputs
+        .private(mob_id, comm::trade_item_removed(&item_label));
+    container.outputs.private(
+        partner_id,
+        comm::trade_item_removed_partner(mob_label, &item_label),
+    );
+
+    Ok(())
+}
+
+pub fn offer_money(container: &mut Container, mob_id: MobId, amount: Money) -> Result<()> {
+    let partner_id = get_offer_or_fail(container, mob_id)?.partner_id;
+
+    let current = inventory_service::get_money(container, mob_id)?;
+    if current.as_u32() < amount.as_u32() {
+        container
+            .outputs
+            .private(mob_id, comm::trade_not_enough_money(amount));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    container
+        .trades
+        .update(mob_id, |offer| offer.money = amount)?;
+
+    let mob_label = container.labels.get_label_f(mob_id);
+    container
+        .outputs
+        .private(mob_id, comm::trade_money_offered(amount));
+    container.outputs.private(
+        partner_id,
+        comm::trade_money_offered_partner(mob_label, amount),
+    );
+
+    Ok(())
+}
+
+/// confirm the trade, when both parties have confirmed the trade is executed
+pub fn confirm(container: &mut Container, mob_id: MobId) -> Result<()> {
+    let partner_id = get_offer_or_fail(container, mob_id)?.partner_id;
+
+    container.trades.confirm(mob_id)?;
+
+    if !container.trades.is_confirmed(partner_id) {
+        let mob_label = container.labels.get_label_f(mob_id);
+        let partner_label = container.labels.get_label_f(partner_id);
+        container
+            .outputs
+            .private(mob_id, comm::trade_confirmed(partner_label));
+        container
+            .outputs
+            .private(partner_id, comm::trade_confirmed_partner(mob_label));
+        return Ok(());
+    }
+
+    execute(container, mob_id)
+}
+
+pub fn cancel(container: &mut Container, mob_id: MobId) -> Result<()> {
+    match container.trades.close(mob_id) {
+        Some((offer, _)) => {
+            container.outputs.private(mob_id, comm::trade_cancelled());
+            container
+                .outputs
+                .private(offer.partner_id, comm::trade_cancelled());
+            Ok(())
+        }
+        None => {
+            container.outputs.private(mob_id, comm::trade_not_trading());
+            Err(Error::InvalidStateFailure)
+        }
+    }
+}
+
+pub fn show(container: &mut Container, mob_id: MobId) -> Result<()> {
+    let offer = get_offer_or_fail(container, mob_id)?;
+    let partner_offer = container.trades.get(offer.partner_id).as_result()?;
+
+    let msg = comm::trade_show(
+        &get_offer_desc(container, &offer),
+        &get_offer_desc(container, partner_offer),
+    );
+    container.outputs.private(mob_id, msg);
+    Ok(())
+}
+
+fn execute(container: &mut Container, mob_id: MobId) -> Result<()> {
+    let (offer, partner_offer) = match container.trades.close(mob_id) {
+        Some((offer, Some(partner_offer))) => (offer, partner_offer),
+        _ => return Err(Error::InvalidStateException),
+    };
+
+    let parties = [&offer, &partner_offer];
+
+    // check that everything offered still available and money can be delivered
+    let all_valid = parties.iter().all(|offer| is_offer_valid(container, offer))
+        && [(&offer, &partner_offer), (&partner_offer, &offer)]
+            .iter()
+            .all(|(giver, receiver)| {
+                giver.money.as_u32() == 0 || can_receive_money(container, receiver.mob_id)
+            });
+    if !all_valid {
+        for offer in &parties {
+            container
+                .outputs
+                .private(offer.mob_id, comm::trade_fail_invalid());
+        }
+        return Err(Error::InvalidStateFailure);
+    }
+
+    // check that both parties can carry what they will receive
+    for (receiver, giver) in &[(&offer, &partner_offer), (&partner_offer, &offer)] {
+        let delta = get_offer_weight(container, giver) - get_offer_weight(container, receiver);
+        let can_carry = delta <= 0.0
+            || container
+                .inventories
+                .get(receiver.mob_id)
+                .map(|inv| inv.can_add(delta))
+                .unwrap_or(true);
+
+        if !can_carry {
+            let label = container.labels.get_label_f(receiver.mob_id).to_string();
+            for offer in &parties {
+                container
+                    .outputs
+                    .private(offer.mob_id, comm::trade_fail_weight(&label));
+            }
+            return Err(Error::InvalidStateFailure);
+        }
+    }
+
+    // swap, money is taken from both parties before anything is delivered
+    for giver in &parties {
+        if giver.money.as_u32() > 0 {
+            inventory_service::remove_money(container, giver.mob_id, giver.money)?;
+        }
+    }
+
+    for (giver, receiver) in &[(&offer, &partner_offer), (&partner_offer, &offer)] {
+        if giver.money.as_u32() > 0 {
+            inventory_service::add_money(container, receiver.mob_id, giver.money)?;
+        }
+
+        for item_id in &giver.items {
+            container.locations.set(*item_id, receiver.mob_id);
+        }
+    }
+
+    for offer in &parties {
+        inventory_service::update_inventory_weight(container, offer.mob_id)?;
+        let partner_label = container.labels.get_label_f(offer.partner_id);
+        container
+            .outputs
+            .private(offer.mob_id, comm::trade_completed(partner_label));
+    }
+
+    Ok(())
+}
+
+fn is_offer_valid(container: &Container, offer: &TradeOffer) -> bool {
+    let items_valid = offer.items.iter().all(|item_id| {
+        container.locations.get(*item_id) == Some(offer.mob_id)
+            && !container.equips.is_equipped(offer.mob_id, *item_id)
+    });
+
+    let money_valid = inventory_service::get_money(container, offer.mob_id)
+        .map(|money| money.as_u32() >= offer.money.as_u32())
+        .unwrap_or(false);
+
+    items_valid && money_valid
+}
+
+/// mob already have money or a new one can be created
+fn can_receive_money(container: &Container, mob_id: MobId) -> bool {
+    inventory_service::get_money_id(container, mob_id).is_some()
+        || container.config.money_id.is_some()
+}
+
+/// weight of offered items, including its contents
+fn get_offer_weight(container: &Container, offer: &TradeOffer) -> Weight {
+    offer
+        .items
+        .iter()
+        .map(|item_id| inventory_service::get_deep_weight(container, *item_id))
+        .sum()
+}
+
+fn get_offer_desc<'a>(container: &'a Container, offer: &TradeOffer) -> comm::TradeOfferDesc<'a> {
+    comm::TradeOfferDesc {
+        label: container.labels.get_label_f(offer.mob_id),
+        items: container.labels.resolve_labels(&offer.items),
+        money: offer.money,
+        confirmed: offer.confirmed,
+    }
+}
+
+fn get_offer_or_fail(container: &mut Container, mob_id: MobId) -> Result<TradeOffer> {
+    match container.trades.get(mob_id) {
+        Some(offer) => Ok(offer.clone()),
+        None => {
+            container.outputs.private(mob_id, comm::trade_not_trading());
+            Err(Error::InvalidStateFailure)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::builder;
+    use crate::game::inventory::Inventory;
+    use crate::game::system::trade_system;
+
+    struct Scenery {
+        container: Container,
+        mob_a: MobId,
+        mob_b: MobId,
+        item_a: ItemId,
+    }
+
+    fn setup() -> Scenery {
+        let mut container = Container::new();
+        let room_id = builder::add_room(&mut container, "room");
+        let mob_a = builder::add_mob(&mut container, "alice", room_id);
+        let mob_b = builder::add_mob(&mut container, "bob", room_id);
+        container
+            .players
+            .create(container.objects.create(), "alice".to_string(), mob_a);
+        container
+            .players
+            .create(container.objects.create(), "bob".to_string(), mob_b);
+        let item_a = builder::add_item(&mut container, "sword", mob_a);
+
+        Scenery {
+            container,
+            mob_a,
+            mob_b,
+            item_a,
+        }
+    }
+
+    #[test]
+    fn test_trade_should_swap_items_when_both_confirm() {
+        let Scenery {
+            mut container,
+            mob_a,
+            mob_b,
+            item_a,
+            ..
+        } = setup();
+
+        open(&mut container, mob_a, mob_b).unwrap();
+        offer_item(&mut container, mob_a, item_a).unwrap();
+        confirm(&mut container, mob_a).unwrap();
+        assert_eq!(Some(mob_a), container.locations.get(item_a));
+
+        confirm(&mut container, mob_b).unwrap();
+        assert_eq!(Some(mob_b), container.locations.get(item_a));
+        assert!(container.trades.get(mob_a).is_none());
+        assert!(container.trades.get(mob_b).is_none());
+    }
+
+    #[test]
+    fn test_trade_should_fail_when_receiver_can_not_carry() {
+        let Scenery {
+            mut container,
+            mob_a,
+            mob_b,
+            item_a,
+            ..
+        } = setup();
+
+        container.items.get_mut(item_a).unwrap().weight = Some(5.0);
+        let mut inventory = Inventory::new(mob_b);
+        inventory.max_weight = Some(1.0);
+        container.inventories.add(inventory).unwrap();
+
+        open(&mut container, mob_a, mob_b).unwrap();
+        offer_item(&mut container, mob_a, item_a).unwrap();
+        confirm(&mut container, mob_a).unwrap();
+        assert!(confirm(&mut container, mob_b).is_err());
+        assert_eq!(Some(mob_a), container.locations.get(item_a));
+    }
+
+    #[test]
+    fn test_trade_should_not_move_anything_when_money_can_not_be_delivered() {
+        let Scenery {
+            mut container,
+            mob_a,
+            mob_b,
+            item_a,
+        } = setup();
+
+        let money_id = builder::add_item(&mut container, "gold", mob_b);
+        let money = container.items.get_mut(money_id).unwrap();
+        money.flags.is_money = true;
+        money.amount = 10;
+
+        // alice has no money and there is no money prefab to create one
+        open(&mut container, mob_a, mob_b).unwrap();
+        offer_item(&mut container, mob_a, item_a).unwrap();
+        offer_money(&mut container, mob_b, Money(5)).unwrap();
+        confirm(&mut container, mob_a).unwrap();
+        assert!(confirm(&mut container, mob_b).is_err());
+
+        assert_eq!(Some(mob_a), container.locations.get(item_a));
+        assert_eq!(
+            Money(10),
+            inventory_service::get_money(&container, mob_b).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_trade_should_be_cancelled_when_party_leaves() {
+        let Scenery {
+            mut container,
+            mob_a,
+            mob_b,
+            ..
+        } = setup();
+
+        open(&mut container, mob_a, mob_b).unwrap();
+        let other_room_id = builder::add_room(&mut container, "other room");
+        container.locations.set(mob_b, other_room_id);
+
+        trade_system::run(&mut container);
+        assert!(container.trades.get(mob_a).is_none());
+        assert!(container.trades.get(mob_b).is_none());
+    }
+
+    #[test]
+    fn test_trade_changes_should_require_new_confirmation() {
+        let Scenery {
+            mut container,
+            mob_a,
+            mob_b,
+            item_a,
+        } = setup();
+
+        open(&mut container, mob_a, mob_b).unwrap();
+        confirm(&mut container, mob_b).unwrap();
+        offer_item(&mut container, mob_a, item_a).unwrap();
+        confirm(&mut container, mob_a).unwrap();
+        assert_eq!(Some(mob_a), container.locations.get(item_a));
+    }
+}
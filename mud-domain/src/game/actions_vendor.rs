@@ -1,23 +1,47 @@
 use crate::errors::*;
 use crate::game::comm::VendorTradeItemDisplay;
 use crate::game::container::Container;
-use crate::game::item::ItemId;
+use crate::game::item::{Durability, ItemId};
 use crate::game::labels::Labels;
-use crate::game::loader::{dto::StaticId, Loader};
+use crate::game::loader::dto::{ObjData, StaticId};
+use crate::game::loader::Loader;
 use crate::game::market::MarketTrade;
 use crate::game::mob::MobId;
 use crate::game::prices::Money;
-use crate::game::tags::Tags;
-use crate::game::{comm, inventory_service};
+use crate::game::tags::{TagId, Tags};
+use crate::game::{actions_items, comm, inventory_service, random_items};
 use crate::utils::strinput::StrInput;
+use commons::prob;
 use commons::{Either, ObjId};
 
+/// fraction of the item price charged to repair it from broken
+pub const REPAIR_PRICE_MULT: f32 = 0.5;
+
 #[derive(Debug)]
 pub struct VendorTradeObj {
     pub static_id: StaticId,
     pub base_price: Money,
     pub buy_price: Option<Money>,
     pub sell_price: Option<Money>,
+    /// amount in stock, None if unlimited
+    pub amount: Option<u32>,
+}
+
+/// resolve prefab tags without creating new ones
+fn get_prefab_tags(tags: &Tags, data: &ObjData) -> Vec<TagId> {
+    data.tags
+        .iter()
+        .flat_map(|tags_data| tags_data.values.iter())
+        .flat_map(|value| tags.find_id(value.as_str()))
+        .collect()
+}
+
+fn get_prefab_tags_by_id(container: &Container, static_id: StaticId) -> Vec<TagId> {
+    container
+        .loader
+        .get_prefab(static_id)
+        .map(|data| get_prefab_tags(&container.tags, data))
+        .unwrap_or(vec![])
 }
 
 pub fn get_vendor_trades(container: &Container, vendor_id: MobId) -> Option<&Vec<MarketTrade>> {
@@ -32,6 +56,7 @@ pub fn find_vendor_list(container: &Container, vendor_id: MobId) -> Result<Vec<V
 
     let trades = get_vendor_trades(container, vendor_id)
         .as_exception_str(format!("vendor {:?} must have a market", vendor_id))?;
+    let stock = &container.vendors.get(vendor_id).as_result()?.stock;
 
     for trade in trades {
         let tags_str = container
@@ -60,11 +85,24 @@ pub fn find_vendor_list(container: &Container, vendor_id: MobId) -> Result<Vec<V
 
             let price = price.price.as_result_exception()?;
 
+            let prefab_tags = get_prefab_tags(&container.tags, data);
+            if stock.get_max(&prefab_tags) == Some(0) {
+                // vendor with limited stock only trade what is in the stock
+                continue;
+            }
+
+            let stock_mult = stock.get_price_mult(data_id, &prefab_tags);
+
             let trade_obj = VendorTradeObj {
                 static_id: data_id,
                 base_price: Money(price),
-                buy_price: trade.buy_price_mult.map(|mult| Money(price).mult(mult)),
-                sell_price: trade.sell_price_mult.map(|mult| Money(price).mult(mult)),
+                buy_price: trade
+                    .buy_price_mult
+                    .map(|mult| Money(price).mult(mult * stock_mult)),
+                sell_price: trade
+                    .sell_price_mult
+                    .map(|mult| Money(price).mult(mult * stock_mult)),
+                amount: stock.get_amount(data_id, &prefab_tags),
             };
             result.push(trade_obj);
         }
@@ -89,6 +127,7 @@ pub fn vendor_items_to_vendor_list_items<'a>(
                 label: label,
                 to_buy: vendor_trade.sell_price,
                 to_sell: vendor_trade.buy_price,
+                amount: vendor_trade.amount,
             };
 
             display
@@ -96,9 +135,67 @@ pub fn vendor_items_to_vendor_list_items<'a>(
         .collect()
 }
 
+/// random generated items in the vendor inventory
+pub fn list_random_stock(container: &Container, vendor_id: MobId) -> Vec<ItemId> {
+    let mut list: Vec<ItemId> = container
+        .locations
+        .list_at(vendor_id)
+        .filter(|&id| {
+            container
+                .items
+                .get(id)
+                .map(|item| item.flags.is_random)
+                .unwrap_or(false)
+        })
+        .filter(|&id| !container.equips.get(vendor_id).contains(&id))
+        .collect();
+    list.sort();
+    list
+}
+
+/// price the vendor charge for a item in its inventory
+pub fn get_item_sell_price(
+    container: &Container,
+    vendor_id: MobId,
+    item_id: ItemId,
+) -> Option<Money> {
+    let price = container.prices.get(item_id)?;
+    get_vendor_trades(container, vendor_id)?
+        .iter()
+        .filter(|trade| container.tags.has_any(item_id, &trade.tags))
+        .flat_map(|trade| trade.sell_price_mult)
+        .map(|mult| price.price.mult(mult))
+        .next()
+}
+
+/// Generate random items into the vendor inventory until it reach the random stock
+pub fn restock_random_items(container: &mut Container, vendor_id: MobId) -> Result<()> {
+    let random_stock = match container.vendors.get(vendor_id) {
+        Some(vendor) => vendor.random_stock as usize,
+        None => return Ok(()),
+    };
+
+    let current = list_random_stock(container, vendor_id).len();
+    let seed = prob::seed_for(vendor_id, container.time.tick);
+    for i in current..random_stock {
+        random_items::spawn_at(container, seed + i as u64, vendor_id)?;
+    }
+
+    Ok(())
+}
+
 pub fn list(container: &mut Container, mob_id: MobId, vendor_id: MobId) -> Result<()> {
     let list = find_vendor_list(container, vendor_id)?;
-    let list = vendor_items_to_vendor_list_items(container, &list);
+    let mut list = vendor_items_to_vendor_list_items(container, &list);
+
+    for item_id in list_random_stock(container, vendor_id) {
+        list.push(VendorTradeItemDisplay {
+            label: container.labels.get_label_f(item_id),
+            to_buy: get_item_sell_price(container, vendor_id, item_id),
+            to_sell: None,
+            amount: Some(1),
+        });
+    }
 
     let msg = comm::vendor_list(list);
     container.outputs.private(mob_id, msg);
@@ -134,19 +231,20 @@ pub fn buy(
         }
     };
 
-    let buy_price: Money = match vendor_items
+    let label = data
+        .label
+        .as_ref()
+        .map(|s| s.as_str())
+        .unwrap_or("undefined");
+
+    let (buy_price, amount) = match vendor_items
         .iter()
         .filter(|i| i.static_id == item_static_id)
-        .flat_map(|i| i.sell_price)
+        .flat_map(|i| i.sell_price.map(|price| (price, i.amount)))
         .next()
     {
-        Some(price) => price,
+        Some(value) => value,
         None => {
-            let label = data
-                .label
-                .as_ref()
-                .map(|s| s.as_str())
-                .unwrap_or("undefined");
             container
                 .outputs
                 .private(mob_id, comm::vendor_buy_item_not_found(label));
@@ -155,6 +253,13 @@ pub fn buy(
         }
     };
 
+    if amount == Some(0) {
+        container
+            .outputs
+            .private(mob_id, comm::vendor_buy_out_of_stock(label));
+        return Err(Error::InvalidStateFailure);
+    }
+
     let mob_money = inventory_service::get_money(container, mob_id)?;
 
     if mob_money.as_u32() < buy_price.as_u32() {
@@ -197,6 +302,13 @@ pub fn buy(
             container.outputs.private(mob_id, comm::vendor_buy_fail());
             err
         })?;
+    let item_id = inventory_service::add_or_merge(container, item_id, item_spawn_location_id)?;
+
+    // remove from vendor stock
+    let prefab_tags = get_prefab_tags_by_id(container, item_static_id);
+    if let Some(vendor) = container.vendors.get_mut(vendor_id) {
+        vendor.stock.take(item_static_id, &prefab_tags);
+    }
 
     // send messages
     let mob_label = container.labels.get_label_f(mob_id);
@@ -246,13 +358,26 @@ pub fn sell(
 
     log::debug!("selling for {:?}", vendor_trades);
 
-    let sell_price = match (vendor_trades, container.prices.get(item_id)) {
-        (Some(trades), Some(price)) => {
+    let prefab_id = container.objects.get_prefab_id(item_id);
+    let prefab_tags = prefab_id
+        .map(|prefab_id| get_prefab_tags_by_id(container, prefab_id))
+        .unwrap_or(vec![]);
+    let stock_mult = match (prefab_id, container.vendors.get(vendor_id)) {
+        // vendor with limited stock only trade what is in the stock
+        (Some(_), Some(vendor)) if vendor.stock.get_max(&prefab_tags) == Some(0) => None,
+        (Some(prefab_id), Some(vendor)) => {
+            Some(vendor.stock.get_price_mult(prefab_id, &prefab_tags))
+        }
+        _ => Some(1.0),
+    };
+
+    let sell_price = match (vendor_trades, container.prices.get(item_id), stock_mult) {
+        (Some(trades), Some(price), Some(stock_mult)) => {
             let sell_price = trades
                 .iter()
                 .filter(|trade| container.tags.has_any(item_id, &trade.tags))
                 .flat_map(|trade| trade.buy_price_mult)
-                .map(|buy_price_mult| price.price.mult(buy_price_mult))
+                .map(|buy_price_mult| price.price.mult(buy_price_mult * stock_mult))
                 .next();
 
             sell_price
@@ -288,6 +413,11 @@ pub fn sell(
     container.remove(item_id);
     inventory_service::update_inventory_weight(container, mob_id)?;
 
+    // add into vendor stock
+    if let (Some(prefab_id), Some(vendor)) = (prefab_id, container.vendors.get_mut(vendor_id)) {
+        vendor.stock.put(prefab_id, &prefab_tags);
+    }
+
     // print messages
     let mob_label = container.labels.get_label_f(mob_id);
     let location_id = container.locations.get(mob_id).unwrap();
@@ -303,3 +433,264 @@ pub fn sell(
 
     Ok(())
 }
+
+/// Buy a random generated item from the vendor inventory
+pub fn buy_item(
+    container: &mut Container,
+    mob_id: MobId,
+    vendor_id: MobId,
+    item_id: ItemId,
+) -> Result<()> {
+    let location_id = container.locations.get(mob_id).as_result()?;
+    let item_label = container.labels.get_label_f(item_id).to_string();
+
+    let price = match get_item_sell_price(container, vendor_id, item_id) {
+        Some(price) if list_random_stock(container, vendor_id).contains(&item_id) => price,
+        _ => {
+            container
+                .outputs
+                .private(mob_id, comm::vendor_buy_item_not_found(&item_label));
+            return Err(Error::InvalidArgumentFailure);
+        }
+    };
+
+    let mob_money = inventory_service::get_money(container, mob_id)?;
+    if mob_money.as_u32() < price.as_u32() {
+        container.outputs.private(
+            mob_id,
+            comm::vendor_buy_you_have_not_enough_money(mob_money, price),
+        );
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    let new_mob_money = inventory_service::remove_money(container, mob_id, price)?;
+    container.locations.set(item_id, mob_id);
+    inventory_service::update_inventory_weight(container, mob_id)?;
+
+    let mob_label = container.labels.get_label_f(mob_id);
+    container.outputs.private(
+        mob_id,
+        comm::vendor_buy_success(&item_label, price, new_mob_money),
+    );
+    container.outputs.broadcast(
+        Some(mob_id),
+        location_id,
+        comm::vendor_buy_success_others(mob_label, &item_label),
+    );
+
+    Ok(())
+}
+
+/// repair cost is proportional to the item price and the missing durability
+pub fn get_repair_cost(container: &Container, item_id: ItemId, durability: &Durability) -> Money {
+    let missing_ratio = 1.0 - durability.ratio();
+    let cost = match container.prices.get(item_id) {
+        Some(price) => price.price.mult(missing_ratio * REPAIR_PRICE_MULT),
+        None => Money(durability.missing()),
+    };
+
+    Money(cost.as_u32().max(1))
+}
+
+pub fn repair(
+    container: &mut Container,
+    mob_id: MobId,
+    item_id: ItemId,
+    vendor_id: MobId,
+) -> Result<()> {
+    let durability = actions_items::check_repairable(container, mob_id, item_id)?;
+    let cost = get_repair_cost(container, item_id, &durability);
+
+    let mob_money = inventory_service::get_money(container, mob_id)?;
+    if mob_money.as_u32() < cost.as_u32() {
+        container.outputs.private(
+            mob_id,
+            comm::vendor_repair_not_enough_money(mob_money, cost),
+        );
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    inventory_service::remove_money(container, mob_id, cost)?;
+
+    if let Some(durability) = container
+        .items
+        .get_mut(item_id)
+        .and_then(|item| item.durability.as_mut())
+    {
+        durability.current = durability.max;
+    }
+
+    let item_label = container.labels.get_label_f(item_id);
+    let vendor_label = container.labels.get_label_f(vendor_id);
+    container.outputs.private(
+        mob_id,
+        comm::vendor_repair_success(vendor_label, item_label, cost),
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::builder;
+
+    const SCENERY: &str = r#"
+cfg {
+  avatar_mob: 3
+  initial_room: 0
+  money_id: 1
+}
+objects.0 {
+  id: 0
+  label: "village"
+  room {}
+}
+objects.1 {
+  id: 1
+  label: "vendor"
+  parent: 0
+  vendor {
+    market_id: 2
+    stock: [{tag: "weapon", amount: 1}]
+  }
+}
+objects.2 {
+  id: 2
+  label: "market"
+  market {
+    trades: [
+      {
+        buy_price_mult: 0.5
+        sell_price_mult: 1.0
+        tags: ["item"]
+      }
+    ]
+  }
+}
+prefabs.1 {
+  id: 1
+  label: "gold"
+  item {
+    amount: 1
+    flags.money = true
+  }
+}
+prefabs.2 {
+  id: 2
+  label: "sword"
+  item.weight: 1.0
+  tags.values: ["item", "weapon"]
+  price.price: 100
+}
+prefabs.3 {
+  id: 3
+  label: "meat"
+  item.weight: 1.0
+  tags.values: ["item"]
+  price.price: 1
+}
+"#;
+
+    #[test]
+    fn test_vendor_stock_should_limit_buy_and_change_prices() {
+        let mut container = Container::new();
+        Loader::load_hocon(&mut container, SCENERY).unwrap();
+        let vendor_id = ObjId(1);
+        let mob_id = builder::add_mob(&mut container, "player", ObjId(0));
+        inventory_service::add_money(&mut container, mob_id, Money(1000)).unwrap();
+
+        // items without stock limit are not traded
+        let list = find_vendor_list(&container, vendor_id).unwrap();
+        assert_eq!(1, list.len());
+        assert_eq!(StaticId(2), list[0].static_id);
+        assert_eq!(Some(1), list[0].amount);
+        assert_eq!(Some(Money(100)), list[0].sell_price);
+
+        let item_id = buy(&mut container, mob_id, vendor_id, StaticId(2)).unwrap();
+        assert!(buy(&mut container, mob_id, vendor_id, StaticId(2)).is_err());
+
+        // empty stock should increase prices
+        let list = find_vendor_list(&container, vendor_id).unwrap();
+        assert_eq!(Some(0), list[0].amount);
+        assert_eq!(Some(Money(150)), list[0].sell_price);
+
+        sell(&mut container, mob_id, item_id, vendor_id).unwrap();
+        let list = find_vendor_list(&container, vendor_id).unwrap();
+        assert_eq!(Some(1), list[0].amount);
+
+        // items out of the stock can not be sold
+        let meat_id = Loader::spawn_at(&mut container, StaticId(3), mob_id).unwrap();
+        assert!(sell(&mut container, mob_id, meat_id, vendor_id).is_err());
+        assert_eq!(Some(mob_id), container.locations.get(meat_id));
+    }
+
+    #[test]
+    fn test_vendor_repair_should_charge_and_restore_durability() {
+        let mut container = Container::new();
+        Loader::load_hocon(&mut container, SCENERY).unwrap();
+        let vendor_id = ObjId(1);
+        let mob_id = builder::add_mob(&mut container, "player", ObjId(0));
+        inventory_service::add_money(&mut container, mob_id, Money(30)).unwrap();
+
+        let sword_id = Loader::spawn_at(&mut container, StaticId(2), mob_id).unwrap();
+        assert!(repair(&mut container, mob_id, sword_id, vendor_id).is_err());
+
+        container.items.get_mut(sword_id).unwrap().durability = Some(Durability {
+            current: 0,
+            max: 10,
+        });
+
+        // broken sword cost half of its price, more than the mob can pay
+        assert!(repair(&mut container, mob_id, sword_id, vendor_id).is_err());
+
+        container
+            .items
+            .get_mut(sword_id)
+            .unwrap()
+            .durability
+            .as_mut()
+            .unwrap()
+            .current = 5;
+
+        repair(&mut container, mob_id, sword_id, vendor_id).unwrap();
+        let durability = container.items.get(sword_id).unwrap().durability.unwrap();
+        assert_eq!(10, durability.current);
+        assert_eq!(
+            Money(5),
+            inventory_service::get_money(&container, mob_id).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_vendor_should_restock_and_sell_random_items() {
+        let mut container = Container::new();
+        Loader::load_hocon(&mut container, SCENERY).unwrap();
+        container.config.random_items = Some(random_items::test::load_conf());
+        let vendor_id = ObjId(1);
+        let mob_id = builder::add_mob(&mut container, "player", ObjId(0));
+        inventory_service::add_money(&mut container, mob_id, Money(1000)).unwrap();
+
+        container.vendors.get_mut(vendor_id).unwrap().random_stock = 2;
+
+        // only generated items are random stock
+        builder::add_item(&mut container, "bag", vendor_id);
+
+        restock_random_items(&mut container, vendor_id).unwrap();
+        restock_random_items(&mut container, vendor_id).unwrap();
+
+        let stock = list_random_stock(&container, vendor_id);
+        assert_eq!(2, stock.len());
+
+        let item_id = stock[0];
+        let price = get_item_sell_price(&container, vendor_id, item_id).unwrap();
+        buy_item(&mut container, mob_id, vendor_id, item_id).unwrap();
+
+        assert_eq!(Some(mob_id), container.locations.get(item_id));
+        assert_eq!(1, list_random_stock(&container, vendor_id).len());
+        assert_eq!(
+            Money(1000 - price.as_u32()),
+            inventory_service::get_money(&container, mob_id).unwrap()
+        );
+    }
+}
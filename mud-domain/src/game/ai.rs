@@ -1,6 +1,7 @@
 use crate::errors::{Error, Result};
 use crate::game::loader::dto::{
-    AiCommandAggressivePatrolHomeData, AiData, CanLoad, CanSnapshot, ObjData,
+    AiCommandAggressivePatrolHomeData, AiCommandHaulData, AiCommandRoamData, AiData, CanLoad,
+    CanSnapshot, ObjData, StaticId,
 };
 use crate::game::mob::{MobCommand, MobId};
 use crate::game::room::RoomId;
@@ -10,7 +11,6 @@ use crate::game::loader::LoadingCtx;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-// TODO: implement those commands, they are not in use
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AiCommand {
     Idle,
@@ -23,9 +23,15 @@ pub enum AiCommand {
         /// distance from spawn, zero means stay in the same room
         distance: u32,
     },
+    /// like passive, but keep wandering into 'distance' from its home
+    Roam {
+        distance: u32,
+    },
+    /// follow the target between rooms and attack anyone fighting it
     FollowAndProtect {
         target_id: ObjId,
     },
+    /// carry items from one storage to other, storages can be rooms or containers
     Hauler {
         from: ObjId,
         to: ObjId,
@@ -41,6 +47,8 @@ pub struct Ai {
     pub command: AiCommand,
     /// can have its commands change by owner?
     pub commandable: bool,
+    /// where the mob was when the ai start to run, used to patrol around
+    pub home_id: Option<RoomId>,
 }
 
 impl Ai {
@@ -49,6 +57,7 @@ impl Ai {
             id,
             command: AiCommand::Idle,
             commandable: false,
+            home_id: None,
         }
     }
 }
@@ -99,9 +108,34 @@ impl AiRepo {
 }
 
 impl CanLoad for AiRepo {
-    fn load(&mut self, _references: &LoadingCtx, obj_id: ObjId, data: &ObjData) -> Result<()> {
+    fn load(&mut self, references: &LoadingCtx, obj_id: ObjId, data: &ObjData) -> Result<()> {
         if let Some(ai_data) = &data.ai {
-            let ai = parse_ai(obj_id, ai_data);
+            let mut ai = parse_ai(obj_id, ai_data);
+
+            // references to objects in the same file are mapped to the created objects
+            let resolve = |id: ObjId| {
+                references
+                    .id_map
+                    .get(&StaticId(id.as_u32()))
+                    .cloned()
+                    .unwrap_or(id)
+            };
+
+            ai.command = match ai.command {
+                AiCommand::FollowAndProtect { target_id } => AiCommand::FollowAndProtect {
+                    target_id: resolve(target_id),
+                },
+                AiCommand::Hauler { from, to } => AiCommand::Hauler {
+                    from: resolve(from),
+                    to: resolve(to),
+                },
+                AiCommand::Extract { from } => AiCommand::Extract {
+                    from: resolve(from),
+                },
+                other => other,
+            };
+            ai.home_id = ai.home_id.map(resolve);
+
             self.add_or_update(ai).unwrap();
         }
 
@@ -127,17 +161,21 @@ pub fn serialize_ai(ai: &Ai) -> AiData {
         } else {
             None
         },
+        command_passive: if ai.command == AiCommand::Passive {
+            Some(true)
+        } else {
+            None
+        },
         command_follow_and_protect: match ai.command {
             AiCommand::FollowAndProtect { target_id } => Some(target_id),
             _ => None,
         },
         command_haul: match &ai.command {
-            AiCommand::Hauler { from: _, to: _ } => todo!(),
-            // AiCommand::Hauler { from, to, wares } => Some(AiCommandHaulData {
-            //     from_id: *from,
-            //     to_id: *to,
-            //     targets: wares.clone(),
-            // }),
+            AiCommand::Hauler { from, to } => Some(AiCommandHaulData {
+                from_id: *from,
+                to_id: *to,
+                targets: vec![],
+            }),
             _ => None,
         },
         command_aggressive_patrol_home: match &ai.command {
@@ -148,26 +186,43 @@ pub fn serialize_ai(ai: &Ai) -> AiData {
             }
             _ => None,
         },
+        command_roam: match &ai.command {
+            AiCommand::Roam { distance } => Some(AiCommandRoamData {
+                distance: *distance,
+            }),
+            _ => None,
+        },
+        command_extract: match ai.command {
+            AiCommand::Extract { from } => Some(from),
+            _ => None,
+        },
         commandable: if ai.commandable { Some(true) } else { None },
+        home_id: ai.home_id,
     }
 }
 
 pub fn parse_ai(obj_id: ObjId, ai_data: &AiData) -> Ai {
     let command = if ai_data.command_aggressive.unwrap_or(false) {
         AiCommand::Aggressive
+    } else if ai_data.command_passive.unwrap_or(false) {
+        AiCommand::Passive
     } else if let Some(target_id) = ai_data.command_follow_and_protect {
         AiCommand::FollowAndProtect { target_id }
-    } else if let Some(_haul) = &ai_data.command_haul {
-        // AiCommand::Hauler {
-        //     from: haul.from_id.clone(),
-        //     to: haul.to_id.clone(),
-        //     wares: haul.targets.clone(),
-        // }
-        panic!("not implemented")
+    } else if let Some(haul) = &ai_data.command_haul {
+        AiCommand::Hauler {
+            from: haul.from_id,
+            to: haul.to_id,
+        }
     } else if let Some(patrol_data) = &ai_data.command_aggressive_patrol_home {
         AiCommand::AggressivePatrolHome {
             distance: patrol_data.distance,
         }
+    } else if let Some(roam_data) = &ai_data.command_roam {
+        AiCommand::Roam {
+            distance: roam_data.distance,
+        }
+    } else if let Some(from) = ai_data.command_extract {
+        AiCommand::Extract { from }
     } else {
         AiCommand::Idle
     };
@@ -176,5 +231,6 @@ pub fn parse_ai(obj_id: ObjId, ai_data: &AiData) -> Ai {
         id: obj_id,
         command: command,
         commandable: ai_data.commandable.unwrap_or(false),
+        home_id: ai_data.home_id,
     }
 }
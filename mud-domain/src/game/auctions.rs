@@ -0,0 +1,179 @@
+use crate::errors::{Error, Result};
+use crate::game::loader::dto::{
+    AuctionBidData, AuctionData, AuctionListingData, CanLoad, CanSnapshot, ObjData, StaticId,
+};
+use crate::game::loader::LoadingCtx;
+use crate::game::prices::Money;
+use commons::{ObjId, PlayerId, TotalTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuctionBid {
+    pub bidder_id: PlayerId,
+    /// money already paid by the bidder
+    pub amount: Money,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuctionListing {
+    pub buyout: Money,
+    pub bid: Option<AuctionBid>,
+    pub expire: TotalTime,
+    /// sale fee of the market where the item was listed
+    pub sale_fee: f32,
+}
+
+impl AuctionListing {
+    pub fn min_bid(&self) -> Money {
+        self.bid
+            .as_ref()
+            .map(|bid| Money(bid.amount.as_u32() + 1))
+            .unwrap_or(Money(1))
+    }
+
+    /// how much of the sale price is kept by the market
+    pub fn compute_fee(&self, price: Money) -> Money {
+        Money(price.mult(self.sale_fee).as_u32().min(price.as_u32()))
+    }
+}
+
+/// Object held by the auction house. Listed lots are owned by the seller, lots without listing
+/// are waiting the owner to collect it, like sold items, proceeds or expired items.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuctionLot {
+    pub id: ObjId,
+    pub owner_id: PlayerId,
+    pub listing: Option<AuctionListing>,
+}
+
+impl AuctionLot {
+    pub fn is_listed(&self) -> bool {
+        self.listing.is_some()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Auctions {
+    index: HashMap<ObjId, AuctionLot>,
+}
+
+impl Auctions {
+    pub fn new() -> Self {
+        Auctions {
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, lot: AuctionLot) -> Result<()> {
+        if self.index.contains_key(&lot.id) {
+            return Err(Error::ConflictException);
+        }
+        log::debug!("{:?} adding auction lot {:?}", lot.id, lot);
+        self.index.insert(lot.id, lot);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, id: ObjId) -> Option<AuctionLot> {
+        self.index.remove(&id)
+    }
+
+    pub fn get(&self, id: ObjId) -> Option<&AuctionLot> {
+        self.index.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: ObjId) -> Option<&mut AuctionLot> {
+        self.index.get_mut(&id)
+    }
+
+    pub fn get_listing(&self, id: ObjId) -> Option<&AuctionListing> {
+        self.index.get(&id).and_then(|lot| lot.listing.as_ref())
+    }
+
+    /// listed lots sorted by id
+    pub fn list_listed(&self) -> Vec<&AuctionLot> {
+        let mut list: Vec<_> = self.index.values().filter(|lot| lot.is_listed()).collect();
+        list.sort_by_key(|lot| lot.id);
+        list
+    }
+
+    pub fn list_expired(&self, total_time: TotalTime) -> Vec<ObjId> {
+        self.index
+            .values()
+            .filter(|lot| {
+                lot.listing
+                    .as_ref()
+                    .map(|listing| total_time.is_after(listing.expire))
+                    .unwrap_or(false)
+            })
+            .map(|lot| lot.id)
+            .collect()
+    }
+
+    /// lots waiting to be collected by the owner
+    pub fn list_to_collect(&self, owner_id: PlayerId) -> Vec<ObjId> {
+        let mut list: Vec<_> = self
+            .index
+            .values()
+            .filter(|lot| lot.owner_id == owner_id && !lot.is_listed())
+            .map(|lot| lot.id)
+            .collect();
+        list.sort();
+        list
+    }
+}
+
+impl CanLoad for Auctions {
+    fn load(&mut self, references: &LoadingCtx, obj_id: ObjId, data: &ObjData) -> Result<()> {
+        let auction_data = match &data.auction {
+            Some(auction_data) => auction_data,
+            None => return Ok(()),
+        };
+
+        let resolve = |static_id: StaticId| {
+            references
+                .id_map
+                .get(&static_id)
+                .cloned()
+                .unwrap_or(ObjId(static_id.as_u32()))
+        };
+
+        let lot = AuctionLot {
+            id: obj_id,
+            owner_id: resolve(auction_data.owner_id),
+            listing: auction_data.listing.as_ref().map(|data| AuctionListing {
+                buyout: Money(data.buyout),
+                bid: data.bid.as_ref().map(|bid| AuctionBid {
+                    bidder_id: resolve(bid.bidder_id),
+                    amount: Money(bid.amount),
+                }),
+                expire: TotalTime(data.expire),
+                sale_fee: data.sale_fee,
+            }),
+        };
+
+        self.index.insert(obj_id, lot);
+        Ok(())
+    }
+}
+
+impl CanSnapshot for Auctions {
+    fn snapshot(&self, obj_id: ObjId, data: &mut ObjData) -> Result<()> {
+        if let Some(lot) = self.index.get(&obj_id) {
+            data.auction = Some(AuctionData {
+                owner_id: lot.owner_id.into(),
+                listing: lot.listing.as_ref().map(|listing| AuctionListingData {
+                    buyout: listing.buyout.as_u32(),
+                    bid: listing.bid.as_ref().map(|bid| AuctionBidData {
+                        bidder_id: bid.bidder_id.into(),
+                        amount: bid.amount.as_u32(),
+                    }),
+                    expire: listing.expire.as_seconds_f64(),
+                    sale_fee: listing.sale_fee,
+                }),
+            });
+        }
+
+        Ok(())
+    }
+}
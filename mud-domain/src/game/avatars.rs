@@ -1,16 +1,27 @@
-use crate::errors::{Error, Result};
+use crate::errors::{AsResult, Error, Result};
+use crate::game::actions_trade;
 use crate::game::container::Container;
+use crate::game::corpse::create_corpse;
+use crate::game::inventory_service;
 use crate::game::labels::Label;
 use crate::game::loader::Loader;
 use crate::game::location::LocationId;
 use crate::game::mob::{Attributes, Damage, Mob, MobId, Pv};
 use crate::game::player::Player;
+use crate::game::pvp;
+use crate::game::room::RoomId;
 use crate::game::{comm, outputs::Outputs};
 use commons::{DeltaTime, PlayerId};
 
-pub fn on_player_disconnect(_container: &mut Container, _player_id: PlayerId) {
-    // let player = container.players.get(player_id).as_result()?;
-    // let _mob_id = player.mob_id;
+pub fn on_player_disconnect(container: &mut Container, player_id: PlayerId) {
+    let mob_id = match container.players.get_mob(player_id) {
+        Some(mob_id) => mob_id,
+        None => return,
+    };
+
+    if container.trades.get(mob_id).is_some() {
+        let _ = actions_trade::cancel(container, mob_id);
+    }
 }
 
 pub fn on_player_login(container: &mut Container, login: &str) -> Result<PlayerId> {
@@ -20,15 +31,59 @@ pub fn on_player_login(container: &mut Container, login: &str) -> Result<PlayerI
     }
 }
 
+/// Apply location death rules for a player avatar that was killed, need to be called before the
+/// avatar get removed
+pub fn on_avatar_killed(
+    container: &mut Container,
+    player_id: PlayerId,
+    mob_id: MobId,
+) -> Result<()> {
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let rules = pvp::resolve_rules(container, room_id);
+
+    if rules.keep_items {
+        // player hold the items until the new avatar is created
+        inventory_service::move_all(&mut container.locations, mob_id, player_id);
+    } else {
+        create_corpse(container, mob_id);
+    }
+
+    log::info!(
+        "{:?} avatar {:?} killed at {:?}, rules {:?}",
+        player_id,
+        mob_id,
+        room_id,
+        rules
+    );
+
+    container.players.update(player_id, |player| {
+        player.respawn_room_id = rules.respawn_room_id;
+    })
+}
+
 pub fn respawn_avatar(container: &mut Container, player_id: PlayerId) -> Result<()> {
     let player_login = container.labels.get_label_f(player_id).to_string();
-    let mob_id = create_avatar(container, &player_login)?;
+    let respawn_room_id = container
+        .players
+        .get(player_id)
+        .as_result()?
+        .respawn_room_id
+        .filter(|room_id| container.rooms.exists(*room_id));
+
+    let room_id = match respawn_room_id {
+        Some(room_id) => room_id,
+        None => container.config.initial_room.as_result()?,
+    };
+
+    let mob_id = create_avatar(container, &player_login, room_id)?;
     container.players.set_mob(player_id, mob_id)?;
+    container
+        .players
+        .update(player_id, |player| player.respawn_room_id = None)?;
 
-    let room_id = container
-        .locations
-        .get(mob_id)
-        .ok_or_else(|| Error::Exception("new avatar has no location".to_string()))?;
+    // give back items hold during the death
+    inventory_service::move_all(&mut container.locations, player_id, mob_id);
+    inventory_service::update_inventory_weight(container, mob_id)?;
 
     container
         .outputs
@@ -42,7 +97,8 @@ pub fn respawn_avatar(container: &mut Container, player_id: PlayerId) -> Result<
 
 pub fn create_player(container: &mut Container, login: &str) -> Result<PlayerId> {
     // create avatar
-    let mob_id = create_avatar(container, login)?;
+    let room_id = container.config.initial_room.unwrap();
+    let mob_id = create_avatar(container, login, room_id)?;
 
     // add player to game
     let player_id = container.objects.create();
@@ -56,9 +112,8 @@ pub fn create_player(container: &mut Container, login: &str) -> Result<PlayerId>
     Ok(player.id)
 }
 
-fn create_avatar(container: &mut Container, login: &str) -> Result<MobId> {
+fn create_avatar(container: &mut Container, login: &str, room_id: RoomId) -> Result<MobId> {
     let avatar_static_id = container.config.avatar_id.unwrap();
-    let room_id = container.config.initial_room.unwrap();
 
     let mob_id = Loader::spawn_at(container, avatar_static_id, room_id)?;
 
@@ -0,0 +1,295 @@
+use crate::errors::{Error, Result};
+use crate::game::container::Container;
+use crate::game::loader::dto::CalendarData;
+use crate::game::location::LocationId;
+use commons::TotalTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DayPeriod {
+    Day,
+    Night,
+}
+
+impl DayPeriod {
+    pub fn parse(value: &str) -> Result<DayPeriod> {
+        match value {
+            "day" => Ok(DayPeriod::Day),
+            "night" => Ok(DayPeriod::Night),
+            other => Err(Error::InvalidArgumentFailureStr(format!(
+                "invalid day period {:?}",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DayPeriod::Day => "day",
+            DayPeriod::Night => "night",
+        }
+    }
+}
+
+/// In-game date, all fields are 0 based
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameDate {
+    pub year: u32,
+    pub season: usize,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+}
+
+/// Convert the game total time into a in-game date
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Calendar {
+    /// real seconds for each in-game hour
+    pub seconds_per_hour: f32,
+    pub hours_per_day: u32,
+    pub days_per_season: u32,
+    /// season names, a year has all seasons
+    pub seasons: Vec<String>,
+    /// hour when day begins
+    pub sunrise: u32,
+    /// hour when night begins
+    pub sunset: u32,
+}
+
+impl Calendar {
+    pub fn new() -> Self {
+        Calendar {
+            seconds_per_hour: 60.0,
+            hours_per_day: 24,
+            days_per_season: 30,
+            seasons: vec![
+                "spring".to_string(),
+                "summer".to_string(),
+                "autumn".to_string(),
+                "winter".to_string(),
+            ],
+            sunrise: 6,
+            sunset: 18,
+        }
+    }
+
+    /// Missing fields use the default calendar values
+    pub fn parse(data: &CalendarData) -> Result<Self> {
+        let default = Calendar::new();
+
+        let calendar = Calendar {
+            seconds_per_hour: data.seconds_per_hour.unwrap_or(default.seconds_per_hour),
+            hours_per_day: data.hours_per_day.unwrap_or(default.hours_per_day),
+            days_per_season: data.days_per_season.unwrap_or(default.days_per_season),
+            seasons: data.seasons.clone().unwrap_or(default.seasons),
+            sunrise: data.sunrise.unwrap_or(default.sunrise),
+            sunset: data.sunset.unwrap_or(default.sunset),
+        };
+
+        if calendar.seconds_per_hour <= 0.0
+            || calendar.hours_per_day == 0
+            || calendar.days_per_season == 0
+            || calendar.seasons.is_empty()
+        {
+            return Err(Error::InvalidArgumentFailureStr(format!(
+                "invalid calendar {:?}",
+                data
+            )));
+        }
+
+        if calendar.sunrise >= calendar.sunset || calendar.sunset >= calendar.hours_per_day {
+            return Err(Error::InvalidArgumentFailureStr(format!(
+                "invalid calendar sunrise {} and sunset {} for a day of {} hours",
+                calendar.sunrise, calendar.sunset, calendar.hours_per_day
+            )));
+        }
+
+        Ok(calendar)
+    }
+
+    pub fn to_data(&self) -> CalendarData {
+        CalendarData {
+            seconds_per_hour: Some(self.seconds_per_hour),
+            hours_per_day: Some(self.hours_per_day),
+            days_per_season: Some(self.days_per_season),
+            seasons: Some(self.seasons.clone()),
+            sunrise: Some(self.sunrise),
+            sunset: Some(self.sunset),
+        }
+    }
+
+    pub fn date(&self, total: TotalTime) -> GameDate {
+        let total_minutes =
+            (total.as_seconds_f64() * 60.0 / self.seconds_per_hour as f64).max(0.0) as u64;
+        let total_hours = total_minutes / 60;
+        let total_days = total_hours / self.hours_per_day as u64;
+        let total_seasons = total_days / self.days_per_season as u64;
+
+        GameDate {
+            year: (total_seasons / self.seasons.len() as u64) as u32,
+            season: (total_seasons % self.seasons.len() as u64) as usize,
+            day: (total_days % self.days_per_season as u64) as u32,
+            hour: (total_hours % self.hours_per_day as u64) as u32,
+            minute: (total_minutes % 60) as u32,
+        }
+    }
+
+    pub fn period(&self, total: TotalTime) -> DayPeriod {
+        let hour = self.date(total).hour;
+        if hour >= self.sunrise && hour < self.sunset {
+            DayPeriod::Day
+        } else {
+            DayPeriod::Night
+        }
+    }
+
+    pub fn season_name(&self, date: &GameDate) -> &str {
+        self.seasons[date.season].as_str()
+    }
+}
+
+/// Search if the location is outdoor, first the room and later all parents zones. Rooms inside
+/// other rooms, like a ship interior, are never outdoor.
+pub fn is_outdoor(container: &Container, location_id: LocationId) -> bool {
+    let mut found_room = false;
+
+    for id in container.locations.list_parents_inclusive(location_id) {
+        if let Some(room) = container.rooms.get(id) {
+            if found_room {
+                return false;
+            }
+
+            if let Some(outdoor) = room.outdoor {
+                return outdoor;
+            }
+
+            found_room = true;
+        }
+
+        if let Some(outdoor) = container.zones.get(id).and_then(|zone| zone.outdoor) {
+            return outdoor;
+        }
+    }
+
+    false
+}
+
+/// Current day period, None for locations that can not see the sky
+pub fn get_outdoor_period(container: &Container, location_id: LocationId) -> Option<DayPeriod> {
+    if is_outdoor(container, location_id) {
+        Some(container.config.calendar.period(container.time.total))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::loader::Loader;
+
+    fn hours(calendar: &Calendar, value: f64) -> TotalTime {
+        TotalTime(value * calendar.seconds_per_hour as f64)
+    }
+
+    #[test]
+    fn test_date() {
+        let calendar = Calendar::new();
+
+        assert_eq!(
+            GameDate {
+                year: 0,
+                season: 0,
+                day: 0,
+                hour: 0,
+                minute: 0
+            },
+            calendar.date(TotalTime(0.0))
+        );
+
+        assert_eq!(
+            GameDate {
+                year: 0,
+                season: 0,
+                day: 0,
+                hour: 13,
+                minute: 30
+            },
+            calendar.date(hours(&calendar, 13.5))
+        );
+
+        assert_eq!(
+            GameDate {
+                year: 1,
+                season: 1,
+                day: 2,
+                hour: 3,
+                minute: 0
+            },
+            calendar.date(hours(&calendar, ((120 + 30 + 2) * 24 + 3) as f64))
+        );
+    }
+
+    #[test]
+    fn test_period() {
+        let calendar = Calendar::new();
+
+        assert_eq!(DayPeriod::Night, calendar.period(hours(&calendar, 5.9)));
+        assert_eq!(DayPeriod::Day, calendar.period(hours(&calendar, 6.0)));
+        assert_eq!(DayPeriod::Day, calendar.period(hours(&calendar, 17.9)));
+        assert_eq!(DayPeriod::Night, calendar.period(hours(&calendar, 18.0)));
+        assert_eq!(DayPeriod::Night, calendar.period(hours(&calendar, 24.0)));
+    }
+
+    #[test]
+    fn test_parse_should_fail_for_invalid_sunrise() {
+        let mut data = Calendar::new().to_data();
+        data.sunrise = Some(20);
+        assert!(Calendar::parse(&data).is_err());
+
+        data.sunrise = Some(0);
+        data.sunset = Some(24);
+        assert!(Calendar::parse(&data).is_err());
+
+        data.sunset = Some(23);
+        assert!(Calendar::parse(&data).is_ok());
+    }
+
+    #[test]
+    fn test_is_outdoor_should_ignore_zone_for_rooms_inside_rooms() {
+        let mut container = Container::new();
+        let lc = Loader::load_hocon(
+            &mut container,
+            r##"
+objects.0 {
+  id: 0
+  label: "valley"
+  zone { outdoor: true }
+}
+objects.1 {
+  id: 1
+  label: "field"
+  parent: 0
+  room {}
+}
+objects.2 {
+  id: 2
+  label: "barn"
+  parent: 0
+  room { outdoor: false }
+}
+objects.3 {
+  id: 3
+  label: "cabin"
+  parent: 1
+  room {}
+}
+"##,
+        )
+        .unwrap();
+
+        assert!(is_outdoor(&container, lc.get(1)));
+        assert!(!is_outdoor(&container, lc.get(2)));
+        assert!(!is_outdoor(&container, lc.get(3)));
+    }
+}
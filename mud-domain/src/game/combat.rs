@@ -1,3 +1,4 @@
+use rand::seq::SliceRandom;
 use rand::Rng;
 
 use super::comm;
@@ -6,48 +7,94 @@ use super::mob::*;
 use crate::errors::Error::InvalidStateFailure;
 use crate::errors::{AsResult, Error, Result};
 use crate::game::corpse::create_corpse;
-use crate::game::mob;
+use crate::game::domain::Dir;
+use crate::game::item::{Item, ItemId, DURABILITY_LOSS_PER_USE};
 use crate::game::outputs::Outputs;
 use crate::game::ownership::Ownerships;
+use crate::game::pvp;
+use crate::game::random_items;
+use crate::game::room::RoomId;
+use crate::game::system::spawn_system;
 use crate::game::triggers::{Event, EventKind};
+use crate::game::{actions, avatars, inventory_service, mob, scripts_service, weather};
 use commons::ObjId;
 
-pub fn is_valid_attack_target(
-    mobs: &MobRepository,
-    owners: &Ownerships,
-    mob_id: MobId,
-    target_id: ObjId,
-) -> bool {
+pub fn is_valid_attack_target(container: &Container, mob_id: MobId, target_id: ObjId) -> bool {
     if target_id == mob_id {
         return false;
     }
 
-    if owners.same_owner(mob_id, target_id) {
+    if container.ownership.same_owner(mob_id, target_id) {
         return false;
     }
 
-    if !mobs.exists(target_id) {
+    if !container.mobs.exists(target_id) {
         return false;
     }
 
-    return true;
+    pvp::check_attack(container, mob_id, target_id).is_ok()
+}
+
+/// Attack penalty when attacking a target in a adjacent room
+pub const RANGED_ATTACK_PENALTY: u32 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AttackReach {
+    /// target is in the same room
+    Melee,
+    /// target is in a adjacent room, reachable only by ranged weapons
+    Ranged { dir: Dir, target_room_id: RoomId },
+}
+
+/// Check if the mob can reach the target from its current location
+pub fn get_attack_reach(
+    container: &Container,
+    mob_id: MobId,
+    target_id: MobId,
+) -> Option<AttackReach> {
+    let room_id = container.locations.get(mob_id)?;
+    let target_room_id = container.locations.get(target_id)?;
+
+    if room_id == target_room_id {
+        return Some(AttackReach::Melee);
+    }
+
+    let range = mob::get_weapon(container, mob_id)
+        .map(|weapon| weapon.range)
+        .unwrap_or(0);
+
+    if range == 0 {
+        return None;
+    }
+
+    container
+        .rooms
+        .exists_exits(room_id, target_room_id)
+        .map(|dir| AttackReach::Ranged {
+            dir,
+            target_room_id,
+        })
 }
 
 pub fn tick_attack(container: &mut Container, mob_id: MobId, target_id: MobId) -> Result<()> {
     let attacker = container.mobs.get(mob_id).ok_or(Error::NotFoundFailure)?;
-    let defender = container.mobs.get(target_id);
 
-    if let Some(defender) = defender {
-        let attacker_room_id = container.locations.get(attacker.id).as_result()?;
-        let defender_room_id = container.locations.get(defender.id).as_result()?;
-
-        if attacker_room_id != defender_room_id {
-            cancel_attack(container, mob_id, Some(&target_id));
-            return Err(Error::InvalidArgumentFailure);
-        }
+    if container.mobs.exists(target_id) {
+        let reach = match get_attack_reach(container, mob_id, target_id) {
+            Some(reach) => reach,
+            None => {
+                cancel_attack(container, mob_id, None);
+                return Err(Error::InvalidArgumentFailure);
+            }
+        };
 
         if attacker.is_read_to_attack(container.time.total) {
-            execute_attack(container, mob_id, target_id)?;
+            if attacker.is_wimpy() && has_exits(container, mob_id) {
+                log::info!("{:?} is wimpy, trying to flee", mob_id);
+                return flee(container, mob_id, None);
+            }
+
+            execute_attack(container, mob_id, target_id, reach)?;
         }
 
         match return_attack(container, target_id, mob_id) {
@@ -66,49 +113,252 @@ pub fn tick_attack(container: &mut Container, mob_id: MobId, target_id: MobId) -
     }
 }
 
-pub fn kill_mob(container: &mut Container, mob_id: MobId) -> Result<()> {
-    log::info!("{:?} was killed", mob_id);
+/// Wimpy mobs cornered in a room without exits keep fighting
+fn has_exits(container: &Container, mob_id: MobId) -> bool {
+    container
+        .locations
+        .get(mob_id)
+        .and_then(|room_id| container.rooms.get(room_id))
+        .map(|room| !room.exits.is_empty())
+        .unwrap_or(false)
+}
+
+pub fn kill_mob(container: &mut Container, mob_id: MobId, killer_id: Option<MobId>) -> Result<()> {
+    log::info!("{:?} was killed by {:?}", mob_id, killer_id);
 
-    create_corpse(container, mob_id);
+    let prefab_id = container.objects.get_prefab_id(mob_id);
+    scripts_service::on_death(container, mob_id, killer_id);
+
+    match container.players.find_from_mob(mob_id) {
+        Some(player_id) => avatars::on_avatar_killed(container, player_id, mob_id)?,
+        None => {
+            if let Err(err) = random_items::roll_loot(container, mob_id) {
+                log::warn!("{:?} fail to generate random loot: {:?}", mob_id, err);
+            }
+            create_corpse(container, mob_id)
+        }
+    }
+
+    spawn_system::on_mob_killed(container, mob_id);
     container.remove(mob_id);
 
-    container.triggers.push(Event::Obj {
-        kind: EventKind::Killed,
+    container.triggers.push(Event::Killed {
         obj_id: mob_id,
+        prefab_id,
+        killer_id,
     });
 
     Ok(())
 }
 
-fn cancel_attack(container: &mut Container, mob_id: MobId, _target: Option<&MobId>) {
+/// Cancel mob attack, if the target is attacking the mob back, it is cancelled too
+fn cancel_attack(container: &mut Container, mob_id: MobId, target: Option<&MobId>) {
     container.mobs.cancel_command(mob_id).unwrap();
+
+    if let Some(&target_id) = target {
+        let target_is_attacking = container
+            .mobs
+            .get(target_id)
+            .map(|target| match target.command {
+                MobCommand::Kill { target_id } => target_id == mob_id,
+                _ => false,
+            })
+            .unwrap_or(false);
+
+        if target_is_attacking {
+            container.mobs.cancel_command(target_id).unwrap();
+        }
+    }
+}
+
+/// List all mobs in the same room that are attacking the mob
+pub fn list_attackers(container: &Container, mob_id: MobId) -> Vec<MobId> {
+    let room_id = match container.locations.get(mob_id) {
+        Some(room_id) => room_id,
+        None => return vec![],
+    };
+
+    container
+        .locations
+        .list_at(room_id)
+        .filter_map(|id| container.mobs.get(id))
+        .filter(|mob| match mob.command {
+            MobCommand::Kill { target_id } => target_id == mob_id,
+            _ => false,
+        })
+        .map(|mob| mob.id)
+        .collect()
+}
+
+/// Try to escape from combat through the given exit or a random one. The chance to succeed
+/// is a roll of mob defense against the best attack of its attackers.
+pub fn flee(container: &mut Container, mob_id: MobId, dir: Option<Dir>) -> Result<()> {
+    let mob = container.mobs.get(mob_id).as_result()?;
+    let mob_target_id = match mob.command {
+        MobCommand::Kill { target_id } => Some(target_id),
+        _ => None,
+    };
+
+    let attackers = list_attackers(container, mob_id);
+    if mob_target_id.is_none() && attackers.is_empty() {
+        container
+            .outputs
+            .private(mob_id, comm::flee_fail_not_in_combat());
+        return Err(InvalidStateFailure);
+    }
+
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let exits = &container.rooms.get(room_id).as_result()?.exits;
+    let exit_dir = match dir {
+        Some(dir) => exits.iter().find(|(i, _)| *i == dir).map(|(i, _)| *i),
+        None => exits.choose(&mut rand::thread_rng()).map(|(i, _)| *i),
+    };
+
+    let exit_dir = match exit_dir {
+        Some(exit_dir) => exit_dir,
+        None => {
+            container.outputs.private(mob_id, comm::flee_fail_no_exit());
+            return Err(Error::InvalidArgumentFailure);
+        }
+    };
+
+    let defense = mob::get_attributes_with_bonus(container, mob_id)?.defense;
+    let attack = attackers
+        .iter()
+        .chain(mob_target_id.iter())
+        .filter_map(|id| mob::get_attributes_with_bonus(container, *id).ok())
+        .map(|attributes| attributes.attack)
+        .max()
+        .unwrap_or(0);
+
+    let mob_label = container.labels.get_label_f(mob_id);
+
+    if !roll_flee(defense, attack) {
+        container.outputs.private(mob_id, comm::flee_fail());
+        container
+            .outputs
+            .broadcast(Some(mob_id), room_id, comm::flee_fail_others(mob_label));
+
+        // a failed attempt consume the mob turn
+        let total_time = container.time.total;
+        container.mobs.update(mob_id, |mob| {
+            mob.add_attack_calm_time(total_time);
+        })?;
+
+        return Ok(());
+    }
+
+    log::info!("{:?} flee to {:?}", mob_id, exit_dir);
+
+    container.outputs.private(mob_id, comm::flee_success());
+    container
+        .outputs
+        .broadcast(Some(mob_id), room_id, comm::flee_others(mob_label));
+
+    cancel_attack(container, mob_id, mob_target_id.as_ref());
+    for attacker_id in attackers {
+        cancel_attack(container, attacker_id, Some(&mob_id));
+    }
+
+    actions::move_dir(container, mob_id, exit_dir)
+}
+
+fn roll_flee(defense: u32, attack: u32) -> bool {
+    roll_dice() + defense >= roll_dice() + attack
 }
 
 // TODO: fix multiples get from get two mutable
 // TODO: log errors, like can not get attribute?
-fn execute_attack(container: &mut Container, mob_id: MobId, target_id: MobId) -> Result<()> {
+fn execute_attack(
+    container: &mut Container,
+    mob_id: MobId,
+    target_id: MobId,
+    reach: AttackReach,
+) -> Result<()> {
+    if !consume_ammo(container, mob_id)? {
+        container.outputs.private(mob_id, comm::kill_out_of_ammo());
+        cancel_attack(container, mob_id, None);
+        return Ok(());
+    }
+
     let attacker_attributes = mob::get_attributes_with_bonus(container, mob_id)?;
     let attacker_room_id = container.locations.get(mob_id).as_result()?;
     let attacker_label = container.labels.get_label_f(mob_id);
 
-    let defender_attributes = mob::get_attributes_with_bonus(container, mob_id)?;
+    let defender_attributes = mob::get_attributes_with_bonus(container, target_id)?;
     let defender_label = container.labels.get_label_f(target_id);
 
+    let attack = match reach {
+        AttackReach::Melee => attacker_attributes.attack,
+        AttackReach::Ranged { .. } => attacker_attributes
+            .attack
+            .saturating_sub(RANGED_ATTACK_PENALTY),
+    };
+
+    // bad weather around the attacker makes harder to hit
+    let weather_penalty = weather::get_outdoor_weather(container, attacker_room_id)
+        .map(|weather| weather.attack_penalty(reach != AttackReach::Melee))
+        .unwrap_or(0);
+    let attack = attack.saturating_sub(weather_penalty);
+
+    let damages = std::iter::once(&attacker_attributes.damage)
+        .chain(attacker_attributes.extra_damages.iter())
+        .collect();
+
     let attack_result = roll_attack(
-        attacker_attributes.attack,
-        &attacker_attributes.damage,
+        attack,
+        damages,
         defender_attributes.defense,
         defender_attributes.rd,
+        &defender_attributes.resistances,
     );
 
-    let room_attack_msg =
-        comm::kill_mob_execute_attack(attacker_label, defender_label, &attack_result);
+    match reach {
+        AttackReach::Melee => {
+            let room_attack_msg =
+                comm::kill_mob_execute_attack(attacker_label, defender_label, &attack_result);
+
+            let player_attack_msg =
+                comm::kill_player_execute_attack(defender_label, &attack_result);
+            container.outputs.private(mob_id, player_attack_msg);
+            container
+                .outputs
+                .broadcast(Some(mob_id), attacker_room_id, room_attack_msg);
+        }
+        AttackReach::Ranged {
+            dir,
+            target_room_id,
+        } => {
+            let player_attack_msg =
+                comm::kill_player_execute_ranged_attack(defender_label, &dir, &attack_result);
+            let room_attack_msg = comm::kill_mob_execute_ranged_attack(
+                attacker_label,
+                defender_label,
+                &dir,
+                &attack_result,
+            );
+            let target_room_attack_msg = comm::kill_mob_execute_ranged_attack_from(
+                attacker_label,
+                defender_label,
+                &dir.inv(),
+                &attack_result,
+            );
+
+            container.outputs.private(mob_id, player_attack_msg);
+            container
+                .outputs
+                .broadcast(Some(mob_id), attacker_room_id, room_attack_msg);
+            container
+                .outputs
+                .broadcast(Some(mob_id), target_room_id, target_room_attack_msg);
+        }
+    }
 
-    let player_attack_msg = comm::kill_player_execute_attack(defender_label, &attack_result);
-    container.outputs.private(mob_id, player_attack_msg);
-    container
-        .outputs
-        .broadcast(Some(mob_id), attacker_room_id, room_attack_msg);
+    // weapons wear on every attack, armors only when hit
+    wear_equipment(container, mob_id, |item| item.weapon.is_some())?;
+    if attack_result.success {
+        wear_equipment(container, target_id, |item| item.armor.is_some())?;
+    }
 
     if attack_result.success {
         // deduct pv
@@ -148,6 +398,7 @@ fn execute_attack_killed(
     xp: Xp,
 ) -> Result<()> {
     let room_id = container.locations.get(mob_id).as_result()?;
+    let target_room_id = container.locations.get(target_id).as_result()?;
     let defender_label = container.labels.get_label_f(target_id);
 
     container
@@ -157,23 +408,123 @@ fn execute_attack_killed(
         .outputs
         .broadcast(Some(mob_id), room_id, comm::killed(defender_label));
 
-    kill_mob(container, target_id)
+    if target_room_id != room_id {
+        container
+            .outputs
+            .broadcast(Some(mob_id), target_room_id, comm::killed(defender_label));
+    }
+
+    kill_mob(container, target_id, Some(mob_id))
+}
+
+/// Reduce durability of mob equipped items that match the filter, notifying when it breaks
+fn wear_equipment<F>(container: &mut Container, mob_id: MobId, filter: F) -> Result<()>
+where
+    F: Fn(&Item) -> bool,
+{
+    let mut items_id: Vec<ItemId> = container
+        .equips
+        .get(mob_id)
+        .into_iter()
+        .filter(|item_id| container.items.get(*item_id).map(&filter).unwrap_or(false))
+        .collect();
+    items_id.sort();
+
+    for item_id in items_id {
+        let broke = container
+            .items
+            .get_mut(item_id)
+            .and_then(|item| item.durability.as_mut())
+            .map(|durability| durability.damage(DURABILITY_LOSS_PER_USE))
+            .unwrap_or(false);
+
+        if !broke {
+            continue;
+        }
+
+        log::info!("{:?} item {:?} broke", mob_id, item_id);
+
+        let room_id = container.locations.get(mob_id).as_result()?;
+        let mob_label = container.labels.get_label_f(mob_id);
+        let item_label = container.labels.get_label_f(item_id);
+        let msg = comm::item_broken(mob_label, item_label);
+        container.outputs.message(mob_id, room_id, msg);
+    }
+
+    Ok(())
+}
+
+/// Consume one ammunition when the equipped weapon requires it, return false if there is no
+/// ammunition available
+fn consume_ammo(container: &mut Container, mob_id: MobId) -> Result<bool> {
+    let ammo_id = match mob::get_weapon(container, mob_id).and_then(|weapon| weapon.ammo) {
+        Some(ammo_id) => ammo_id,
+        None => return Ok(true),
+    };
+
+    match inventory_service::find_by_prefab(container, mob_id, ammo_id) {
+        Some(item_id) => {
+            inventory_service::consume_one(container, item_id)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
 }
 
-fn roll_attack(attack: u32, damage: &Damage, defense: u32, rd: u32) -> AttackResult {
+fn roll_attack(
+    attack: u32,
+    damages: Vec<&Damage>,
+    defense: u32,
+    rd: u32,
+    resistances: &Vec<Resistance>,
+) -> AttackResult {
     let mut result = AttackResult::new(attack, defense, rd);
 
     result.attack_dice = roll_dice() + attack;
     result.defense_dice = roll_dice() + defense;
     result.success = result.attack_dice >= result.defense_dice;
     if result.success {
-        result.damage_total = roll_damage(damage);
-        result.damage_deliver = result.damage_total - rd;
+        let totals = damages
+            .into_iter()
+            .map(|damage| (damage.kind, roll_damage(damage)))
+            .collect();
+
+        apply_damages(&mut result, totals, rd, resistances);
     }
 
     result
 }
 
+/// Apply the type resistances on each damage, the generic rd is consumed by each damage
+/// in order until is exhausted
+fn apply_damages(
+    result: &mut AttackResult,
+    totals: Vec<(DamageType, u32)>,
+    rd: u32,
+    resistances: &Vec<Resistance>,
+) {
+    let mut remaining_rd = rd;
+
+    for (kind, total) in totals {
+        let resisted = resistances
+            .iter()
+            .find(|resistance| resistance.kind == kind)
+            .map(|resistance| resistance.apply(total))
+            .unwrap_or(total);
+
+        let deliver = resisted.saturating_sub(remaining_rd);
+        remaining_rd -= resisted.min(remaining_rd);
+
+        result.damage_total += total;
+        result.damage_deliver += deliver;
+        result.damages.push(DamageResult {
+            kind,
+            total,
+            deliver,
+        });
+    }
+}
+
 fn roll_dice() -> u32 {
     let mut rng = rand::thread_rng();
 
@@ -188,9 +539,10 @@ fn roll_damage(damage: &Damage) -> u32 {
 /// mob_id is the mob that receive the attack
 /// target_id is the mob that executed the attack
 fn return_attack(container: &mut Container, mob_id: MobId, target_id: MobId) -> Result<()> {
+    let can_reach = get_attack_reach(container, mob_id, target_id).is_some();
     let mob = container.mobs.get_mut(mob_id).as_result()?;
 
-    if !mob.is_combat() {
+    if !mob.is_combat() && can_reach {
         let location_id = container.locations.get(target_id).as_result()?;
         let mob_label = container.labels.get_label_f(mob_id);
         let aggressor_mob_label = container.labels.get_label_f(target_id);
@@ -228,3 +580,304 @@ fn return_attack(container: &mut Container, mob_id: MobId, target_id: MobId) ->
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::builder;
+    use crate::game::item::{Durability, ItemId, Weapon};
+    use crate::game::loader::dto::StaticId;
+    use crate::game::outputs::Output;
+    use commons::TotalTime;
+
+    struct Scenery {
+        container: Container,
+        room1_id: RoomId,
+        room2_id: RoomId,
+        mob_id: MobId,
+        wolf_id: MobId,
+    }
+
+    fn setup() -> Scenery {
+        let mut container = Container::new();
+        let room1_id = builder::add_room(&mut container, "room1");
+        let room2_id = builder::add_room(&mut container, "room2");
+        builder::add_portal(&mut container, room1_id, room2_id, Dir::N);
+
+        let mob_id = builder::add_mob(&mut container, "mob", room1_id);
+        let wolf_id = builder::add_mob(&mut container, "wolf", room1_id);
+
+        // make flee always succeed
+        container
+            .mobs
+            .update(mob_id, |mob| mob.attributes.defense = 100)
+            .unwrap();
+        container
+            .mobs
+            .update(wolf_id, |mob| mob.attributes.attack = 0)
+            .unwrap();
+
+        container
+            .mobs
+            .set_mob_attack_target(mob_id, wolf_id)
+            .unwrap();
+        container
+            .mobs
+            .set_mob_attack_target(wolf_id, mob_id)
+            .unwrap();
+
+        Scenery {
+            container,
+            room1_id,
+            room2_id,
+            mob_id,
+            wolf_id,
+        }
+    }
+
+    #[test]
+    fn test_flee_should_move_and_cancel_combat_on_both_sides() {
+        let mut s = setup();
+        flee(&mut s.container, s.mob_id, None).unwrap();
+
+        assert_eq!(Some(s.room2_id), s.container.locations.get(s.mob_id));
+        assert_eq!(Some(s.room1_id), s.container.locations.get(s.wolf_id));
+        assert!(s.container.mobs.get(s.mob_id).unwrap().is_idle());
+        assert!(s.container.mobs.get(s.wolf_id).unwrap().is_idle());
+    }
+
+    #[test]
+    fn test_flee_should_fail_when_not_in_combat() {
+        let mut s = setup();
+        s.container.mobs.cancel_command(s.mob_id).unwrap();
+        s.container.mobs.cancel_command(s.wolf_id).unwrap();
+
+        assert!(flee(&mut s.container, s.mob_id, None).is_err());
+        assert_eq!(Some(s.room1_id), s.container.locations.get(s.mob_id));
+    }
+
+    #[test]
+    fn test_flee_should_fail_for_invalid_exit() {
+        let mut s = setup();
+        assert!(flee(&mut s.container, s.mob_id, Some(Dir::S)).is_err());
+        assert!(s.container.mobs.get(s.mob_id).unwrap().is_combat());
+    }
+
+    #[test]
+    fn test_wimpy_mob_should_flee_instead_of_attack() {
+        let mut s = setup();
+        s.container
+            .mobs
+            .update(s.mob_id, |mob| {
+                mob.wimpy = Some(5);
+                mob.attributes.pv.current = 3;
+            })
+            .unwrap();
+
+        tick_attack(&mut s.container, s.mob_id, s.wolf_id).unwrap();
+
+        assert_eq!(Some(s.room2_id), s.container.locations.get(s.mob_id));
+        assert!(s.container.mobs.get(s.wolf_id).unwrap().is_idle());
+    }
+
+    #[test]
+    fn test_wimpy_mob_without_exits_should_attack() {
+        let mut s = setup();
+        let room3_id = builder::add_room(&mut s.container, "room3");
+        s.container.locations.set(s.mob_id, room3_id);
+        s.container.locations.set(s.wolf_id, room3_id);
+        s.container
+            .mobs
+            .update(s.mob_id, |mob| {
+                mob.wimpy = Some(5);
+                mob.attributes.pv.current = 3;
+            })
+            .unwrap();
+
+        tick_attack(&mut s.container, s.mob_id, s.wolf_id).unwrap();
+
+        // the attack consume the turn
+        let total_time = s.container.time.total;
+        let mob = s.container.mobs.get(s.mob_id).unwrap();
+        assert_eq!(Some(room3_id), s.container.locations.get(s.mob_id));
+        assert!(mob.is_combat());
+        assert!(!mob.is_read_to_attack(total_time));
+    }
+
+    #[test]
+    fn test_attack_should_wear_weapon_until_broken() {
+        let mut s = setup();
+        s.container.mobs.cancel_command(s.wolf_id).unwrap();
+
+        let sword_id = builder::add_item(&mut s.container, "sword", s.mob_id);
+        let item = s.container.items.get_mut(sword_id).unwrap();
+        item.weapon = Some(Weapon::new());
+        item.durability = Some(Durability::new(1));
+        s.container.equips.add(s.mob_id, sword_id);
+
+        tick_attack(&mut s.container, s.mob_id, s.wolf_id).unwrap();
+
+        assert!(s.container.items.get(sword_id).unwrap().is_broken());
+        assert!(s
+            .container
+            .outputs
+            .take()
+            .iter()
+            .any(|output| match output {
+                Output::Private { msg, .. } => msg.contains("your sword broke!"),
+                _ => false,
+            }));
+    }
+
+    #[test]
+    fn test_attack_should_apply_defender_resistances() {
+        let mut s = setup();
+        s.container.mobs.cancel_command(s.wolf_id).unwrap();
+        s.container
+            .mobs
+            .update(s.mob_id, |mob| {
+                mob.attributes.attack = 100;
+                mob.attributes.damage.min = 10;
+                mob.attributes.damage.max = 10;
+            })
+            .unwrap();
+        s.container
+            .mobs
+            .update(s.wolf_id, |mob| {
+                mob.attributes.defense = 0;
+                mob.attributes.pv.current = 100;
+                mob.attributes.resistances = vec![Resistance {
+                    kind: DamageType::Blunt,
+                    percent: 50,
+                    rd: 0,
+                }];
+            })
+            .unwrap();
+
+        tick_attack(&mut s.container, s.mob_id, s.wolf_id).unwrap();
+
+        assert_eq!(
+            95,
+            s.container
+                .mobs
+                .get(s.wolf_id)
+                .unwrap()
+                .attributes
+                .pv
+                .current
+        );
+    }
+
+    fn add_bow(container: &mut Container, mob_id: MobId, ammo: Option<StaticId>) -> ItemId {
+        let bow_id = builder::add_item(container, "bow", mob_id);
+        let mut weapon = Weapon::new();
+        weapon.range = 1;
+        weapon.ammo = ammo;
+        container.items.get_mut(bow_id).unwrap().weapon = Some(weapon);
+        container.equips.add(mob_id, bow_id);
+        bow_id
+    }
+
+    #[test]
+    fn test_melee_attack_into_other_room_should_be_cancelled() {
+        let mut s = setup();
+        s.container.locations.set(s.wolf_id, s.room2_id);
+
+        assert!(tick_attack(&mut s.container, s.mob_id, s.wolf_id).is_err());
+        assert!(s.container.mobs.get(s.mob_id).unwrap().is_idle());
+    }
+
+    #[test]
+    fn test_ranged_attack_into_adjacent_room_should_consume_ammo() {
+        let mut s = setup();
+        s.container.locations.set(s.wolf_id, s.room2_id);
+        s.container.mobs.cancel_command(s.wolf_id).unwrap();
+
+        let ammo_prefab_id = StaticId(1);
+        add_bow(&mut s.container, s.mob_id, Some(ammo_prefab_id));
+        let arrow_id = builder::add_item(&mut s.container, "arrow", s.mob_id);
+        s.container.items.get_mut(arrow_id).unwrap().amount = 2;
+        s.container
+            .objects
+            .set_prefab_id(arrow_id, ammo_prefab_id)
+            .unwrap();
+
+        tick_attack(&mut s.container, s.mob_id, s.wolf_id).unwrap();
+        assert_eq!(1, s.container.items.get(arrow_id).unwrap().amount);
+        assert!(s.container.mobs.get(s.mob_id).unwrap().is_combat());
+
+        // wolf can not reach back
+        assert!(s.container.mobs.get(s.wolf_id).unwrap().is_idle());
+
+        // last arrow is removed
+        s.container
+            .mobs
+            .update(s.mob_id, |mob| mob.state.attack_calm_down = TotalTime(0.0))
+            .unwrap();
+        tick_attack(&mut s.container, s.mob_id, s.wolf_id).unwrap();
+        assert!(!s.container.items.exists(arrow_id));
+
+        // without ammo the attack is cancelled
+        s.container
+            .mobs
+            .update(s.mob_id, |mob| mob.state.attack_calm_down = TotalTime(0.0))
+            .unwrap();
+        tick_attack(&mut s.container, s.mob_id, s.wolf_id).unwrap();
+        assert!(s.container.mobs.get(s.mob_id).unwrap().is_idle());
+    }
+
+    #[test]
+    fn test_apply_damages_with_resistances() {
+        let resistances = vec![Resistance {
+            kind: DamageType::Fire,
+            percent: 50,
+            rd: 1,
+        }];
+
+        let mut result = AttackResult::new(0, 0, 2);
+        apply_damages(
+            &mut result,
+            vec![(DamageType::Slash, 5), (DamageType::Fire, 10)],
+            2,
+            &resistances,
+        );
+
+        assert_eq!(15, result.damage_total);
+        // slash 5 - rd 2 = 3, fire 10 * 50% - 1 = 4
+        assert_eq!(7, result.damage_deliver);
+        assert_eq!(2, result.damages.len());
+        assert_eq!(DamageType::Slash, result.damages[0].kind);
+        assert_eq!(3, result.damages[0].deliver);
+        assert_eq!(DamageType::Fire, result.damages[1].kind);
+        assert_eq!(4, result.damages[1].deliver);
+    }
+
+    #[test]
+    fn test_apply_damages_should_not_underflow() {
+        let mut result = AttackResult::new(0, 0, 10);
+        apply_damages(&mut result, vec![(DamageType::Blunt, 2)], 10, &vec![]);
+        assert_eq!(2, result.damage_total);
+        assert_eq!(0, result.damage_deliver);
+    }
+
+    #[test]
+    fn test_get_attack_reach() {
+        let mut s = setup();
+        assert_eq!(
+            Some(AttackReach::Melee),
+            get_attack_reach(&s.container, s.mob_id, s.wolf_id)
+        );
+
+        s.container.locations.set(s.wolf_id, s.room2_id);
+        assert_eq!(None, get_attack_reach(&s.container, s.mob_id, s.wolf_id));
+
+        add_bow(&mut s.container, s.mob_id, None);
+        assert_eq!(
+            Some(AttackReach::Ranged {
+                dir: Dir::N,
+                target_room_id: s.room2_id
+            }),
+            get_attack_reach(&s.container, s.mob_id, s.wolf_id)
+        );
+    }
+}
@@ -5,14 +5,19 @@ use super::mob::*;
 use crate::errors::{AsResult, Result};
 use crate::game::actions_command::RequestCommand;
 use crate::game::astro_bodies::{AstroBodyKind, DistanceMkm};
+use crate::game::calendar::{DayPeriod, GameDate};
+use crate::game::equip::EquipSlot;
 use crate::game::labels::Label;
 use crate::game::location::LocationId;
+use crate::game::needs::{EffectKind, NeedKind, NeedLevel, Needs, NEEDS_MAX};
 use crate::game::obj::Obj;
 use crate::game::outputs::OMarker;
 use crate::game::prices::Money;
+use crate::game::pvp::PvpPolicy;
 use crate::game::room::{Room, RoomId};
+use crate::game::weather::WeatherKind;
 use crate::utils::text::{plot_points, PlotCfg, PlotPoint};
-use commons::{ObjId, TotalTime, V2};
+use commons::{DeltaTime, ObjId, TotalTime, V2};
 
 use std::collections::{HashMap, HashSet};
 
@@ -25,6 +30,13 @@ pub struct InventoryDesc<'a> {
     pub max_weight: Option<Weight>,
     pub total_weight: Weight,
     pub items: Vec<InventoryItemDesc<'a>>,
+    /// equipped items sorted by slot
+    pub equipments: Vec<EquipDesc<'a>>,
+}
+
+pub struct EquipDesc<'a> {
+    pub slot: EquipSlot,
+    pub label: &'a str,
 }
 
 pub struct InventoryItemDesc<'a> {
@@ -60,28 +72,63 @@ pub fn help() -> String {
   examine <target>                      - examine target insides carefully
   n,s,e,w                               - move to different directions
   say <msg>                             - say something in the room
+  say <obj>|all follow me|wait|guard    - give orders to who is under your command
   uptime                                - server uptime
+  time                                  - current in-game hour
+  date                                  - current in-game day, season and year
   stats                                 - show your stats information and inventory
   rest                                  - rest to recovery from wounds, see stand
   stand                                 - sand up and stop to rest, see rest
+  eat|drink|quaff <item>                - consume food, drinks and potions
+  use <item>                            - use a item from your inventory or the room
   kill <target>                         - attack something and try to kill it
+  shoot <dir> <target>                  - shoot with a ranged weapon into a adjacent room
+  flee [dir]                            - try to escape from combat, random exit if no dir
+  wimpy [<pv>|off]                      - automatically flee when pv drops to value
+  pvp [on|off]                          - show or change if you can fight other players
   get <obj>                             - pick up a <obj> from floor
   get <obj> in <obj>                    - pick up a <obj> from <from>
   equip <item>                          - use a weapon or wear a armor
   remove <item>                         - strip an item you are using
-  drop <item>                           - drop a object
-  put <item> <obj>                      - put a object into other container
+  drop [<amount>] <item>                - drop a object or part of a stack
+  put [<amount>] <item> in <obj>        - put a object into other container
+  give [<amount>] <item> to <mob>       - give a object to someone
   enter <target>                        - enter in something
   out|exit                              - get out of something
   hire                                  - hire someone
+  talk <mob>                            - talk with someone, answer with the option number
+  quests                                - list your quests and its progress
+  quest abandon <quest>                 - give up of a quest
+  trade [<player>]                      - show current trade or start to trade with a player
+  trade offer|remove <item>             - add or remove a item from your trade offer
+  trade money <amount>                  - offer money in the trade
+  trade confirm|cancel                  - confirm or cancel the trade
+  auction [list]                        - list items in the auction house
+  auction sell <item> <buyout>          - list a item in the auction house
+  auction bid <id> <amount>             - bid for a item
+  auction buy <id>                      - buy a item for the buyout price
+  auction cancel <id>                   - cancel your auction without bids
+  auction collect                       - collect items and money from auctions
+  bank                                  - show your vault in a bank
+  bank deposit|withdraw <amount>        - deposit or withdraw money from your vault
+  bank store <item>                     - store a item in your vault
+  bank retrieve [<amount>] <item>       - retrieve a item from your vault
   map                                   - show map of current zone
+  path <room>                           - show the way into a known room
+  walk [to] <room>                      - walk step by step into a known room
   buy <item>                            - list objecst to buy or buy a item
   sell <item>                           - list objecst to sell or sell a item
+  repair <item>                         - pay a vendor to repair a item
+  repair <item> with <kit>              - repair a item using a repair kit
   extract <obj>                         - extract resources from a stuff that can be extracted
+  craft                                 - list known recipes
+  craft <recipe>                        - craft a recipe, it can require a workstation or tool
   command                               - list commandable units
   command <obj>: follow me              - command a obj to follow you
   command <obj>: extract                - command a obj to extract materials
   command <obj>: haul <from id> <to id> - command a obj to haul objects from id a to id b
+  command <obj>: guard                  - command a obj to guard your current room
+  command <obj>: wait                   - command a obj to stop and wait
   sm                                    - show starmap
   move <target>                         - travel ship to some object
   land <target>                         - land ship into a location
@@ -98,6 +145,8 @@ pub fn look_description(
     exits: Vec<Dir>,
     can_exit: bool,
     visible_objects: Vec<&str>,
+    period: Option<DayPeriod>,
+    weather: Option<WeatherKind>,
 ) -> Result<String> {
     let mut buffer = vec![];
 
@@ -122,6 +171,14 @@ pub fn look_description(
     ));
     buffer.push(OMarker::Desc.wrap(room_desc));
 
+    if let Some(period) = period {
+        buffer.push(look_sky(period).to_string());
+    }
+
+    if let Some(weather) = weather {
+        buffer.push(look_weather(weather).to_string());
+    }
+
     for label in visible_objects {
         buffer.push(format!("- {}", label));
     }
@@ -161,6 +218,34 @@ pub fn move_not_possible_resting() -> String {
     format!("you can not go there, you are to lazy to stand up")
 }
 
+pub fn path_show(room: &str, steps: Vec<String>) -> String {
+    format!("path to {}: {}", room, steps.join(", "))
+}
+
+pub fn path_not_found(room: &str) -> String {
+    format!("you do not know how to reach {}", room)
+}
+
+pub fn path_already_there(room: &str) -> String {
+    format!("you already are at {}", room)
+}
+
+pub fn path_room_not_found(room: &str) -> String {
+    format!("you do not know any place called [{}]", room)
+}
+
+pub fn walk_start(room: &str, steps: usize) -> String {
+    format!("you start to walk to {}, {} steps away", room, steps)
+}
+
+pub fn walk_arrived(room: &str) -> String {
+    format!("you arrive at {}", room)
+}
+
+pub fn walk_fail_busy() -> String {
+    format!("you can not walk now, you are busy")
+}
+
 pub fn spawn_mob(label: &str) -> String {
     format!("a {} appears here from no where", label)
 }
@@ -169,6 +254,66 @@ pub fn uptime(time: TotalTime) -> String {
     format!("now it is {}s after start", time.as_seconds_f64())
 }
 
+pub fn calendar_time(date: &GameDate, period: DayPeriod) -> String {
+    format!(
+        "it is {:02}:{:02}, {}",
+        date.hour,
+        date.minute,
+        period.as_str()
+    )
+}
+
+pub fn calendar_date(date: &GameDate, season: &str) -> String {
+    format!(
+        "it is day {} of {}, year {}",
+        date.day + 1,
+        season,
+        date.year + 1
+    )
+}
+
+pub fn calendar_sunrise() -> String {
+    "the sun rises in the horizon".to_string()
+}
+
+pub fn calendar_sunset() -> String {
+    "the sun sets and the night begins".to_string()
+}
+
+pub fn weather_label(weather: WeatherKind) -> &'static str {
+    match weather {
+        WeatherKind::Clear => "clear sky",
+        WeatherKind::Rain => "rain",
+        WeatherKind::Storm => "storm",
+        WeatherKind::DustStorm => "dust storm",
+    }
+}
+
+pub fn weather_changed(weather: WeatherKind) -> String {
+    match weather {
+        WeatherKind::Clear => "the clouds go away and the sky clears".to_string(),
+        WeatherKind::Rain => "it starts to rain".to_string(),
+        WeatherKind::Storm => "a storm begins, thunders roar in the sky".to_string(),
+        WeatherKind::DustStorm => "a dust storm comes, covering everything in dust".to_string(),
+    }
+}
+
+pub fn look_weather(weather: WeatherKind) -> &'static str {
+    match weather {
+        WeatherKind::Clear => "the weather is clear",
+        WeatherKind::Rain => "it is raining",
+        WeatherKind::Storm => "a storm rages around",
+        WeatherKind::DustStorm => "a dust storm blows, you can barely see",
+    }
+}
+
+pub fn look_sky(period: DayPeriod) -> &'static str {
+    match period {
+        DayPeriod::Day => "the sun shines in the sky",
+        DayPeriod::Night => "it is night, the sky is full of stars",
+    }
+}
+
 pub fn kill_target_not_found(target: &str) -> String {
     format!("target [{}] not found!", target)
 }
@@ -177,6 +322,82 @@ pub fn kill_can_not_kill_players(target: &str) -> String {
     format!("target [{}] is friendly, you can not kill him!", target)
 }
 
+pub fn kill_can_not_kill_safe_zone(target: &str) -> String {
+    format!(
+        "[{}] is in a safe place, no fights are allowed here!",
+        target
+    )
+}
+
+pub fn kill_can_not_kill_pvp_not_flagged(target: &str) -> String {
+    format!(
+        "you can not attack [{}], both need to be with pvp enabled, see 'pvp on'",
+        target
+    )
+}
+
+pub fn flee_fail_not_in_combat() -> String {
+    "you are not fighting anyone".to_string()
+}
+
+pub fn flee_fail_no_exit() -> String {
+    "there is no where to flee!".to_string()
+}
+
+pub fn flee_fail() -> String {
+    "you try to flee, but fail!".to_string()
+}
+
+pub fn flee_fail_others(label: &str) -> String {
+    format!("{} try to flee, but fail", label)
+}
+
+pub fn flee_success() -> String {
+    "you flee from combat!".to_string()
+}
+
+pub fn flee_others(label: &str) -> String {
+    format!("{} flee from combat", label)
+}
+
+pub fn wimpy_set(wimpy: Option<u32>) -> String {
+    match wimpy {
+        Some(value) => format!("you will flee when your pv drops to {}", value),
+        None => "you will fight until the death".to_string(),
+    }
+}
+
+pub fn wimpy_invalid_argument() -> String {
+    "invalid argument, use 'wimpy <pv>' or 'wimpy off'".to_string()
+}
+
+pub fn pvp_set(enabled: bool) -> String {
+    if enabled {
+        format!("pvp enabled, you can attack and be attacked by other players in pvp areas")
+    } else {
+        format!("pvp disabled")
+    }
+}
+
+pub fn pvp_show(enabled: bool, policy: PvpPolicy) -> String {
+    let policy_str = match policy {
+        PvpPolicy::Disabled => "players can not fight here",
+        PvpPolicy::Safe => "this is a safe place",
+        PvpPolicy::Allowed => "players with pvp enabled can fight here",
+        PvpPolicy::Arena => "this is a arena, everyone can fight here",
+    };
+
+    format!(
+        "your pvp is {}, {}",
+        if enabled { "enabled" } else { "disabled" },
+        policy_str
+    )
+}
+
+pub fn pvp_invalid_argument() -> String {
+    format!("invalid argument, use 'pvp on' or 'pvp off'")
+}
+
 pub fn attack_player_initiate(label: &str) -> String {
     format!("you attack {}!", label)
 }
@@ -193,11 +414,28 @@ pub fn kill_cancel(mob: &str, target: &str) -> String {
     format!("{} relax, {} is not around", mob, target)
 }
 
+/// describe damage types, like 'slash' or 'slash 2/3, fire 1/1' for multiples
+fn damage_desc(attack_result: &AttackResult) -> String {
+    match attack_result.damages.as_slice() {
+        [damage] => damage.kind.as_str().to_string(),
+        damages => {
+            let list: Vec<String> = damages
+                .iter()
+                .map(|i| format!("{} {}/{}", i.kind.as_str(), i.deliver, i.total))
+                .collect();
+            format!("({})", list.join(", "))
+        }
+    }
+}
+
 pub fn kill_player_execute_attack(target: &str, attack_result: &AttackResult) -> String {
     if attack_result.success {
         format!(
-            "you attack {} and hit, causing {}/{} damage!",
-            target, attack_result.damage_deliver, attack_result.damage_total
+            "you attack {} and hit, causing {}/{} {} damage!",
+            target,
+            attack_result.damage_deliver,
+            attack_result.damage_total,
+            damage_desc(attack_result)
         )
     } else {
         format!("you attack {} and miss!", target)
@@ -207,14 +445,87 @@ pub fn kill_player_execute_attack(target: &str, attack_result: &AttackResult) ->
 pub fn kill_mob_execute_attack(mob: &str, target: &str, attack_result: &AttackResult) -> String {
     if attack_result.success {
         format!(
-            "{} execute a attack and hit {} causing {}/{} damage!",
-            mob, target, attack_result.damage_deliver, attack_result.damage_total
+            "{} execute a attack and hit {} causing {}/{} {} damage!",
+            mob,
+            target,
+            attack_result.damage_deliver,
+            attack_result.damage_total,
+            damage_desc(attack_result)
         )
     } else {
         format!("{} execute a attack {} and miss!", mob, target)
     }
 }
 
+pub fn kill_player_execute_ranged_attack(
+    target: &str,
+    dir: &Dir,
+    attack_result: &AttackResult,
+) -> String {
+    if attack_result.success {
+        format!(
+            "you shoot {} at {} and hit, causing {}/{} {} damage!",
+            target,
+            dir.as_str(),
+            attack_result.damage_deliver,
+            attack_result.damage_total,
+            damage_desc(attack_result)
+        )
+    } else {
+        format!("you shoot {} at {} and miss!", target, dir.as_str())
+    }
+}
+
+pub fn kill_mob_execute_ranged_attack(
+    mob: &str,
+    target: &str,
+    dir: &Dir,
+    attack_result: &AttackResult,
+) -> String {
+    if attack_result.success {
+        format!(
+            "{} shoots {} at {} and hit causing {}/{} {} damage!",
+            mob,
+            target,
+            dir.as_str(),
+            attack_result.damage_deliver,
+            attack_result.damage_total,
+            damage_desc(attack_result)
+        )
+    } else {
+        format!("{} shoots {} at {} and miss!", mob, target, dir.as_str())
+    }
+}
+
+pub fn kill_mob_execute_ranged_attack_from(
+    mob: &str,
+    target: &str,
+    dir: &Dir,
+    attack_result: &AttackResult,
+) -> String {
+    if attack_result.success {
+        format!(
+            "{} shoots {} from {} and hit causing {}/{} {} damage!",
+            mob,
+            target,
+            dir.as_str(),
+            attack_result.damage_deliver,
+            attack_result.damage_total,
+            damage_desc(attack_result)
+        )
+    } else {
+        format!("{} shoots {} from {} and miss!", mob, target, dir.as_str())
+    }
+}
+
+pub fn kill_out_of_ammo() -> String {
+    "you are out of ammunition!".to_string()
+}
+
+pub fn shoot_no_ranged_weapon() -> String {
+    "you need a ranged weapon to shoot".to_string()
+}
+
 pub fn killed_by_player(mob: &str, xp: Xp) -> String {
     format!("you killed {} and receive {} XP", mob, xp)
 }
@@ -243,13 +554,17 @@ pub fn item_body_disappears(item: &str) -> String {
 }
 
 pub fn stats(xp: Xp, attributes: &Attributes, inventory: &InventoryDesc) -> String {
-    let inventory_str = show_inventory(inventory);
+    let inventory_str = format!(
+        "{}\n{}",
+        show_equipments(&inventory.equipments),
+        show_inventory(inventory)
+    );
 
     format!(
         "Stats: \n\
          attack:  {}\n\
          defense: {}\n\
-         damage:  {}-{}\n\
+         damage:  {}-{} {}\n\
          pv:      {}-{}\n\
          xp:      {}\n\
          {}\n",
@@ -257,6 +572,7 @@ pub fn stats(xp: Xp, attributes: &Attributes, inventory: &InventoryDesc) -> Stri
         attributes.defense,
         attributes.damage.min,
         attributes.damage.max,
+        attributes.damage.kind.as_str(),
         attributes.pv.current,
         attributes.pv.max,
         xp,
@@ -289,6 +605,14 @@ pub fn examine_target_item(item: &str, inventory: &InventoryDesc) -> String {
     format!("you examine {}!\n{}", item, show_inventory(inventory))
 }
 
+pub fn show_equipments(equipments: &Vec<EquipDesc>) -> String {
+    let mut buffer: Vec<String> = vec!["Equipment:".to_string()];
+    for equip in equipments {
+        buffer.push(format!("- {}: {}", equip.slot.as_str(), equip.label));
+    }
+    buffer.join("\n")
+}
+
 pub fn show_inventory(inventory: &InventoryDesc) -> String {
     let mut buffer: Vec<String> = vec!["Inventory:".to_string()];
     for item in &inventory.items {
@@ -422,6 +746,58 @@ pub fn drop_item_others(actor: &str, item_label: &str) -> String {
     format!("{} drop a {}\n", actor, item_label)
 }
 
+pub fn give_what() -> String {
+    "what do you want to give and to who?\n".to_string()
+}
+
+pub fn give_item_not_found(label: &str) -> String {
+    format!("you can not find a {} to give\n", label)
+}
+
+pub fn give_target_not_found(label: &str) -> String {
+    format!("you can not find {} to give\n", label)
+}
+
+pub fn give_target_can_not_carry(target: &str, item_label: &str) -> String {
+    format!("{} can not carry {}\n", target, item_label)
+}
+
+pub fn give_item(item_label: &str, target: &str) -> String {
+    format!("you give a {} to {}\n", item_label, target)
+}
+
+pub fn give_item_others(actor: &str, item_label: &str, target: &str) -> String {
+    format!("{} give a {} to {}\n", actor, item_label, target)
+}
+
+pub fn put_what() -> String {
+    "what do you want to put and where?\n".to_string()
+}
+
+pub fn put_item_not_found(label: &str) -> String {
+    format!("you can not find a {} to put\n", label)
+}
+
+pub fn put_container_not_found(label: &str) -> String {
+    format!("you can not find a {} to put into\n", label)
+}
+
+pub fn put_invalid_container(label: &str) -> String {
+    format!("you can not put anything into {}\n", label)
+}
+
+pub fn put_container_full(storage: &str, item_label: &str) -> String {
+    format!("{} can not hold {}\n", storage, item_label)
+}
+
+pub fn put_item(item_label: &str, storage: &str) -> String {
+    format!("you put a {} into {}\n", item_label, storage)
+}
+
+pub fn put_item_others(actor: &str, item_label: &str, storage: &str) -> String {
+    format!("{} put a {} into {}\n", actor, item_label, storage)
+}
+
 pub fn admin_invalid_command() -> String {
     format!("invalid admin command")
 }
@@ -458,6 +834,88 @@ pub fn rest_healed() -> String {
     format!("you feel fully healed")
 }
 
+pub fn rest_needs_empty() -> String {
+    "you are too hungry or thirsty to recover, you stop resting".to_string()
+}
+
+pub fn item_broken(mob: &str, item: &str) -> PPMsg {
+    PPMsg {
+        private_msg: format!("your {} broke!", item),
+        public_msg: format!("{}'s {} broke!", mob, item),
+    }
+}
+
+pub fn repair_what() -> String {
+    "what do you want to repair?".to_string()
+}
+
+pub fn repair_item_not_found(label: &str) -> String {
+    format!("you don't have {} to repair", label)
+}
+
+pub fn repair_invalid_item(item: &str) -> String {
+    format!("{} can not be repaired", item)
+}
+
+pub fn repair_not_needed(item: &str) -> String {
+    format!("{} does not need repair", item)
+}
+
+pub fn repair_invalid_kit(kit: &str) -> String {
+    format!("{} is not a repair kit", kit)
+}
+
+pub fn repair_with_kit(mob: &str, item: &str, kit: &str, durability: Durability) -> PPMsg {
+    PPMsg {
+        private_msg: format!(
+            "you repair {} using {}, durability {}/{}",
+            item, kit, durability.current, durability.max
+        ),
+        public_msg: format!("{} repair {} using {}", mob, item, kit),
+    }
+}
+
+pub fn needs_changed(kind: NeedKind, level: NeedLevel) -> String {
+    match (kind, level) {
+        (NeedKind::Hunger, NeedLevel::Low) => "you are hungry".to_string(),
+        (NeedKind::Hunger, NeedLevel::Empty) => "you are starving".to_string(),
+        (NeedKind::Thirst, NeedLevel::Low) => "you are thirsty".to_string(),
+        (NeedKind::Thirst, NeedLevel::Empty) => "you are dehydrated".to_string(),
+        (NeedKind::Hunger, NeedLevel::Ok) => "you are not hungry anymore".to_string(),
+        (NeedKind::Thirst, NeedLevel::Ok) => "you are not thirsty anymore".to_string(),
+    }
+}
+
+pub fn stats_needs(needs: &Needs) -> String {
+    format!(
+        "hunger:  {:.0}/{:.0}\nthirst:  {:.0}/{:.0}\n",
+        needs.hunger, NEEDS_MAX, needs.thirst, NEEDS_MAX
+    )
+}
+
+pub fn effect_expired(kind: EffectKind) -> String {
+    format!("the {} effect wore off", kind.as_str())
+}
+
+pub fn consume_what(verb: &str) -> String {
+    format!("what do you want to {}?", verb)
+}
+
+pub fn consume_item_not_found(verb: &str, label: &str) -> String {
+    format!("you can not find a {} to {}", label, verb)
+}
+
+pub fn consume_invalid_item(verb: &str, label: &str) -> String {
+    format!("you can not {} {}", verb, label)
+}
+
+pub fn consume_item(mob: &str, verb: &str, item: &str) -> PPMsg {
+    PPMsg {
+        private_msg: format!("you {} {}", verb, item),
+        public_msg: format!("{} {} {}", mob, verb, item),
+    }
+}
+
 pub fn rest_start_others(label: &str) -> String {
     format!("{} sit and rest", label)
 }
@@ -673,6 +1131,27 @@ pub fn space_launch_burning_circularization() -> String {
     OMarker::Sensorial.wrap("*desired AP reached, circularizing the orbit*")
 }
 
+pub fn space_land_aborted_weather(weather: WeatherKind) -> String {
+    OMarker::Sensorial.wrap(&format!(
+        "*landing aborted, {} at the landing zone*",
+        weather_label(weather)
+    ))
+}
+
+pub fn space_land_holding_weather(weather: WeatherKind) -> String {
+    OMarker::Sensorial.wrap(&format!(
+        "*holding position, waiting for the {} to pass*",
+        weather_label(weather)
+    ))
+}
+
+pub fn space_launch_aborted_weather(weather: WeatherKind) -> String {
+    OMarker::Sensorial.wrap(&format!(
+        "*launch aborted, can not launch during a {}*",
+        weather_label(weather)
+    ))
+}
+
 pub fn space_launch_failed() -> String {
     OMarker::Sensorial.wrap("*fail to launch, you can not launch from here*")
 }
@@ -807,6 +1286,8 @@ pub struct VendorTradeItemDisplay<'a> {
     pub label: &'a str,
     pub to_buy: Option<Money>,
     pub to_sell: Option<Money>,
+    /// amount in stock, None if unlimited
+    pub amount: Option<u32>,
 }
 
 pub fn vendor_operation_fail() -> String {
@@ -837,7 +1318,7 @@ pub fn vendor_can_not_sell(label: &str) -> String {
 pub fn vendor_list(list: Vec<VendorTradeItemDisplay>) -> String {
     let mut buffer = String::new();
     buffer.push_str("List\n");
-    buffer.push_str("name buy sell\n");
+    buffer.push_str("name buy sell amount\n");
 
     for item in list {
         buffer.push_str("- ");
@@ -854,6 +1335,11 @@ pub fn vendor_list(list: Vec<VendorTradeItemDisplay>) -> String {
         } else {
             buffer.push_str("XXX");
         }
+        buffer.push_str(" ");
+        match item.amount {
+            Some(amount) => buffer.push_str(&amount.to_string()),
+            None => buffer.push_str("-"),
+        }
         buffer.push_str("\n");
     }
 
@@ -868,6 +1354,10 @@ pub fn vendor_buy_item_not_found(label: &str) -> String {
     format!("seller don't have a '{}'", label)
 }
 
+pub fn vendor_buy_out_of_stock(label: &str) -> String {
+    format!("seller is out of '{}', come back later", label)
+}
+
 pub fn vendor_buy_you_have_not_enough_money(money: Money, price: Money) -> String {
     format!(
         "not enough money, it cost {} and you have only {}",
@@ -876,6 +1366,22 @@ pub fn vendor_buy_you_have_not_enough_money(money: Money, price: Money) -> Strin
     )
 }
 
+pub fn vendor_repair_not_enough_money(money: Money, cost: Money) -> String {
+    format!(
+        "not enough money, the repair cost {} and you have only {}",
+        cost.as_u32(),
+        money.as_u32()
+    )
+}
+
+pub fn vendor_repair_success(vendor: &str, item: &str, cost: Money) -> String {
+    format!("{} repair your {} for {}", vendor, item, cost.as_u32())
+}
+
+pub fn vendor_repair_no_vendor() -> String {
+    "there is nobody here to repair it, try 'repair <item> with <kit>'".to_string()
+}
+
 pub fn vendor_buy_success(item: &str, price: Money, new_money: Money) -> String {
     format!(
         "you bought a {} for {}, you have now {} of money",
@@ -938,6 +1444,343 @@ pub fn hire_list(candidates: Vec<&str>) -> String {
     buff
 }
 
+#[derive(Debug)]
+pub struct AuctionListDesc<'a> {
+    pub id: u32,
+    pub label: &'a str,
+    pub bid: Option<Money>,
+    pub buyout: Money,
+    pub expire_in: DeltaTime,
+    pub is_owner: bool,
+}
+
+pub fn auction_not_available() -> String {
+    "there is no auction house here".to_string()
+}
+
+pub fn auction_not_found() -> String {
+    "auction not found, check 'auction list'".to_string()
+}
+
+pub fn auction_invalid_command() -> String {
+    "invalid auction command, see help".to_string()
+}
+
+pub fn auction_invalid_price() -> String {
+    "invalid price".to_string()
+}
+
+pub fn auction_sell_invalid_item(label: &str) -> String {
+    format!("you can not auction {}", label)
+}
+
+pub fn auction_not_enough_money(amount: Money) -> String {
+    format!("you need {} of money", amount.as_u32())
+}
+
+pub fn auction_listed(label: &str, buyout: Money, fee: Money) -> String {
+    format!(
+        "{} listed for {}, you paid {} of listing fee",
+        label,
+        buyout.as_u32(),
+        fee.as_u32()
+    )
+}
+
+pub fn auction_own_item() -> String {
+    "you can not bid or buy your own items".to_string()
+}
+
+pub fn auction_bid_too_low(min_bid: Money) -> String {
+    format!("bid must be at least {}", min_bid.as_u32())
+}
+
+pub fn auction_bid(label: &str, amount: Money) -> String {
+    format!("you bid {} for {}", amount.as_u32(), label)
+}
+
+pub fn auction_outbid(label: &str) -> String {
+    format!(
+        "your bid for {} was outbid, collect your money in any auction house",
+        label
+    )
+}
+
+pub fn auction_won(label: &str, price: Money) -> String {
+    format!(
+        "you bought {} for {}, collect it in any auction house",
+        label,
+        price.as_u32()
+    )
+}
+
+pub fn auction_sold(label: &str, price: Money, fee: Money) -> String {
+    format!(
+        "{} was sold for {} with {} of fee, collect your money in any auction house",
+        label,
+        price.as_u32(),
+        fee.as_u32()
+    )
+}
+
+pub fn auction_expired(label: &str) -> String {
+    format!(
+        "auction of {} expired, collect it in any auction house",
+        label
+    )
+}
+
+pub fn auction_cancel_has_bids() -> String {
+    "you can not cancel a auction with bids".to_string()
+}
+
+pub fn auction_cancelled(label: &str) -> String {
+    format!("auction of {} cancelled, collect it back", label)
+}
+
+pub fn auction_nothing_to_collect() -> String {
+    "you have nothing to collect".to_string()
+}
+
+pub fn auction_collect_money(amount: Money) -> String {
+    format!("you collect {} of money", amount.as_u32())
+}
+
+pub fn auction_collect_item(label: &str) -> String {
+    format!("you collect {}", label)
+}
+
+pub fn auction_collect_too_heavy(label: &str) -> String {
+    format!("{} is too heavy to carry", label)
+}
+
+pub fn auction_list(listings: &Vec<AuctionListDesc>, to_collect: usize) -> String {
+    let mut buffer = "Auctions:\n".to_string();
+    buffer.push_str("id name bid buyout expire\n");
+    for listing in listings {
+        buffer.push_str(&format!(
+            "#{} {}{} {} {} {:.0}s\n",
+            listing.id,
+            listing.label,
+            if listing.is_owner { " (yours)" } else { "" },
+            listing
+                .bid
+                .map(|bid| bid.as_u32().to_string())
+                .unwrap_or("-".to_string()),
+            listing.buyout.as_u32(),
+            listing.expire_in.as_seconds_f32().max(0.0)
+        ));
+    }
+    if to_collect > 0 {
+        buffer.push_str(&format!(
+            "you have {} lots to collect, use 'auction collect'\n",
+            to_collect
+        ));
+    }
+    buffer
+}
+
+pub fn bank_not_available() -> String {
+    "there is no bank here".to_string()
+}
+
+pub fn bank_invalid_command() -> String {
+    "invalid bank command, see help".to_string()
+}
+
+pub fn bank_invalid_amount(value: &str) -> String {
+    format!("invalid amount {}", value)
+}
+
+pub fn bank_not_enough_money(amount: Money) -> String {
+    format!("you do not have {} to deposit", amount.as_u32())
+}
+
+pub fn bank_not_enough_balance(amount: Money) -> String {
+    format!("your vault do not have {} to withdraw", amount.as_u32())
+}
+
+pub fn bank_deposit(amount: Money, balance: Money) -> String {
+    format!(
+        "you deposit {}, your vault have now {}",
+        amount.as_u32(),
+        balance.as_u32()
+    )
+}
+
+pub fn bank_withdraw(amount: Money, balance: Money) -> String {
+    format!(
+        "you withdraw {}, your vault have now {}",
+        amount.as_u32(),
+        balance.as_u32()
+    )
+}
+
+pub fn bank_store_invalid_item(label: &str) -> String {
+    format!("you can not store {}", label)
+}
+
+pub fn bank_vault_full() -> String {
+    "your vault is full".to_string()
+}
+
+pub fn bank_store(label: &str) -> String {
+    format!("you store {} in your vault", label)
+}
+
+pub fn bank_retrieve_not_found(label: &str) -> String {
+    format!("there is no {} in your vault", label)
+}
+
+pub fn bank_retrieve_too_heavy(label: &str) -> String {
+    format!("you can not carry {}", label)
+}
+
+pub fn bank_retrieve(label: &str) -> String {
+    format!("you retrieve {} from your vault", label)
+}
+
+pub fn bank_show(balance: Money, items: &Vec<String>, max_items: usize) -> String {
+    let mut buffer = format!("Vault:\nmoney: {}\n", balance.as_u32());
+    buffer.push_str(&format!("items ({}/{}):\n", items.len(), max_items));
+    for label in items {
+        buffer.push_str(&format!("- {}\n", label));
+    }
+    buffer
+}
+
+#[derive(Debug)]
+pub struct TradeOfferDesc<'a> {
+    pub label: &'a str,
+    pub items: Vec<&'a str>,
+    pub money: Money,
+    pub confirmed: bool,
+}
+
+pub fn trade_invalid_target(label: &str) -> String {
+    format!("you can not trade with {}", label)
+}
+
+pub fn trade_target_not_found(label: &str) -> String {
+    format!("{} not found", label)
+}
+
+pub fn trade_already_trading() -> String {
+    "you are already trading, confirm or cancel it first".to_string()
+}
+
+pub fn trade_target_busy(label: &str) -> String {
+    format!("{} is busy trading with someone else", label)
+}
+
+pub fn trade_opened(partner_label: &str) -> String {
+    format!(
+        "you start to trade with {}, use 'trade offer <item>', 'trade money <amount>', 'trade confirm' or 'trade cancel'",
+        partner_label
+    )
+}
+
+pub fn trade_opened_partner(mob_label: &str) -> String {
+    format!(
+        "{} start to trade with you, use 'trade offer <item>', 'trade money <amount>', 'trade confirm' or 'trade cancel'",
+        mob_label
+    )
+}
+
+pub fn trade_not_trading() -> String {
+    "you are not trading with anyone".to_string()
+}
+
+pub fn trade_invalid_command() -> String {
+    "invalid trade command, see help".to_string()
+}
+
+pub fn trade_item_not_found(label: &str) -> String {
+    format!("you can not offer {}", label)
+}
+
+pub fn trade_item_equipped(label: &str) -> String {
+    format!("you need to remove {} before to trade it", label)
+}
+
+pub fn trade_item_already_offered(label: &str) -> String {
+    format!("{} is already offered", label)
+}
+
+pub fn trade_item_offered(label: &str) -> String {
+    format!("you offer {}", label)
+}
+
+pub fn trade_item_offered_partner(mob_label: &str, label: &str) -> String {
+    format!("{} offer {}", mob_label, label)
+}
+
+pub fn trade_item_removed(label: &str) -> String {
+    format!("you remove {} from the trade", label)
+}
+
+pub fn trade_item_removed_partner(mob_label: &str, label: &str) -> String {
+    format!("{} remove {} from the trade", mob_label, label)
+}
+
+pub fn trade_invalid_money(value: &str) -> String {
+    format!("invalid money amount {}", value)
+}
+
+pub fn trade_not_enough_money(amount: Money) -> String {
+    format!("you do not have {} of money", amount.as_u32())
+}
+
+pub fn trade_money_offered(amount: Money) -> String {
+    format!("you offer {} of money", amount.as_u32())
+}
+
+pub fn trade_money_offered_partner(mob_label: &str, amount: Money) -> String {
+    format!("{} offer {} of money", mob_label, amount.as_u32())
+}
+
+pub fn trade_confirmed(partner_label: &str) -> String {
+    format!("you confirm the trade, waiting for {}", partner_label)
+}
+
+pub fn trade_confirmed_partner(mob_label: &str) -> String {
+    format!("{} confirm the trade", mob_label)
+}
+
+pub fn trade_cancelled() -> String {
+    "trade cancelled".to_string()
+}
+
+pub fn trade_completed(partner_label: &str) -> String {
+    format!("trade with {} completed", partner_label)
+}
+
+pub fn trade_fail_invalid() -> String {
+    "trade fail, offered items are no longer available".to_string()
+}
+
+pub fn trade_fail_weight(label: &str) -> String {
+    format!("trade fail, {} can not carry all items", label)
+}
+
+pub fn trade_show(offer: &TradeOfferDesc, partner_offer: &TradeOfferDesc) -> String {
+    let mut buffer = "Trade:\n".to_string();
+    for desc in &[offer, partner_offer] {
+        buffer.push_str(&format!(
+            "{} offer{}:\n",
+            desc.label,
+            if desc.confirmed { " (confirmed)" } else { "" }
+        ));
+        for item in &desc.items {
+            buffer.push_str(&format!(" - {}\n", item));
+        }
+        if desc.money.as_u32() > 0 {
+            buffer.push_str(&format!(" - {} of money\n", desc.money.as_u32()));
+        }
+    }
+    buffer
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum RoomMapCell {
     Empty,
@@ -1069,6 +1912,95 @@ pub fn extract_stop(mob: &str, target: &str) -> PPMsg {
     }
 }
 
+pub fn craft_invalid_recipe(label: &str) -> String {
+    format!("you don't know how to craft '{}', check 'craft'", label)
+}
+
+pub fn craft_busy() -> String {
+    "you are busy doing something else".to_string()
+}
+
+pub fn craft_fail_workstation(recipe: &str, workstation: &str) -> String {
+    format!("you need a {} to craft {}", workstation, recipe)
+}
+
+pub fn craft_fail_tool(recipe: &str, tool: &str) -> String {
+    format!("you need a {} tool to craft {}", tool, recipe)
+}
+
+pub fn craft_fail_inputs(recipe: &str) -> String {
+    format!("you don't have the materials to craft {}", recipe)
+}
+
+pub fn craft_start(mob: &str, recipe: &str) -> PPMsg {
+    PPMsg {
+        private_msg: format!("you start to craft {}", recipe),
+        public_msg: format!("{} start to craft {}", mob, recipe),
+    }
+}
+
+pub fn craft_complete(mob: &str, item: &str) -> PPMsg {
+    PPMsg {
+        private_msg: format!("you craft {}", item),
+        public_msg: format!("{} craft {}", mob, item),
+    }
+}
+
+pub fn craft_complete_floor(mob: &str, item: &str) -> PPMsg {
+    PPMsg {
+        private_msg: format!(
+            "you craft {}, but it is too heavy and was left in the floor",
+            item
+        ),
+        public_msg: format!("{} craft {}", mob, item),
+    }
+}
+
+#[derive(Debug)]
+pub struct RecipeDesc {
+    pub label: String,
+    pub inputs: Vec<(u32, String)>,
+    pub outputs: Vec<(u32, String)>,
+    pub workstation: Option<String>,
+    pub tool: Option<String>,
+    pub time: DeltaTime,
+    pub can_craft: bool,
+}
+
+pub fn craft_list(recipes: &Vec<RecipeDesc>) -> String {
+    let format_items = |items: &Vec<(u32, String)>| {
+        items
+            .iter()
+            .map(|(amount, label)| format!("{} {}", amount, label))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut buffer = "Recipes:\n".to_string();
+    for recipe in recipes {
+        buffer.push_str(&format!(
+            "- {}{}: {} -> {} ({:.0}s)",
+            recipe.label,
+            if recipe.can_craft {
+                ""
+            } else {
+                " (missing materials)"
+            },
+            format_items(&recipe.inputs),
+            format_items(&recipe.outputs),
+            recipe.time.as_seconds_f32()
+        ));
+        if let Some(workstation) = &recipe.workstation {
+            buffer.push_str(&format!(", at {}", workstation));
+        }
+        if let Some(tool) = &recipe.tool {
+            buffer.push_str(&format!(", using {}", tool));
+        }
+        buffer.push_str("\n");
+    }
+    buffer
+}
+
 pub fn list_commandables(list: &Vec<&Label>) -> String {
     let mut buffer = "In your command:".to_string();
     if list.is_empty() {
@@ -1099,9 +2031,11 @@ pub fn list_commands(target: &str, list: &Vec<RequestCommand>) -> String {
     } else {
         for i in list {
             let label = match i {
-                RequestCommand::Idle => "idle",
+                RequestCommand::Idle => "wait",
                 RequestCommand::FollowMe => "follow me",
                 RequestCommand::Extract => "extract",
+                RequestCommand::Haul => "haul <from id> <to id>",
+                RequestCommand::Guard => "guard",
             };
 
             buffer.push_str(&format!(
@@ -1153,6 +2087,27 @@ pub fn command_extract_ack(target: &str) -> String {
     format!("{} is extracting", OMarker::Label.wrap(target))
 }
 
+pub fn command_wait_ack(target: &str) -> String {
+    format!("{} is waiting", OMarker::Label.wrap(target))
+}
+
+pub fn command_haul_ack(target: &str, from: &str, to: &str) -> String {
+    format!(
+        "{} is hauling from {} to {}",
+        OMarker::Label.wrap(target),
+        OMarker::Label.wrap(from),
+        OMarker::Label.wrap(to)
+    )
+}
+
+pub fn command_guard_ack(target: &str, room: &str) -> String {
+    format!(
+        "{} is guarding {}",
+        OMarker::Label.wrap(target),
+        OMarker::Label.wrap(room)
+    )
+}
+
 pub fn command_haul_invalid_argument() -> String {
     "invalid move command, you need to provide from and to".to_string()
 }
@@ -1171,6 +2126,34 @@ pub fn command_haul_to_not_found(value: &str) -> String {
     )
 }
 
+pub fn order_follow_ack() -> &'static str {
+    "right behind you"
+}
+
+pub fn order_wait_ack() -> &'static str {
+    "I will wait here"
+}
+
+pub fn order_guard_ack() -> &'static str {
+    "nobody will pass"
+}
+
+pub fn order_extract_ack() -> &'static str {
+    "back to work"
+}
+
+pub fn order_refuse_not_owner() -> &'static str {
+    "I do not take orders from you"
+}
+
+pub fn order_refuse_can_not() -> &'static str {
+    "I can not do that"
+}
+
+pub fn order_refuse_nothing_to_extract() -> &'static str {
+    "there is nothing to extract here"
+}
+
 pub fn command_travel_not_found() -> String {
     format!("you don't know any place to travel")
 }
@@ -1187,6 +2170,156 @@ pub fn command_travel_candidates(candidates: Vec<(String, f32)>) -> String {
     buffer
 }
 
+pub fn talk_target_not_found(target: &str) -> String {
+    format!("there is no {} to talk", target)
+}
+
+pub fn talk_nothing_to_say(npc: &str) -> String {
+    format!("{} has nothing to say", npc)
+}
+
+pub fn talk_start_others(mob: &str, npc: &str) -> String {
+    format!("{} talks with {}", mob, npc)
+}
+
+pub fn talk_node(npc: &str, text: &str, options: Vec<&str>) -> String {
+    let mut buffer = format!("{} says: {}", OMarker::Label.wrap(npc), text);
+    for (i, option) in options.iter().enumerate() {
+        buffer.push_str(&format!("\n{}) {}", i + 1, option));
+    }
+    buffer
+}
+
+pub fn talk_invalid_option(index: usize) -> String {
+    format!("{} is not a option, choose one of the numbers", index)
+}
+
+pub fn talk_npc_gone(npc: &str) -> String {
+    format!("{} is not here anymore", npc)
+}
+
+pub fn talk_end(npc: &str) -> String {
+    format!("you stop to talk with {}", npc)
+}
+
+pub fn talk_receive_item(item: &str) -> String {
+    format!("you receive {}", item)
+}
+
+pub fn talk_receive_money(amount: Money) -> String {
+    format!("you receive {} of money", amount.as_u32())
+}
+
+pub fn talk_pay_money(amount: Money) -> String {
+    format!("you pay {} of money", amount.as_u32())
+}
+
+pub fn talk_not_enough_money(amount: Money) -> String {
+    format!("you need {} of money", amount.as_u32())
+}
+
+pub fn talk_can_not_carry() -> String {
+    "you can not carry it".to_string()
+}
+
+pub fn quest_objective_kill(prefab: &str, current: u32, amount: u32) -> String {
+    format!("kill {} {}/{}", prefab, current, amount)
+}
+
+pub fn quest_objective_deliver(prefab: &str, target: &str, current: u32, amount: u32) -> String {
+    format!("deliver {} to {} {}/{}", prefab, target, current, amount)
+}
+
+pub fn quest_objective_reach(room: &str, done: bool) -> String {
+    format!("reach {} {}", room, if done { "1/1" } else { "0/1" })
+}
+
+pub fn quest_objective_extract(prefab: &str, current: u32, amount: u32) -> String {
+    format!("extract {} {}/{}", prefab, current, amount)
+}
+
+pub fn quest_started(quest: &str, objectives: Vec<String>) -> String {
+    let mut buffer = format!("new quest {}", OMarker::Label.wrap(quest));
+    for objective in objectives {
+        buffer.push_str(&format!("\n- {}", objective));
+    }
+    buffer
+}
+
+pub fn quest_already_active(quest: &str) -> String {
+    format!("you are already doing {}", quest)
+}
+
+pub fn quest_already_completed(quest: &str) -> String {
+    format!("you already completed {}", quest)
+}
+
+pub fn quest_list(quests: Vec<(&str, Vec<String>)>) -> String {
+    if quests.is_empty() {
+        return "you have no quests".to_string();
+    }
+
+    let mut buffer = "Quests:".to_string();
+    for (quest, objectives) in quests {
+        buffer.push_str(&format!("\n{}", OMarker::Label.wrap(quest)));
+        for objective in objectives {
+            buffer.push_str(&format!("\n- {}", objective));
+        }
+    }
+    buffer
+}
+
+pub fn quest_progress(quest: &str, objective: &str) -> String {
+    format!("{}: {}", quest, objective)
+}
+
+pub fn quest_completed(quest: &str) -> String {
+    format!("quest {} completed", quest)
+}
+
+pub fn quest_reward_xp(xp: u32) -> String {
+    format!("you receive {} xp", xp)
+}
+
+pub fn quest_reward_money(amount: Money) -> String {
+    format!("you receive {} of money", amount.as_u32())
+}
+
+pub fn quest_reward_item(item: &str) -> String {
+    format!("you receive {}", item)
+}
+
+pub fn quest_abandon(quest: &str) -> String {
+    format!("you abandon {}", quest)
+}
+
+pub fn quest_not_found(quest: &str) -> String {
+    format!("you have no quest {}", quest)
+}
+
+pub fn quest_invalid_command() -> String {
+    "usage: quests or quest abandon <quest>".to_string()
+}
+
+pub fn use_what() -> String {
+    "what do you want to use?".to_string()
+}
+
+pub fn use_item_not_found(label: &str) -> String {
+    format!("you can not find a {} to use", label)
+}
+
+pub fn use_nothing_happens(item: &str) -> String {
+    format!("you can not use {}", item)
+}
+
+pub fn use_item(mob: &str, item: &str) -> PPMsg {
+    PPMsg {
+        private_msg: format!("you use {}", item),
+        public_msg: format!("{} uses {}", mob, item),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
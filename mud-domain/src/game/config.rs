@@ -1,6 +1,8 @@
+use crate::game::calendar::Calendar;
 use crate::game::loader::dto::StaticId;
 use crate::game::room::RoomId;
 use commons::ObjId;
+use random_stuff::Conf;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -8,6 +10,9 @@ pub struct Config {
     pub initial_room: Option<RoomId>,
     pub avatar_id: Option<StaticId>,
     pub money_id: Option<StaticId>,
+    /// materials and items used to generate random items
+    pub random_items: Option<Conf>,
+    pub calendar: Calendar,
 }
 
 impl Config {
@@ -16,6 +21,8 @@ impl Config {
             initial_room: None,
             avatar_id: None,
             money_id: None,
+            random_items: None,
+            calendar: Calendar::new(),
         }
     }
 }
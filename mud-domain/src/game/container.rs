@@ -1,7 +1,9 @@
 use crate::errors::*;
 use crate::game::ai::AiRepo;
 use crate::game::astro_bodies::AstroBodies;
+use crate::game::auctions::Auctions;
 use crate::game::config::Config;
+use crate::game::dialogues::Dialogues;
 use crate::game::domain::{GameTime, MobCtx, PlayerCtx};
 use crate::game::equip::Equips;
 use crate::game::hire::Hires;
@@ -16,11 +18,16 @@ use crate::game::mob::{MobId, MobRepository};
 use crate::game::obj::Objects;
 use crate::game::outputs::Outputs;
 use crate::game::ownership::*;
+use crate::game::pathfinding::Paths;
 use crate::game::player::PlayerRepository;
 use crate::game::pos::PosRepo;
 use crate::game::prices::Prices;
+use crate::game::pvp::Aggressions;
+use crate::game::quests::Quests;
 use crate::game::random_rooms::RandomRoomsRepository;
+use crate::game::recipes::Recipes;
 use crate::game::room::{RoomId, RoomRepository};
+use crate::game::scripts::Scripts;
 use crate::game::ships::Ships;
 use crate::game::spawn::Spawns;
 use crate::game::surfaces::Surfaces;
@@ -28,8 +35,11 @@ use crate::game::surfaces_object::SurfaceObjects;
 use crate::game::system::{item_system, rest_system, ship_system, spawn_system};
 use crate::game::tags::Tags;
 use crate::game::timer::*;
+use crate::game::trades::Trades;
 use crate::game::triggers::*;
+use crate::game::vaults::Vaults;
 use crate::game::vendors::Vendors;
+use crate::game::weather::Weathers;
 use crate::game::zone::{ZoneId, Zones};
 use crate::game::{item, mob, spawn, system};
 use commons::{DeltaTime, ObjId, PlayerId};
@@ -87,6 +97,17 @@ pub struct Container {
     pub ai: AiRepo,
     pub extractables: Extractables,
     pub travels: Travels,
+    pub aggressions: Aggressions,
+    pub trades: Trades,
+    pub auctions: Auctions,
+    pub vaults: Vaults,
+    pub recipes: Recipes,
+    pub dialogues: Dialogues,
+    pub quests: Quests,
+    pub scripts: Scripts,
+    pub weathers: Weathers,
+    #[serde(skip)]
+    pub paths: Paths,
 }
 
 impl Container {
@@ -125,6 +146,16 @@ impl Container {
             ai: AiRepo::new(),
             extractables: Extractables::new(),
             travels: Travels::new(),
+            aggressions: Aggressions::new(),
+            trades: Trades::new(),
+            auctions: Auctions::new(),
+            vaults: Vaults::new(),
+            recipes: Recipes::new(),
+            dialogues: Dialogues::new(),
+            quests: Quests::new(),
+            scripts: Scripts::new(),
+            weathers: Weathers::new(),
+            paths: Paths::new(),
         }
     }
 
@@ -148,6 +179,14 @@ impl Container {
         self.inventories.remove(obj_id);
         self.ai.remove(obj_id);
         self.extractables.remove(obj_id);
+        self.aggressions.remove(obj_id);
+        self.auctions.remove(obj_id);
+        self.vaults.remove(obj_id);
+        self.recipes.remove(obj_id);
+        self.dialogues.remove(obj_id);
+        self.quests.remove(obj_id);
+        self.scripts.remove(obj_id);
+        self.weathers.remove(obj_id);
 
         self.triggers.push(Event::Obj {
             kind: EventKind::Removed,
@@ -0,0 +1,395 @@
+use crate::errors::{Error, Result};
+use crate::game::loader::dto::{
+    DialogueConditionData, DialogueData, DialogueEffectData, DialogueNodeData, DialogueOptionData,
+    StaticId,
+};
+use crate::game::loader::LoadingCtx;
+use crate::game::prices::Money;
+use crate::game::quests::QuestId;
+use crate::game::tags::{TagId, Tags};
+use commons::ObjId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub type DialogueId = ObjId;
+pub type DialogueNodeId = String;
+
+/// Requirements of the talking mob for a option to be available
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DialogueCondition {
+    Tag(TagId),
+    NotTag(TagId),
+    Money(Money),
+    Item(StaticId),
+}
+
+/// Applied to the talking mob when the option is selected, flags are just tags of the mob
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DialogueEffect {
+    GiveItem(StaticId),
+    GiveMoney(Money),
+    TakeMoney(Money),
+    SetFlag(TagId),
+    StartQuest(QuestId),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DialogueOption {
+    pub text: String,
+    /// conversation ends when there is no next node
+    pub next: Option<DialogueNodeId>,
+    pub conditions: Vec<DialogueCondition>,
+    pub effects: Vec<DialogueEffect>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DialogueNode {
+    pub text: String,
+    pub options: Vec<DialogueOption>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Dialogue {
+    pub id: DialogueId,
+    pub start: DialogueNodeId,
+    pub nodes: HashMap<DialogueNodeId, DialogueNode>,
+}
+
+impl Dialogue {
+    pub fn get_node(&self, node_id: &str) -> Option<&DialogueNode> {
+        self.nodes.get(node_id)
+    }
+
+    pub fn parse(
+        tags: &mut Tags,
+        references: &LoadingCtx,
+        id: DialogueId,
+        data: &DialogueData,
+    ) -> Result<Self> {
+        let mut nodes = HashMap::new();
+
+        for (node_id, node_data) in &data.nodes {
+            let mut options = vec![];
+
+            for option_data in node_data.options.iter().flatten() {
+                if let Some(next) = &option_data.next {
+                    if !data.nodes.contains_key(next) {
+                        return Err(Error::InvalidArgumentFailureStr(format!(
+                            "dialogue {:?} node {:?} option has unknown next node {:?}",
+                            id, node_id, next
+                        )));
+                    }
+                }
+
+                options.push(DialogueOption {
+                    text: option_data.text.clone(),
+                    next: option_data.next.clone(),
+                    conditions: option_data
+                        .conditions
+                        .iter()
+                        .flatten()
+                        .map(|condition| parse_condition(tags, id, condition))
+                        .collect::<Result<Vec<_>>>()?,
+                    effects: option_data
+                        .effects
+                        .iter()
+                        .flatten()
+                        .map(|effect| parse_effect(tags, references, id, effect))
+                        .collect::<Result<Vec<_>>>()?,
+                });
+            }
+
+            nodes.insert(
+                node_id.clone(),
+                DialogueNode {
+                    text: node_data.text.clone(),
+                    options,
+                },
+            );
+        }
+
+        if !nodes.contains_key(&data.start) {
+            return Err(Error::InvalidArgumentFailureStr(format!(
+                "dialogue {:?} has unknown start node {:?}",
+                id, data.start
+            )));
+        }
+
+        Ok(Dialogue {
+            id,
+            start: data.start.clone(),
+            nodes,
+        })
+    }
+
+    pub fn to_data(&self, tags: &Tags) -> DialogueData {
+        let resolve = |tag_id: TagId| tags.get_str(tag_id).map(|tag| tag.to_string());
+
+        let nodes: BTreeMap<DialogueNodeId, DialogueNodeData> = self
+            .nodes
+            .iter()
+            .map(|(node_id, node)| {
+                let options = node
+                    .options
+                    .iter()
+                    .map(|option| DialogueOptionData {
+                        text: option.text.clone(),
+                        next: option.next.clone(),
+                        conditions: Some(
+                            option
+                                .conditions
+                                .iter()
+                                .map(|condition| {
+                                    let mut data = DialogueConditionData::new();
+                                    match condition {
+                                        DialogueCondition::Tag(tag_id) => {
+                                            data.tag = resolve(*tag_id)
+                                        }
+                                        DialogueCondition::NotTag(tag_id) => {
+                                            data.not_tag = resolve(*tag_id)
+                                        }
+                                        DialogueCondition::Money(money) => {
+                                            data.money = Some(money.as_u32())
+                                        }
+                                        DialogueCondition::Item(prefab_id) => {
+                                            data.item = Some(*prefab_id)
+                                        }
+                                    }
+                                    data
+                                })
+                                .collect(),
+                        ),
+                        effects: Some(
+                            option
+                                .effects
+                                .iter()
+                                .map(|effect| {
+                                    let mut data = DialogueEffectData::new();
+                                    match effect {
+                                        DialogueEffect::GiveItem(prefab_id) => {
+                                            data.give_item = Some(*prefab_id)
+                                        }
+                                        DialogueEffect::GiveMoney(money) => {
+                                            data.give_money = Some(money.as_u32())
+                                        }
+                                        DialogueEffect::TakeMoney(money) => {
+                                            data.take_money = Some(money.as_u32())
+                                        }
+                                        DialogueEffect::SetFlag(tag_id) => {
+                                            data.set_flag = resolve(*tag_id)
+                                        }
+                                        DialogueEffect::StartQuest(quest_id) => {
+                                            data.start_quest = Some((*quest_id).into())
+                                        }
+                                    }
+                                    data
+                                })
+                                .collect(),
+                        ),
+                    })
+                    .collect();
+
+                (
+                    node_id.clone(),
+                    DialogueNodeData {
+                        text: node.text.clone(),
+                        options: Some(options),
+                    },
+                )
+            })
+            .collect();
+
+        DialogueData {
+            start: self.start.clone(),
+            nodes,
+        }
+    }
+}
+
+fn parse_condition(
+    tags: &mut Tags,
+    id: DialogueId,
+    data: &DialogueConditionData,
+) -> Result<DialogueCondition> {
+    match (&data.tag, &data.not_tag, data.money, data.item) {
+        (Some(tag), None, None, None) => Ok(DialogueCondition::Tag(tags.get_id(tag))),
+        (None, Some(tag), None, None) => Ok(DialogueCondition::NotTag(tags.get_id(tag))),
+        (None, None, Some(money), None) => Ok(DialogueCondition::Money(Money(money))),
+        (None, None, None, Some(prefab_id)) => Ok(DialogueCondition::Item(prefab_id)),
+        _ => Err(Error::InvalidArgumentFailureStr(format!(
+            "dialogue {:?} condition require exactly one of tag, not_tag, money or item",
+            id
+        ))),
+    }
+}
+
+fn parse_effect(
+    tags: &mut Tags,
+    references: &LoadingCtx,
+    id: DialogueId,
+    data: &DialogueEffectData,
+) -> Result<DialogueEffect> {
+    match (
+        data.give_item,
+        data.give_money,
+        data.take_money,
+        &data.set_flag,
+        data.start_quest,
+    ) {
+        (Some(prefab_id), None, None, None, None) => Ok(DialogueEffect::GiveItem(prefab_id)),
+        (None, Some(money), None, None, None) => Ok(DialogueEffect::GiveMoney(Money(money))),
+        (None, None, Some(money), None, None) => Ok(DialogueEffect::TakeMoney(Money(money))),
+        (None, None, None, Some(flag), None) => Ok(DialogueEffect::SetFlag(tags.get_id(flag))),
+        (None, None, None, None, Some(quest_id)) => {
+            // quests in the same file are mapped to the created objects
+            let quest_id = references
+                .id_map
+                .get(&quest_id)
+                .cloned()
+                .unwrap_or(ObjId(quest_id.as_u32()));
+            Ok(DialogueEffect::StartQuest(quest_id))
+        }
+        _ => Err(Error::InvalidArgumentFailureStr(format!(
+            "dialogue {:?} effect require exactly one of give_item, give_money, take_money, set_flag or start_quest",
+            id
+        ))),
+    }
+}
+
+/// Where a mob is in a dialogue, it is not persisted and kept by the connection
+#[derive(Clone, Debug, PartialEq)]
+pub struct Conversation {
+    pub npc_id: ObjId,
+    pub dialogue_id: DialogueId,
+    pub node_id: DialogueNodeId,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Dialogues {
+    index: HashMap<DialogueId, Dialogue>,
+    /// npcs and the dialogue they use when someone talk with them
+    talkers: HashMap<ObjId, DialogueId>,
+}
+
+impl Dialogues {
+    pub fn new() -> Self {
+        Dialogues {
+            index: HashMap::new(),
+            talkers: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, dialogue: Dialogue) -> Result<()> {
+        if self.index.contains_key(&dialogue.id) {
+            return Err(Error::ConflictException);
+        }
+        self.index.insert(dialogue.id, dialogue);
+        Ok(())
+    }
+
+    pub fn get(&self, id: DialogueId) -> Option<&Dialogue> {
+        self.index.get(&id)
+    }
+
+    pub fn set_talker(&mut self, npc_id: ObjId, dialogue_id: DialogueId) {
+        self.talkers.insert(npc_id, dialogue_id);
+    }
+
+    pub fn get_talker(&self, npc_id: ObjId) -> Option<DialogueId> {
+        self.talkers.get(&npc_id).cloned()
+    }
+
+    pub fn remove(&mut self, id: ObjId) {
+        self.index.remove(&id);
+        self.talkers.remove(&id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::container::Container;
+    use crate::game::loader::Loader;
+
+    const DIALOGUE: &str = r##"
+objects.0 {
+  id: 0
+  label: "smith talk"
+  dialogue {
+    start: "hello"
+    nodes {
+      hello {
+        text: "What do you want?"
+        options: [
+          {text: "Give me a sword", next: "sword", conditions: [{not_tag: "got_sword"}], effects: [{give_item: 2}, {set_flag: "got_sword"}]}
+          {text: "Bye"}
+        ]
+      }
+      sword {
+        text: "Take it."
+      }
+    }
+  }
+}
+objects.1 {
+  id: 1
+  label: "smith"
+  dialogue_id: 0
+}
+"##;
+
+    #[test]
+    fn test_load_and_to_data() {
+        let mut container = Container::new();
+        let lc = Loader::load_hocon(&mut container, DIALOGUE).unwrap();
+        let dialogue_id = lc.get(0);
+        assert_eq!(Some(dialogue_id), container.dialogues.get_talker(lc.get(1)));
+
+        let tags = &container.tags;
+        let dialogue = container.dialogues.get(dialogue_id).unwrap();
+        let hello = dialogue.get_node("hello").unwrap();
+        assert_eq!(2, hello.options.len());
+        assert_eq!(
+            vec![DialogueCondition::NotTag(
+                tags.find_id("got_sword").unwrap()
+            )],
+            hello.options[0].conditions
+        );
+        assert_eq!(
+            vec![
+                DialogueEffect::GiveItem(StaticId(2)),
+                DialogueEffect::SetFlag(tags.find_id("got_sword").unwrap())
+            ],
+            hello.options[0].effects
+        );
+        assert_eq!(None, hello.options[1].next);
+
+        let data = dialogue.to_data(tags);
+        let mut tags = container.tags.clone();
+        let dialogue2 = Dialogue::parse(&mut tags, &lc, dialogue_id, &data).unwrap();
+        assert_eq!(
+            hello.options[0].effects,
+            dialogue2.get_node("hello").unwrap().options[0].effects
+        );
+    }
+
+    #[test]
+    fn test_parse_should_fail_with_unknown_nodes() {
+        let mut container = Container::new();
+        let lc = Loader::load_hocon(&mut container, DIALOGUE).unwrap();
+        let dialogue_id = lc.get(0);
+        let data = container
+            .dialogues
+            .get(dialogue_id)
+            .unwrap()
+            .to_data(&container.tags);
+
+        let mut invalid = data.clone();
+        invalid.start = "unknown".to_string();
+        assert!(Dialogue::parse(&mut container.tags, &lc, dialogue_id, &invalid).is_err());
+
+        let mut invalid = data.clone();
+        invalid.nodes.remove("sword");
+        assert!(Dialogue::parse(&mut container.tags, &lc, dialogue_id, &invalid).is_err());
+    }
+}
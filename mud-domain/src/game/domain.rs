@@ -14,7 +14,12 @@ pub struct Modifier(pub i32);
 impl Modifier {
     /// Apply modifier to a value by increasing / decreasing it, min attribute is zero
     pub fn apply(&self, value: Attribute) -> Attribute {
-        0.min(value as i32 + self.0) as u32
+        0.max(value as i32 + self.0) as u32
+    }
+
+    /// Reduce or increase the modifier by a multiplier, rounded toward zero
+    pub fn scale(&self, mult: f32) -> Modifier {
+        Modifier((self.0 as f32 * mult) as i32)
     }
 
     pub fn as_i32(&self) -> i32 {
@@ -135,3 +140,21 @@ impl NextId {
         self.next = self.next.max(id + 1);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_modifier_apply_should_not_go_below_zero() {
+        assert_eq!(7, Modifier(2).apply(5));
+        assert_eq!(3, Modifier(-2).apply(5));
+        assert_eq!(0, Modifier(-10).apply(5));
+    }
+
+    #[test]
+    fn test_modifier_scale_should_round_toward_zero() {
+        assert_eq!(Modifier(1), Modifier(3).scale(0.5));
+        assert_eq!(Modifier(-1), Modifier(-3).scale(0.5));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::errors;
+use crate::errors::Error;
 use crate::game::item::ItemId;
 use crate::game::mob::MobId;
 use commons::ObjId;
@@ -6,6 +8,76 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum EquipSlot {
+    Head,
+    Neck,
+    Body,
+    Hands,
+    MainHand,
+    OffHand,
+    Ring,
+    Legs,
+    Feet,
+}
+
+impl EquipSlot {
+    pub fn parse(value: &str) -> errors::Result<EquipSlot> {
+        match value {
+            "head" => Ok(EquipSlot::Head),
+            "neck" => Ok(EquipSlot::Neck),
+            "body" => Ok(EquipSlot::Body),
+            "hands" => Ok(EquipSlot::Hands),
+            "main-hand" => Ok(EquipSlot::MainHand),
+            "off-hand" => Ok(EquipSlot::OffHand),
+            "ring" => Ok(EquipSlot::Ring),
+            "legs" => Ok(EquipSlot::Legs),
+            "feet" => Ok(EquipSlot::Feet),
+            other => Err(Error::InvalidArgumentFailureStr(format!(
+                "invalid equip slot {:?}",
+                other
+            ))),
+        }
+    }
+
+    /// parse a list of slots, 'two-handed' is a shortcut for main-hand and off-hand
+    pub fn parse_list(values: &Vec<String>) -> errors::Result<Vec<EquipSlot>> {
+        let mut slots = vec![];
+        for value in values {
+            match value.as_str() {
+                "two-handed" => {
+                    slots.push(EquipSlot::MainHand);
+                    slots.push(EquipSlot::OffHand);
+                }
+                other => slots.push(EquipSlot::parse(other)?),
+            }
+        }
+        Ok(slots)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EquipSlot::Head => "head",
+            EquipSlot::Neck => "neck",
+            EquipSlot::Body => "body",
+            EquipSlot::Hands => "hands",
+            EquipSlot::MainHand => "main-hand",
+            EquipSlot::OffHand => "off-hand",
+            EquipSlot::Ring => "ring",
+            EquipSlot::Legs => "legs",
+            EquipSlot::Feet => "feet",
+        }
+    }
+
+    /// how many items can be equipped in the same slot
+    pub fn capacity(&self) -> usize {
+        match self {
+            EquipSlot::Ring => 2,
+            _ => 1,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Equip {
     pub obj_id: MobId,
@@ -54,6 +126,13 @@ impl Equips {
         }
     }
 
+    pub fn is_equipped(&self, mob_id: MobId, item_id: ItemId) -> bool {
+        self.index
+            .get(&mob_id)
+            .map(|equip| equip.equipments.contains(&item_id))
+            .unwrap_or(false)
+    }
+
     pub fn get(&self, id: MobId) -> HashSet<ItemId> {
         self.index
             .get(&id)
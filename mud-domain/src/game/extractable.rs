@@ -7,6 +7,7 @@ use crate::game::inventory_service::{
 use crate::game::loader::dto::{CanLoad, ObjData, StaticId};
 use crate::game::loader::{Loader, LoadingCtx};
 use crate::game::mob::{MobId, EXTRACT_TIME};
+use crate::game::triggers::Event;
 use crate::utils::strinput::StrInput;
 use commons::{ObjId, TimeTrigger};
 
@@ -147,6 +148,11 @@ pub fn tick_extract(container: &mut Container, mob_id: MobId, target_id: ObjId)
             let item_id = Loader::spawn_at(container, prefab_id, mob_id)?;
             update_inventory_weight(container, mob_id)?;
 
+            container.triggers.push(Event::Extract {
+                obj_id: mob_id,
+                item_id,
+            });
+
             log::info!(
                 "{:?} extract a {:?} with id {:?}",
                 mob_id,
@@ -3,9 +3,10 @@ use crate::game::container::Container;
 use crate::game::item::{Item, ItemId, ItemRepository, Weight};
 use crate::game::labels::Labels;
 use crate::game::loader::dto::StaticId;
-use crate::game::loader::Loader;
+use crate::game::loader::{Loader, LoadingCtx};
 use crate::game::location;
 use crate::game::location::{LocationId, Locations};
+use crate::game::obj::PrefabId;
 use crate::game::prices::Money;
 use commons::ObjId;
 
@@ -63,7 +64,7 @@ pub fn update_inventory_weight(container: &mut Container, location_id: LocationI
     let total_weight = locations
         .list_deep_at(location_id)
         .into_iter()
-        .flat_map(|id| items.get(id).and_then(|item| item.weight))
+        .flat_map(|id| items.get(id).map(|item| item.total_weight()))
         .sum();
 
     inventory.current_weight = Some(total_weight);
@@ -71,6 +72,27 @@ pub fn update_inventory_weight(container: &mut Container, location_id: LocationI
     Ok(())
 }
 
+/// update the weight of the location and all its parents, used when the location is a container
+/// that can be inside other containers
+pub fn update_inventory_weight_with_parents(
+    container: &mut Container,
+    location_id: LocationId,
+) -> Result<()> {
+    for id in container.locations.list_parents_inclusive(location_id) {
+        update_inventory_weight(container, id)?;
+    }
+    Ok(())
+}
+
+/// weight of the item including all its contents
+pub fn get_deep_weight(container: &Container, item_id: ItemId) -> Weight {
+    let mut ids = container.locations.list_deep_at(item_id);
+    ids.push(item_id);
+    ids.into_iter()
+        .flat_map(|id| container.items.get(id).map(|item| item.total_weight()))
+        .sum()
+}
+
 pub fn add(container: &mut Container, item_id: ItemId, location_id: LocationId) -> Result<()> {
     let item = container.items.get(item_id).as_result()?;
 
@@ -86,11 +108,89 @@ pub fn add(container: &mut Container, item_id: ItemId, location_id: LocationId)
 
         add_money_with_item(container, location_id, amount, Some(item_id))
     } else {
-        container.locations.set(item_id, location_id);
-        Ok(())
+        add_or_merge(container, item_id, location_id).map(|_| ())
     }
 }
 
+/// find a stack in the location where a stackable item can be merged
+pub fn find_stack(
+    container: &Container,
+    item_id: ItemId,
+    location_id: LocationId,
+) -> Option<ItemId> {
+    let item = container.items.get(item_id)?;
+    if !item.flags.is_stackable {
+        return None;
+    }
+
+    let prefab_id = container.objects.get_prefab_id(item_id)?;
+
+    get_inventory_list(&container.locations, &container.items, location_id)
+        .filter(|other| other.id != item_id && other.flags.is_stackable)
+        .filter(|other| !container.equips.is_equipped(location_id, other.id))
+        .map(|other| other.id)
+        .find(|other_id| container.objects.get_prefab_id(*other_id) == Some(prefab_id))
+}
+
+/// move the item into the location merging with a existing stack, returns the id of the item
+/// that hold the amount, the moved item is removed when merged
+pub fn add_or_merge(
+    container: &mut Container,
+    item_id: ItemId,
+    location_id: LocationId,
+) -> Result<ItemId> {
+    match find_stack(container, item_id, location_id) {
+        Some(stack_id) => {
+            let amount = container.items.get(item_id).as_result()?.amount;
+            container.items.get_mut(stack_id).as_result()?.amount += amount;
+            container.remove(item_id);
+            log::debug!(
+                "{:?} merged {:?} into stack {:?}",
+                location_id,
+                item_id,
+                stack_id
+            );
+            Ok(stack_id)
+        }
+        None => {
+            container.locations.set(item_id, location_id);
+            Ok(item_id)
+        }
+    }
+}
+
+/// split a amount from a stack into a new item at same location, if amount is equal or bigger
+/// that the stack the same item is returned
+pub fn split(container: &mut Container, item_id: ItemId, amount: u32) -> Result<ItemId> {
+    let item = container.items.get(item_id).as_result()?;
+    if amount == 0 {
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    if amount >= item.amount {
+        return Ok(item_id);
+    }
+
+    // copy the source item instead of instantiate the prefab to keep instance state like
+    // durability and price, generated items also have no prefab
+    let mut data = Loader::snapshot_obj(container, item_id)?;
+    data.id = None;
+    data.parent = None;
+    data.children = None;
+
+    let location_id = container.locations.get(item_id);
+    let new_item_id = container.objects.create();
+    Loader::apply_data(container, new_item_id, &data, &LoadingCtx::default())?;
+    container.items.get_mut(new_item_id).as_result()?.amount = amount;
+    container.items.get_mut(item_id).as_result()?.amount -= amount;
+
+    if let Some(location_id) = location_id {
+        container.locations.set(new_item_id, location_id);
+    }
+
+    Ok(new_item_id)
+}
+
 pub fn add_money(container: &mut Container, obj_id: ObjId, amount: Money) -> Result<()> {
     // check if already have any money item and append, otherwise find one in loader and spawn
     add_money_with_item(container, obj_id, amount, None)
@@ -208,6 +308,36 @@ fn add_money_with_item(
     }
 }
 
+/// find the first item in the inventory instantiated from the prefab
+pub fn find_by_prefab(container: &Container, obj_id: ObjId, prefab_id: PrefabId) -> Option<ItemId> {
+    get_inventory_list(&container.locations, &container.items, obj_id)
+        .map(|item| item.id)
+        .find(|item_id| container.objects.get_prefab_id(*item_id) == Some(prefab_id))
+}
+
+/// consume one unit of a item, the item is removed when the last unit is consumed
+pub fn consume_one(container: &mut Container, item_id: ItemId) -> Result<()> {
+    consume(container, item_id, 1)
+}
+
+/// consume a amount of a item, the item is removed when the last unit is consumed
+pub fn consume(container: &mut Container, item_id: ItemId, amount: u32) -> Result<()> {
+    let location_id = container.locations.get(item_id);
+    let item = container.items.get_mut(item_id).as_result()?;
+
+    if item.amount > amount {
+        item.amount -= amount;
+    } else {
+        container.remove(item_id);
+    }
+
+    if let Some(location_id) = location_id {
+        update_inventory_weight(container, location_id)?;
+    }
+
+    Ok(())
+}
+
 pub fn move_all(locations: &mut Locations, from: ObjId, to: ObjId) {
     let list: Vec<_> = locations.list_at(from).collect();
     for i in list {
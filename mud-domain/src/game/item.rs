@@ -1,7 +1,10 @@
 use super::comm;
 use crate::errors::{AsResult, Error, Result};
 use crate::game::domain::{Modifier, Rd};
-use crate::game::mob::Damage;
+use crate::game::equip::EquipSlot;
+use crate::game::mob::{Damage, Resistance};
+use crate::game::needs::Consumable;
+use crate::game::obj::PrefabId;
 use commons::*;
 
 use serde::{Deserialize, Serialize};
@@ -21,6 +24,10 @@ pub struct ItemFlags {
     pub is_corpse: bool,
     // TODO: should really exists? Normalize use cases
     pub is_money: bool,
+    /// items from same prefab are merged into a single item with amount
+    pub is_stackable: bool,
+    /// generated by random items, it has no prefab
+    pub is_random: bool,
 }
 
 impl ItemFlags {
@@ -30,6 +37,8 @@ impl ItemFlags {
             is_stuck: false,
             is_corpse: false,
             is_money: false,
+            is_stackable: false,
+            is_random: false,
         }
     }
 }
@@ -43,6 +52,13 @@ pub struct Item {
     pub armor: Option<Armor>,
     pub flags: ItemFlags,
     pub weight: Option<Weight>,
+    /// slots used when equipped, when empty weapons use main-hand and armor body
+    pub slots: Vec<EquipSlot>,
+    pub consumable: Option<Consumable>,
+    /// weapons and armors wear on use and stop to provide bonus when broken
+    pub durability: Option<Durability>,
+    /// durability points restored when used to repair other item
+    pub repair_kit: Option<u32>,
 }
 
 impl Item {
@@ -55,6 +71,10 @@ impl Item {
             armor: None,
             flags: ItemFlags::new(),
             weight: None,
+            slots: vec![],
+            consumable: None,
+            durability: None,
+            repair_kit: None,
         }
     }
 
@@ -62,24 +82,117 @@ impl Item {
         self.weapon.is_some() || self.armor.is_some()
     }
 
+    pub fn get_equip_slots(&self) -> Vec<EquipSlot> {
+        if !self.slots.is_empty() {
+            self.slots.clone()
+        } else if self.weapon.is_some() {
+            vec![EquipSlot::MainHand]
+        } else if self.armor.is_some() {
+            vec![EquipSlot::Body]
+        } else {
+            vec![]
+        }
+    }
+
     pub fn total_weight(&self) -> Weight {
         self.weight.unwrap_or(0.0) * self.amount as f32
     }
+
+    pub fn is_broken(&self) -> bool {
+        self.durability
+            .as_ref()
+            .map(|durability| durability.is_broken())
+            .unwrap_or(false)
+    }
+}
+
+/// Below this ratio of durability the item bonus are reduced by half
+pub const DURABILITY_WORN_RATIO: f32 = 0.5;
+/// Durability lost by a weapon on each attack or by an armor on each hit received
+pub const DURABILITY_LOSS_PER_USE: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Durability {
+    pub current: u32,
+    pub max: u32,
+}
+
+impl Durability {
+    pub fn new(max: u32) -> Self {
+        Durability { current: max, max }
+    }
+
+    pub fn ratio(&self) -> f32 {
+        if self.max == 0 {
+            0.0
+        } else {
+            self.current as f32 / self.max as f32
+        }
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.current == 0
+    }
+
+    pub fn is_worn(&self) -> bool {
+        self.ratio() < DURABILITY_WORN_RATIO
+    }
+
+    pub fn missing(&self) -> u32 {
+        self.max.saturating_sub(self.current)
+    }
+
+    /// multiplier applied to item bonus
+    pub fn bonus_mult(&self) -> f32 {
+        if self.is_broken() {
+            0.0
+        } else if self.is_worn() {
+            0.5
+        } else {
+            1.0
+        }
+    }
+
+    /// return true if the item just broke
+    pub fn damage(&mut self, amount: u32) -> bool {
+        if self.is_broken() {
+            return false;
+        }
+
+        self.current = self.current.saturating_sub(amount);
+        self.is_broken()
+    }
+
+    /// return how many points were restored
+    pub fn repair(&mut self, amount: u32) -> u32 {
+        let restored = amount.min(self.missing());
+        self.current += restored;
+        restored
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Weapon {
     pub damage: Damage,
+    pub extra_damages: Vec<Damage>,
     pub calm_down: DeltaTime,
     pub attack: Modifier,
+    /// how many rooms away it can attack, zero is melee only. Only adjacent rooms are
+    /// supported for now
+    pub range: u32,
+    /// prefab of the item consumed from inventory on each attack
+    pub ammo: Option<PrefabId>,
 }
 
 impl Weapon {
     pub fn new() -> Self {
         Weapon {
-            damage: Damage { min: 1, max: 1 },
+            damage: Damage::new(1, 1),
+            extra_damages: vec![],
             calm_down: DeltaTime(1.0),
             attack: Modifier(0),
+            range: 0,
+            ammo: None,
         }
     }
 }
@@ -88,6 +201,7 @@ impl Weapon {
 pub struct Armor {
     pub defense: Modifier,
     pub rd: Rd,
+    pub resistances: Vec<Resistance>,
 }
 
 impl Armor {
@@ -95,6 +209,7 @@ impl Armor {
         Armor {
             defense: Modifier(0),
             rd: 0,
+            resistances: vec![],
         }
     }
 }
@@ -16,28 +16,34 @@ use crate::errors::{AsResult, Error, Result};
 use crate::game::ai;
 use crate::game::ai::{Ai, AiCommand, AiRepo};
 use crate::game::astro_bodies::{AstroBody, AstroBodyKind};
+use crate::game::calendar::{Calendar, DayPeriod};
 use crate::game::config::Config;
 use crate::game::container::Container;
+use crate::game::dialogues::Dialogue;
 use crate::game::domain::{Dir, Modifier};
+use crate::game::equip::EquipSlot;
 use crate::game::extractable::Extractable;
 use crate::game::hire::Hire;
 use crate::game::inventory::Inventory;
-use crate::game::item::{Armor, Item, Weapon, Weight};
+use crate::game::item::{Armor, Durability, Item, Weapon, Weight};
 use crate::game::labels::{Label, NO_LABEL};
 use crate::game::loader::migrations::*;
-use crate::game::market::{Market, MarketTrade};
-use crate::game::mob::{Damage, Mob, MobId};
+use crate::game::market::{Market, MarketAuction, MarketTrade};
+use crate::game::mob::{Damage, DamageType, Mob, MobId, Resistance};
+use crate::game::needs::{Consumable, Needs};
 use crate::game::obj::Objects;
 use crate::game::pos::Pos;
 use crate::game::prices::{Money, Price};
+use crate::game::pvp::PvpRules;
 use crate::game::random_rooms::{
     RandomRoomsCfg, RandomRoomsRepository, RandomRoomsSpawnCfg, RandomRoomsState,
 };
+use crate::game::recipes::Recipe;
 use crate::game::room::Room;
 use crate::game::ships::Ship;
 use crate::game::spawn::{Spawn, SpawnBuilder};
 use crate::game::surfaces::Surface;
-use crate::game::vendors::Vendor;
+use crate::game::vendors::{Vendor, VendorStockItem, VendorStockLimit};
 use crate::game::zone::Zone;
 
 pub mod dto;
@@ -427,7 +433,14 @@ impl Loader {
             mob.attributes.pv.max = mob_data.pv_max;
             mob.attributes.damage.max = mob_data.damage_max;
             mob.attributes.damage.min = mob_data.damage_min;
+            if let Some(damage_type) = &mob_data.damage_type {
+                mob.attributes.damage.kind = DamageType::parse(damage_type.as_str())?;
+            }
+            mob.attributes.resistances = Resistance::parse_list(&mob_data.resistances)?;
             mob.xp = mob_data.xp;
+            mob.wimpy = mob_data.wimpy;
+            mob.needs = mob_data.needs.as_ref().map(Needs::parse);
+            mob.random_loot = mob_data.random_loot;
             container.mobs.add(mob);
 
             if let Some(hire_cost) = mob_data.hire_cost {
@@ -440,6 +453,13 @@ impl Loader {
         if let Some(room_data) = &data.room {
             let mut room = Room::new(obj_id);
             room.can_exit = room_data.can_exit.unwrap_or(false);
+            room.is_bank = room_data.bank.unwrap_or(false);
+            room.outdoor = room_data.outdoor;
+            room.night_desc = room_data.night_desc.clone();
+
+            if let Some(pvp_data) = &room_data.pvp {
+                room.pvp = Some(Loader::parse_pvp_rules(container, references, pvp_data)?);
+            }
 
             if let Some(exists) = &room_data.exits {
                 for i in exists {
@@ -454,7 +474,7 @@ impl Loader {
         }
 
         if let Some(spawn_data) = &data.spawn {
-            let builder = Loader::spawn_data_to_spawn_builder(spawn_data);
+            let builder = Loader::spawn_data_to_spawn_builder(spawn_data)?;
 
             let mut locations_id = vec![];
             if let Some(locations) = &spawn_data.locations_id {
@@ -467,6 +487,14 @@ impl Loader {
 
             let mut spawn = builder.create_spawn(obj_id);
             spawn.locations_id = locations_id;
+            if let Some(zone_id) = spawn_data.zone_id {
+                spawn.zone_id = Some(Loader::get_by_static_id(
+                    &container.objects,
+                    &references,
+                    zone_id,
+                )?);
+            }
+            spawn.radius = spawn_data.radius;
             if let Some(next) = spawn_data.next_spawn {
                 spawn.next = TotalTime(next);
             }
@@ -478,18 +506,34 @@ impl Loader {
 
             item.amount = data_item.amount.unwrap_or(1);
             item.weight = data_item.weight;
+            if let Some(slots) = &data_item.slots {
+                item.slots = EquipSlot::parse_list(slots)?;
+            }
+
+            if let Some(consumable_data) = &data_item.consumable {
+                item.consumable = Some(Consumable::parse(consumable_data)?);
+            }
+
+            item.durability = data_item.durability.as_ref().map(|data| Durability {
+                current: data.current.min(data.max),
+                max: data.max,
+            });
+            item.repair_kit = data_item.repair_kit;
 
             if let Some(flags) = &data_item.flags {
                 item.flags.is_corpse = flags.body.unwrap_or(false);
                 item.flags.is_money = flags.money.unwrap_or(false);
                 item.flags.is_inventory = flags.inventory.unwrap_or(false);
                 item.flags.is_stuck = flags.stuck.unwrap_or(false);
+                item.flags.is_stackable = flags.stackable.unwrap_or(false);
+                item.flags.is_random = flags.random.unwrap_or(false);
             }
 
             if let Some(armor_data) = &data_item.armor {
                 let mut armor = Armor::new();
                 armor.defense = Modifier(armor_data.defense);
                 armor.rd = armor_data.rd;
+                armor.resistances = Resistance::parse_list(&armor_data.resistances)?;
                 item.armor = Some(armor);
             }
 
@@ -497,10 +541,18 @@ impl Loader {
                 let mut weapon = Weapon::new();
                 weapon.attack = Modifier(weapon_data.attack);
                 weapon.calm_down = DeltaTime(weapon_data.calm_down);
-                weapon.damage = Damage {
-                    min: weapon_data.min,
-                    max: weapon_data.max,
-                };
+                weapon.damage = Damage::new(weapon_data.min, weapon_data.max);
+                if let Some(damage_type) = &weapon_data.damage_type {
+                    weapon.damage.kind = DamageType::parse(damage_type.as_str())?;
+                }
+                weapon.extra_damages = weapon_data
+                    .extra_damages
+                    .iter()
+                    .flatten()
+                    .map(Damage::parse)
+                    .collect::<Result<Vec<_>>>()?;
+                weapon.range = weapon_data.range.unwrap_or(0);
+                weapon.ammo = weapon_data.ammo;
                 item.weapon = Some(weapon);
             }
 
@@ -520,11 +572,40 @@ impl Loader {
                     .expect("could not find market id");
                 vendor.market_id = Some(id);
             }
+
+            for stock_data in vendor_data.stock.iter().flatten() {
+                vendor.stock.limits.push(VendorStockLimit {
+                    tag_id: container.tags.get_id(stock_data.tag.as_str()),
+                    max: stock_data.amount as u32,
+                });
+            }
+
+            if let Some(restock_time) = vendor_data.restock_time {
+                vendor.stock.restock_time = DeltaTime(restock_time);
+            }
+
+            vendor.random_stock = vendor_data.random_stock.unwrap_or(0);
+
+            for item_data in vendor_data.stock_items.iter().flatten() {
+                vendor.stock.items.insert(
+                    item_data.prefab_id,
+                    VendorStockItem {
+                        amount: item_data.amount,
+                        max: item_data.max,
+                    },
+                );
+            }
+
             container.vendors.add(vendor);
         }
 
         if let Some(zone_data) = &data.zone {
-            container.zones.add(Zone { id: obj_id }).unwrap();
+            let mut zone = Zone::new(obj_id);
+            zone.outdoor = zone_data.outdoor;
+            if let Some(pvp_data) = &zone_data.pvp {
+                zone.pvp = Some(Loader::parse_pvp_rules(container, references, pvp_data)?);
+            }
+            container.zones.add(zone).unwrap();
 
             if let Some(rr_data) = &zone_data.random_rooms {
                 let entrance_id = Loader::get_by_static_id(
@@ -543,15 +624,15 @@ impl Loader {
                             "locations_id is not supported for spawn in random maps, it should be empty"
                         );
 
-                        let spawn_builder = Loader::spawn_data_to_spawn_builder(&spawn_data.spawn);
-                        RandomRoomsSpawnCfg {
+                        let spawn_builder = Loader::spawn_data_to_spawn_builder(&spawn_data.spawn)?;
+                        Ok(RandomRoomsSpawnCfg {
                             amount: spawn_data.amount,
                             level_min: spawn_data.level_min,
                             level_max: spawn_data.level_max,
                             spawn_builder: spawn_builder,
-                        }
+                        })
                     })
-                    .collect();
+                    .collect::<Result<Vec<_>>>()?;
 
                 container
                     .random_rooms
@@ -567,6 +648,7 @@ impl Loader {
                             width: rr_data.width,
                             height: rr_data.height,
                             spawns,
+                            random_items: rr_data.random_items.unwrap_or(0),
                         },
                         generated: rr_data.generated,
                     })
@@ -591,6 +673,19 @@ impl Loader {
             container
                 .players
                 .create(player_id, player_data.login.clone(), avatar_id);
+
+            if player_data.pvp.unwrap_or(false) {
+                container
+                    .players
+                    .update(player_id, |player| player.pvp = true)?;
+            }
+
+            if let Some(respawn_room_id) = player_data.respawn_room_id {
+                let respawn_room_id = get_ref!(respawn_room_id);
+                container.players.update(player_id, |player| {
+                    player.respawn_room_id = Some(respawn_room_id)
+                })?;
+            }
         }
 
         if let Some(memory) = &data.memory {
@@ -630,6 +725,7 @@ impl Loader {
                         sell_price_mult: trade_data.sell_price_mult,
                     })
                     .collect(),
+                auction: market_data.auction.as_ref().map(MarketAuction::parse),
             };
             container
                 .markets
@@ -637,11 +733,32 @@ impl Loader {
                 .expect("fail to insert market");
         }
 
+        if let Some(recipe_data) = &data.recipe {
+            let recipe = Recipe::parse(&mut container.tags, obj_id, recipe_data)?;
+            container.recipes.add(recipe)?;
+        }
+
+        if let Some(dialogue_data) = &data.dialogue {
+            let dialogue = Dialogue::parse(&mut container.tags, references, obj_id, dialogue_data)?;
+            container.dialogues.add(dialogue)?;
+        }
+
+        if let Some(dialogue_id) = data.dialogue_id {
+            let dialogue_id =
+                Loader::get_by_static_id(&container.objects, &references, dialogue_id)?;
+            container.dialogues.set_talker(obj_id, dialogue_id);
+        }
+
         let loaders: Vec<&mut dyn CanLoad> = vec![
             &mut container.ai,
             &mut container.extractables,
             &mut container.inventories,
             &mut container.travels,
+            &mut container.auctions,
+            &mut container.vaults,
+            &mut container.quests,
+            &mut container.scripts,
+            &mut container.weathers,
         ];
 
         for loader in loaders {
@@ -682,6 +799,10 @@ impl Loader {
                         calm_down: data.item_weapon_calmdown.unwrap(),
                         attack: data.item_weapon_attack.unwrap(),
                         defense: data.item_weapon_defense.unwrap(),
+                        range: None,
+                        ammo: None,
+                        damage_type: None,
+                        extra_damages: None,
                     };
 
                     item.weapon = Some(weapon);
@@ -691,6 +812,7 @@ impl Loader {
                     let armor = ItemArmorData {
                         defense: data.item_armor_defense.unwrap(),
                         rd: data.item_armor_rd.unwrap(),
+                        resistances: None,
                     };
 
                     item.armor = Some(armor);
@@ -829,6 +951,8 @@ impl Loader {
             money_id: container.config.money_id,
             tick: Some(container.time.tick.as_u32()),
             total_time: Some(container.time.total.as_seconds_f64()),
+            random_items: container.config.random_items.clone(),
+            calendar: Some(container.config.calendar.to_data()),
         });
 
         for prefab in container.loader.list_prefabs() {
@@ -889,7 +1013,11 @@ impl Loader {
             obj_data.room = Some(RoomData {
                 // TODO: hack for migration
                 can_exit: if room.can_exit { Some(true) } else { None },
+                bank: if room.is_bank { Some(true) } else { None },
                 exits: Some(exits), // if exits.is_empty() { None } else { Some(exits) },
+                pvp: room.pvp.as_ref().map(|rules| rules.to_data()),
+                outdoor: room.outdoor,
+                night_desc: room.night_desc.clone(),
             });
         }
 
@@ -901,7 +1029,7 @@ impl Loader {
             obj_data.parent = Some(parent.into());
         }
 
-        if let Some(_zone) = container.zones.get(id) {
+        if let Some(zone) = container.zones.get(id) {
             let random_room_data = if let Some(state) = container.random_rooms.get(id) {
                 let random_room_cfg = &state.cfg;
 
@@ -919,8 +1047,20 @@ impl Loader {
                             time_max: i.spawn_builder.delay_max.as_seconds_f32(),
                             // random maps have empty locations
                             locations_id: None,
+                            zone_id: None,
+                            radius: None,
                             next_spawn: None,
                             ai_overwrite: i.spawn_builder.ai_overwrite.clone(),
+                            followers: nonempty_or_none!(i.spawn_builder.followers.clone()),
+                            avoid_players: if i.spawn_builder.avoid_players {
+                                Some(true)
+                            } else {
+                                None
+                            },
+                            period: i
+                                .spawn_builder
+                                .period
+                                .map(|period| period.as_str().to_string()),
                         },
                     })
                     .collect();
@@ -932,6 +1072,11 @@ impl Loader {
                     height: random_room_cfg.height,
                     levels: random_room_cfg.levels,
                     spawns: spanws,
+                    random_items: if random_room_cfg.random_items == 0 {
+                        None
+                    } else {
+                        Some(random_room_cfg.random_items)
+                    },
                     // entrance is always created during load
                     generated: state.generated,
                 })
@@ -941,6 +1086,8 @@ impl Loader {
 
             obj_data.zone = Some(ZoneData {
                 random_rooms: random_room_data,
+                pvp: zone.pvp.as_ref().map(|rules| rules.to_data()),
+                outdoor: zone.outdoor,
             });
         }
 
@@ -965,8 +1112,17 @@ impl Loader {
                 time_min: spawn.delay.min.as_seconds_f32(),
                 time_max: spawn.delay.max.as_seconds_f32(),
                 locations_id: nonempty_or_none!(locations),
+                zone_id: spawn.zone_id.map(|id| id.into()),
+                radius: spawn.radius,
                 next_spawn: Some(spawn.next.as_seconds_f64()),
                 ai_overwrite: spawn.ai_overwrite.clone(),
+                followers: nonempty_or_none!(spawn.followers.clone()),
+                avoid_players: if spawn.avoid_players {
+                    Some(true)
+                } else {
+                    None
+                },
+                period: spawn.period.map(|period| period.as_str().to_string()),
             };
 
             obj_data.spawn = Some(spawn_data);
@@ -988,6 +1144,15 @@ impl Loader {
                 pv_max: mob.attributes.pv.max,
                 xp: mob.xp,
                 hire_cost: hire_cost,
+                wimpy: mob.wimpy,
+                damage_type: if mob.attributes.damage.kind == DamageType::Blunt {
+                    None
+                } else {
+                    Some(mob.attributes.damage.kind.as_str().to_string())
+                },
+                resistances: Resistance::to_data_list(&mob.attributes.resistances),
+                needs: mob.needs.as_ref().map(|needs| needs.to_data()),
+                random_loot: mob.random_loot,
             })
         }
 
@@ -997,6 +1162,8 @@ impl Loader {
                 inventory: true_or_none!(item.flags.is_inventory),
                 stuck: true_or_none!(item.flags.is_stuck),
                 body: true_or_none!(item.flags.is_corpse),
+                stackable: true_or_none!(item.flags.is_stackable),
+                random: true_or_none!(item.flags.is_random),
             };
 
             let weapon = if let Some(weapon) = &item.weapon {
@@ -1006,6 +1173,22 @@ impl Loader {
                     calm_down: weapon.calm_down.as_seconds_f32(),
                     attack: weapon.attack.as_i32(),
                     defense: 0,
+                    range: if weapon.range == 0 {
+                        None
+                    } else {
+                        Some(weapon.range)
+                    },
+                    ammo: weapon.ammo,
+                    damage_type: if weapon.damage.kind == DamageType::Blunt {
+                        None
+                    } else {
+                        Some(weapon.damage.kind.as_str().to_string())
+                    },
+                    extra_damages: if weapon.extra_damages.is_empty() {
+                        None
+                    } else {
+                        Some(weapon.extra_damages.iter().map(|i| i.to_data()).collect())
+                    },
                 })
             } else {
                 None
@@ -1015,6 +1198,7 @@ impl Loader {
                 Some(ItemArmorData {
                     defense: armor.defense.as_i32(),
                     rd: armor.rd,
+                    resistances: Resistance::to_data_list(&armor.resistances),
                 })
             } else {
                 None
@@ -1032,6 +1216,22 @@ impl Loader {
                 weapon: weapon,
                 armor: armor,
                 weight: item.weight,
+                slots: if item.slots.is_empty() {
+                    None
+                } else {
+                    Some(
+                        item.slots
+                            .iter()
+                            .map(|slot| slot.as_str().to_string())
+                            .collect(),
+                    )
+                },
+                consumable: item.consumable.as_ref().map(|c| c.to_data()),
+                durability: item.durability.as_ref().map(|d| DurabilityData {
+                    current: d.current,
+                    max: d.max,
+                }),
+                repair_kit: item.repair_kit,
             });
         }
 
@@ -1044,6 +1244,8 @@ impl Loader {
                 id: StaticId(player.id.0),
                 login: player.login.clone(),
                 avatar_id: player.mob_id.into(),
+                pvp: if player.pvp { Some(true) } else { None },
+                respawn_room_id: player.respawn_room_id.map(|id| id.into()),
             });
         }
 
@@ -1061,9 +1263,56 @@ impl Loader {
         }
 
         if let Some(vendor) = container.vendors.get(id) {
+            let stock = if vendor.stock.is_unlimited() {
+                None
+            } else {
+                Some(
+                    vendor
+                        .stock
+                        .limits
+                        .iter()
+                        .map(|limit| VendorStockData {
+                            tag: container
+                                .tags
+                                .get_str(limit.tag_id)
+                                .expect("fail to resolve tag")
+                                .to_string(),
+                            amount: limit.max as f32,
+                        })
+                        .collect(),
+                )
+            };
+
+            let mut stock_items: Vec<VendorStockItemData> = vendor
+                .stock
+                .items
+                .iter()
+                .map(|(prefab_id, item)| VendorStockItemData {
+                    prefab_id: *prefab_id,
+                    amount: item.amount,
+                    max: item.max,
+                })
+                .collect();
+            stock_items.sort_by_key(|i| i.prefab_id);
+
             obj_data.vendor = Some(VendorData {
                 market_id: vendor.market_id.map(|id| id.into()),
-                stock: None,
+                restock_time: if vendor.has_restock() {
+                    Some(vendor.stock.restock_time.as_seconds_f32())
+                } else {
+                    None
+                },
+                stock,
+                stock_items: if stock_items.is_empty() {
+                    None
+                } else {
+                    Some(stock_items)
+                },
+                random_stock: if vendor.random_stock == 0 {
+                    None
+                } else {
+                    Some(vendor.random_stock)
+                },
             });
         }
 
@@ -1095,9 +1344,22 @@ impl Loader {
                         sell_price_mult: trade.sell_price_mult,
                     })
                     .collect(),
+                auction: market.auction.as_ref().map(|auction| auction.to_data()),
             });
         }
 
+        if let Some(recipe) = container.recipes.get(id) {
+            obj_data.recipe = Some(recipe.to_data(&container.tags));
+        }
+
+        if let Some(dialogue) = container.dialogues.get(id) {
+            obj_data.dialogue = Some(dialogue.to_data(&container.tags));
+        }
+
+        if let Some(dialogue_id) = container.dialogues.get_talker(id) {
+            obj_data.dialogue_id = Some(dialogue_id.into());
+        }
+
         if let Some(inventory) = container.inventories.get(id) {
             obj_data.inventory = Some(InventoryData {
                 max_weight: inventory.max_weight,
@@ -1111,7 +1373,7 @@ impl Loader {
         }
 
         if let Some(ai) = container.ai.get(id) {
-            obj_data.ai = Some(Loader::serialize_ai(ai));
+            obj_data.ai = Some(ai::serialize_ai(ai));
         }
 
         if let Some(extracatble) = container.extractables.get(id) {
@@ -1124,6 +1386,17 @@ impl Loader {
             obj_data.travel = Some(travel.clone());
         }
 
+        let snapshots: Vec<&dyn CanSnapshot> = vec![
+            &container.auctions,
+            &container.vaults,
+            &container.quests,
+            &container.scripts,
+            &container.weathers,
+        ];
+        for snapshot in snapshots {
+            snapshot.snapshot(id, &mut obj_data)?;
+        }
+
         Ok(obj_data)
     }
 
@@ -1148,10 +1421,16 @@ impl Loader {
                 money_id,
                 tick,
                 total_time,
+                ref random_items,
+                ref calendar,
             }) => {
                 container.config.initial_room = Some(ObjId(initial_room.as_u32()));
                 container.config.avatar_id = Some(avatar_mob);
                 container.config.money_id = money_id;
+                container.config.random_items = random_items.clone();
+                if let Some(calendar_data) = calendar {
+                    container.config.calendar = Calendar::parse(calendar_data)?;
+                }
 
                 match (tick, total_time) {
                     (Some(tick), Some(total_time)) => {
@@ -1268,15 +1547,40 @@ impl Loader {
         Ok(loading_ctx)
     }
 
-    fn spawn_data_to_spawn_builder(data: &SpawnData) -> SpawnBuilder {
-        SpawnBuilder {
+    fn parse_pvp_rules(
+        container: &Container,
+        references: &LoadingCtx,
+        data: &PvpData,
+    ) -> Result<PvpRules> {
+        let respawn_room_id = match data.respawn_room_id {
+            Some(static_id) => Some(Loader::get_by_static_id(
+                &container.objects,
+                references,
+                static_id,
+            )?),
+            None => None,
+        };
+
+        PvpRules::parse(data, respawn_room_id)
+    }
+
+    fn spawn_data_to_spawn_builder(data: &SpawnData) -> Result<SpawnBuilder> {
+        let period = match &data.period {
+            Some(value) => Some(DayPeriod::parse(value.as_str())?),
+            None => None,
+        };
+
+        Ok(SpawnBuilder {
             max: data.max,
             delay_min: DeltaTime(data.time_min),
             delay_max: DeltaTime(data.time_max),
             prefab_id: data.prefab_id,
             next: data.next_spawn.as_ref().map(|time| TotalTime(*time)),
             ai_overwrite: data.ai_overwrite.clone(),
-        }
+            followers: data.followers.clone().unwrap_or_default(),
+            avoid_players: data.avoid_players.unwrap_or(false),
+            period,
+        })
     }
 
     pub fn apply_ai_data(ai_repo: &mut AiRepo, mob_id: MobId, ai_data: &AiData) -> Result<()> {
@@ -1284,38 +1588,6 @@ impl Loader {
         ai_repo.add_or_update(ai)?;
         Ok(())
     }
-
-    fn serialize_ai(ai: &Ai) -> AiData {
-        AiData {
-            command_aggressive: if ai.command == AiCommand::Aggressive {
-                Some(true)
-            } else {
-                None
-            },
-            command_follow_and_protect: match ai.command {
-                AiCommand::FollowAndProtect { target_id } => Some(target_id),
-                _ => None,
-            },
-            command_haul: match &ai.command {
-                AiCommand::Hauler { from: _, to: _ } => todo!(),
-                // AiCommand::Hauler { from, to, wares } => Some(AiCommandHaulData {
-                //     from_id: *from,
-                //     to_id: *to,
-                //     targets: wares.clone(),
-                // }),
-                _ => None,
-            },
-            command_aggressive_patrol_home: match &ai.command {
-                AiCommand::AggressivePatrolHome { distance } => {
-                    Some(AiCommandAggressivePatrolHomeData {
-                        distance: *distance,
-                    })
-                }
-                _ => None,
-            },
-            commandable: if ai.commandable { Some(true) } else { None },
-        }
-    }
 }
 
 #[cfg(test)]
@@ -1529,12 +1801,453 @@ mod test {
         data.vendor = Some(VendorData {
             market_id: None,
             stock: None,
+            restock_time: None,
+            stock_items: None,
+            random_stock: None,
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(data, result);
+    }
+
+    #[test]
+    fn test_serialize_vendor_random_stock() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.vendor = Some(VendorData {
+            market_id: None,
+            stock: None,
+            restock_time: Some(30.0),
+            stock_items: None,
+            random_stock: Some(3),
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(data, result);
+    }
+
+    #[test]
+    fn test_serialize_ai_hauler() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.ai = Some(AiData {
+            command_aggressive: None,
+            command_passive: None,
+            command_follow_and_protect: None,
+            command_haul: Some(AiCommandHaulData {
+                from_id: ObjId(0),
+                to_id: ObjId(0),
+                targets: vec![],
+            }),
+            commandable: Some(true),
+            command_aggressive_patrol_home: None,
+            command_roam: None,
+            command_extract: None,
+            home_id: Some(ObjId(0)),
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(data, result);
+    }
+
+    #[test]
+    fn test_serialize_ai_extract() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.ai = Some(AiData {
+            command_aggressive: None,
+            command_passive: None,
+            command_follow_and_protect: None,
+            command_haul: None,
+            commandable: Some(true),
+            command_aggressive_patrol_home: None,
+            command_roam: None,
+            command_extract: Some(ObjId(0)),
+            home_id: None,
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(data, result);
+    }
+
+    #[test]
+    fn test_serialize_market_auction() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.market = Some(MarketData {
+            trades: vec![],
+            auction: Some(MarketAuctionData {
+                listing_fee: Some(5),
+                sale_fee: Some(0.1),
+                duration: Some(60.0),
+            }),
         });
 
         let result = load_and_snapshot(data.clone());
         assert_data_eq(data, result);
     }
 
+    #[test]
+    fn test_serialize_auction_lot() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.auction = Some(AuctionData {
+            owner_id: StaticId(0),
+            listing: Some(AuctionListingData {
+                buyout: 100,
+                bid: Some(AuctionBidData {
+                    bidder_id: StaticId(0),
+                    amount: 10,
+                }),
+                expire: 30.0,
+                sale_fee: 0.1,
+            }),
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(data, result);
+    }
+
+    #[test]
+    fn test_serialize_recipe() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.recipe = Some(RecipeData {
+            inputs: vec![
+                RecipeInputData {
+                    prefab_id: Some(StaticId(1)),
+                    tag: None,
+                    amount: Some(2),
+                },
+                RecipeInputData {
+                    prefab_id: None,
+                    tag: Some("wood".to_string()),
+                    amount: Some(1),
+                },
+            ],
+            outputs: vec![RecipeOutputData {
+                prefab_id: StaticId(2),
+                amount: Some(1),
+            }],
+            time: Some(10.0),
+            workstation: Some("forge".to_string()),
+            tool: None,
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(data, result);
+    }
+
+    #[test]
+    fn test_serialize_bank_room_and_vault() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.room = Some(RoomData {
+            can_exit: None,
+            bank: Some(true),
+            exits: Some(vec![]),
+            pvp: None,
+            outdoor: None,
+            night_desc: None,
+        });
+        data.vault = Some(VaultData {
+            money: 50,
+            storage_id: Some(StaticId(0)),
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(data, result);
+    }
+
+    #[test]
+    fn test_serialize_quest_and_quest_log() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.quest = Some(QuestData {
+            objectives: vec![
+                QuestObjectiveData {
+                    kind: "kill".to_string(),
+                    prefab_id: Some(StaticId(1)),
+                    target_id: None,
+                    amount: Some(3),
+                },
+                QuestObjectiveData {
+                    kind: "reach".to_string(),
+                    prefab_id: None,
+                    target_id: Some(StaticId(0)),
+                    amount: None,
+                },
+            ],
+            reward: Some(QuestRewardData {
+                xp: Some(10),
+                money: Some(5),
+                items: Some(vec![StaticId(2)]),
+            }),
+        });
+        data.quest_log = Some(QuestLogData {
+            active: vec![QuestProgressData {
+                quest_id: StaticId(0),
+                progress: vec![2, 0],
+            }],
+            completed: vec![StaticId(0)],
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(data, result);
+    }
+
+    #[test]
+    fn test_serialize_script() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        let mut script = ScriptData::new();
+        script.on_say = Some(r#"send(actor_id, "hello");"#.to_string());
+        script.on_timer = Some(r#"broadcast(room_id, "tick");"#.to_string());
+        script.timer_interval = Some(5.0);
+        script.next_timer = Some(10.0);
+        script.refs = Some(vec![("me".to_string(), StaticId(0))].into_iter().collect());
+        data.script = Some(script);
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(data, result);
+    }
+
+    #[test]
+    fn test_serialize_weather() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.zone = Some(ZoneData {
+            random_rooms: None,
+            pvp: None,
+            outdoor: Some(true),
+        });
+        data.weather = Some(WeatherData {
+            states: vec!["clear".to_string(), "storm".to_string()],
+            current: Some("storm".to_string()),
+            time_min: 10.0,
+            time_max: 20.0,
+            next_change: Some(15.0),
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(data, result);
+    }
+
+    #[test]
+    fn test_serialize_damage_types_and_resistances() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.mob = Some(MobData {
+            attack: 10,
+            defense: 10,
+            damage_min: 1,
+            damage_max: 2,
+            pv: 10,
+            pv_max: 10,
+            xp: 0,
+            hire_cost: None,
+            wimpy: None,
+            damage_type: Some("pierce".to_string()),
+            resistances: Some(vec![ResistanceData {
+                kind: "fire".to_string(),
+                percent: Some(50),
+                rd: Some(1),
+            }]),
+            needs: None,
+            random_loot: Some(0.5),
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(result, data);
+
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        let mut item = ItemData::new();
+        item.weapon = Some(ItemWeaponData {
+            min: 1,
+            max: 2,
+            calm_down: 1.0,
+            attack: 0,
+            defense: 0,
+            range: None,
+            ammo: None,
+            damage_type: Some("slash".to_string()),
+            extra_damages: Some(vec![DamageData {
+                min: 1,
+                max: 1,
+                kind: "fire".to_string(),
+            }]),
+        });
+        item.armor = Some(ItemArmorData {
+            defense: 1,
+            rd: 1,
+            resistances: Some(vec![ResistanceData {
+                kind: "energy".to_string(),
+                percent: None,
+                rd: Some(2),
+            }]),
+        });
+        item.flags = Some(ItemFlagsData::new());
+        data.item = Some(item);
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(result, data);
+    }
+
+    #[test]
+    fn test_serialize_needs_and_consumable() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.mob = Some(MobData {
+            attack: 10,
+            defense: 10,
+            damage_min: 1,
+            damage_max: 2,
+            pv: 10,
+            pv_max: 10,
+            xp: 0,
+            hire_cost: None,
+            wimpy: None,
+            damage_type: None,
+            resistances: None,
+            needs: Some(MobNeedsData {
+                hunger: 50.0,
+                thirst: 20.0,
+            }),
+            random_loot: None,
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(result, data);
+
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        let mut item = ItemData::new();
+        item.consumable = Some(ConsumableData {
+            kind: "potion".to_string(),
+            heal: Some(10),
+            hunger: None,
+            thirst: Some(5.0),
+            effect: Some(EffectData {
+                kind: "attack".to_string(),
+                amount: 2,
+                duration: 30.0,
+            }),
+        });
+        item.flags = Some(ItemFlagsData::new());
+        data.item = Some(item);
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(result, data);
+    }
+
+    #[test]
+    fn test_serialize_item_durability_and_repair_kit() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        let mut item = ItemData::new();
+        item.armor = Some(ItemArmorData {
+            defense: 2,
+            rd: 1,
+            resistances: None,
+        });
+        item.durability = Some(DurabilityData {
+            current: 5,
+            max: 20,
+        });
+        item.flags = Some(ItemFlagsData::new());
+        data.item = Some(item);
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(result, data);
+
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        let mut item = ItemData::new();
+        item.repair_kit = Some(10);
+        item.flags = Some(ItemFlagsData::new());
+        data.item = Some(item);
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(result, data);
+    }
+
+    #[test]
+    fn test_serialize_zone_and_room_pvp() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.zone = Some(ZoneData {
+            random_rooms: None,
+            pvp: Some(PvpData {
+                policy: Some("arena".to_string()),
+                respawn_room_id: Some(StaticId(0)),
+                keep_items: Some(true),
+            }),
+            outdoor: None,
+        });
+        data.room = Some(RoomData {
+            can_exit: None,
+            bank: None,
+            exits: None,
+            pvp: Some(PvpData {
+                policy: Some("safe".to_string()),
+                respawn_room_id: None,
+                keep_items: None,
+            }),
+            outdoor: None,
+            night_desc: None,
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(result, data);
+    }
+
+    #[test]
+    fn test_serialize_player_respawn_room() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.room = Some(RoomData {
+            can_exit: None,
+            bank: None,
+            exits: None,
+            pvp: None,
+            outdoor: None,
+            night_desc: None,
+        });
+        data.player = Some(PlayerData {
+            id: StaticId(0),
+            login: "player".to_string(),
+            avatar_id: StaticId(0),
+            pvp: Some(true),
+            respawn_room_id: Some(StaticId(0)),
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(result, data);
+    }
+
+    #[test]
+    fn test_serialize_zone_and_room_outdoor() {
+        let mut data = ObjData::new();
+        data.id = Some(StaticId(0));
+        data.zone = Some(ZoneData {
+            random_rooms: None,
+            pvp: None,
+            outdoor: Some(true),
+        });
+        data.room = Some(RoomData {
+            can_exit: None,
+            bank: None,
+            exits: None,
+            pvp: None,
+            outdoor: Some(false),
+            night_desc: Some("a dark room".to_string()),
+        });
+
+        let result = load_and_snapshot(data.clone());
+        assert_data_eq(result, data);
+    }
+
     #[test]
     fn test_find_prefab_by_tags_or() {
         let mut loader = Loader::new();
@@ -37,10 +37,24 @@ pub struct RoomExitData {
     pub to: StaticId,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PvpData {
+    /// disabled, safe, allowed or arena
+    pub policy: Option<String>,
+    pub respawn_room_id: Option<StaticId>,
+    pub keep_items: Option<bool>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct RoomData {
     pub can_exit: Option<bool>,
+    pub bank: Option<bool>,
     pub exits: Option<Vec<RoomExitData>>,
+    pub pvp: Option<PvpData>,
+    /// overwrite zone outdoor flag
+    pub outdoor: Option<bool>,
+    /// description used by outdoor rooms during the night
+    pub night_desc: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -63,6 +77,33 @@ pub struct MobData {
     pub pv_max: u32,
     pub xp: u32,
     pub hire_cost: Option<u32>,
+    pub wimpy: Option<u32>,
+    pub damage_type: Option<String>,
+    pub resistances: Option<Vec<ResistanceData>>,
+    /// mobs without needs never get hungry or thirsty
+    pub needs: Option<MobNeedsData>,
+    /// probability to drop a random generated item when killed
+    pub random_loot: Option<f32>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MobNeedsData {
+    pub hunger: f32,
+    pub thirst: f32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DamageData {
+    pub min: u32,
+    pub max: u32,
+    pub kind: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ResistanceData {
+    pub kind: String,
+    pub percent: Option<u32>,
+    pub rd: Option<u32>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -74,6 +115,8 @@ pub struct ItemFlagsData {
     pub inventory: Option<bool>,
     pub stuck: Option<bool>,
     pub body: Option<bool>,
+    pub stackable: Option<bool>,
+    pub random: Option<bool>,
 }
 
 impl ItemFlagsData {
@@ -83,6 +126,8 @@ impl ItemFlagsData {
             inventory: None,
             stuck: None,
             body: None,
+            stackable: None,
+            random: None,
         }
     }
 }
@@ -94,12 +139,17 @@ pub struct ItemWeaponData {
     pub calm_down: f32,
     pub attack: i32,
     pub defense: i32,
+    pub range: Option<u32>,
+    pub ammo: Option<StaticId>,
+    pub damage_type: Option<String>,
+    pub extra_damages: Option<Vec<DamageData>>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ItemArmorData {
     pub defense: i32,
     pub rd: u32,
+    pub resistances: Option<Vec<ResistanceData>>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -114,6 +164,37 @@ pub struct ItemData {
     pub weapon: Option<ItemWeaponData>,
     pub armor: Option<ItemArmorData>,
     pub weight: Option<f32>,
+    /// equip slots, like 'head', 'ring' or 'two-handed'
+    pub slots: Option<Vec<String>>,
+    pub consumable: Option<ConsumableData>,
+    pub durability: Option<DurabilityData>,
+    /// durability points restored when used as repair kit
+    pub repair_kit: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DurabilityData {
+    pub current: u32,
+    pub max: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EffectData {
+    /// attack or defense
+    pub kind: String,
+    pub amount: i32,
+    /// duration in seconds
+    pub duration: f32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ConsumableData {
+    /// food, drink or potion
+    pub kind: String,
+    pub heal: Option<u32>,
+    pub hunger: Option<f32>,
+    pub thirst: Option<f32>,
+    pub effect: Option<EffectData>,
 }
 
 impl ItemData {
@@ -124,6 +205,10 @@ impl ItemData {
             weapon: None,
             armor: None,
             weight: None,
+            slots: None,
+            consumable: None,
+            durability: None,
+            repair_kit: None,
         }
     }
 }
@@ -179,9 +264,231 @@ pub struct MarketTradeData {
     pub sell_price_mult: Option<f32>,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MarketAuctionData {
+    /// paid when a item is listed, never refunded
+    pub listing_fee: Option<u32>,
+    /// percentage of the sale price kept by the market
+    pub sale_fee: Option<f32>,
+    /// how long until a listing expire in seconds
+    pub duration: Option<f32>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct MarketData {
     pub trades: Vec<MarketTradeData>,
+    /// markets with auction enable auction house in rooms with its vendors
+    pub auction: Option<MarketAuctionData>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct AuctionBidData {
+    pub bidder_id: StaticId,
+    pub amount: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct AuctionListingData {
+    pub buyout: u32,
+    pub bid: Option<AuctionBidData>,
+    pub expire: f64,
+    pub sale_fee: f32,
+}
+
+/// Object held by the auction house, or listed or waiting to be collected by the owner
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct AuctionData {
+    pub owner_id: StaticId,
+    pub listing: Option<AuctionListingData>,
+}
+
+/// Input consumed by a recipe, defined by prefab or by tag
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RecipeInputData {
+    pub prefab_id: Option<StaticId>,
+    pub tag: Option<String>,
+    pub amount: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RecipeOutputData {
+    pub prefab_id: StaticId,
+    pub amount: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RecipeData {
+    pub inputs: Vec<RecipeInputData>,
+    pub outputs: Vec<RecipeOutputData>,
+    /// time in seconds to craft
+    pub time: Option<f32>,
+    /// tag of a object required in the room
+    pub workstation: Option<String>,
+    /// tag of a item required in the inventory
+    pub tool: Option<String>,
+}
+
+/// kill, deliver, reach or extract
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct QuestObjectiveData {
+    pub kind: String,
+    /// prefab to kill, deliver or extract
+    pub prefab_id: Option<StaticId>,
+    /// who receive the delivery or the room to reach
+    pub target_id: Option<StaticId>,
+    pub amount: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct QuestRewardData {
+    pub xp: Option<u32>,
+    pub money: Option<u32>,
+    pub items: Option<Vec<StaticId>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct QuestData {
+    pub objectives: Vec<QuestObjectiveData>,
+    pub reward: Option<QuestRewardData>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct QuestProgressData {
+    pub quest_id: StaticId,
+    /// current amount for each objective
+    pub progress: Vec<u32>,
+}
+
+/// Quests of a player
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct QuestLogData {
+    pub active: Vec<QuestProgressData>,
+    pub completed: Vec<StaticId>,
+}
+
+/// Weather simulated for all rooms inside a zone or astro body
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct WeatherData {
+    /// possible weathers, repeat a value to increase its chance
+    pub states: Vec<String>,
+    pub current: Option<String>,
+    /// seconds between each weather change
+    pub time_min: f32,
+    pub time_max: f32,
+    pub next_change: Option<f64>,
+}
+
+/// Rhai source code executed on each hook
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ScriptData {
+    /// room script, a mob enter in the room
+    pub on_enter: Option<String>,
+    /// room script, a mob leave the room
+    pub on_leave: Option<String>,
+    /// room or objects in the room, someone say something
+    pub on_say: Option<String>,
+    /// item script, a mob use the item
+    pub on_use: Option<String>,
+    /// mob script, the mob was killed
+    pub on_death: Option<String>,
+    /// executed every timer_interval seconds
+    pub on_timer: Option<String>,
+    pub timer_interval: Option<f32>,
+    pub next_timer: Option<f64>,
+    /// objects available to the script as constants with the given name
+    pub refs: Option<BTreeMap<String, StaticId>>,
+}
+
+impl ScriptData {
+    pub fn new() -> Self {
+        ScriptData {
+            on_enter: None,
+            on_leave: None,
+            on_say: None,
+            on_use: None,
+            on_death: None,
+            on_timer: None,
+            timer_interval: None,
+            next_timer: None,
+            refs: None,
+        }
+    }
+}
+
+/// Only one field must be defined
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct DialogueConditionData {
+    /// the mob must have the tag
+    pub tag: Option<String>,
+    /// the mob must not have the tag
+    pub not_tag: Option<String>,
+    /// the mob must have at least this amount of money
+    pub money: Option<u32>,
+    /// the mob must carry a item of this prefab
+    pub item: Option<StaticId>,
+}
+
+impl DialogueConditionData {
+    pub fn new() -> Self {
+        DialogueConditionData {
+            tag: None,
+            not_tag: None,
+            money: None,
+            item: None,
+        }
+    }
+}
+
+/// Only one field must be defined
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct DialogueEffectData {
+    pub give_item: Option<StaticId>,
+    pub give_money: Option<u32>,
+    pub take_money: Option<u32>,
+    /// add a tag to the mob
+    pub set_flag: Option<String>,
+    pub start_quest: Option<StaticId>,
+}
+
+impl DialogueEffectData {
+    pub fn new() -> Self {
+        DialogueEffectData {
+            give_item: None,
+            give_money: None,
+            take_money: None,
+            set_flag: None,
+            start_quest: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct DialogueOptionData {
+    pub text: String,
+    /// node to move, conversation ends if not defined
+    pub next: Option<String>,
+    pub conditions: Option<Vec<DialogueConditionData>>,
+    pub effects: Option<Vec<DialogueEffectData>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct DialogueNodeData {
+    pub text: String,
+    pub options: Option<Vec<DialogueOptionData>>,
+}
+
+/// Dialogue tree, npcs reference it by dialogue_id
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct DialogueData {
+    pub start: String,
+    pub nodes: BTreeMap<String, DialogueNodeData>,
+}
+
+/// Bank vault of a player, stored items are children of the storage object
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct VaultData {
+    pub money: u32,
+    pub storage_id: Option<StaticId>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -190,10 +497,23 @@ pub struct VendorStockData {
     pub amount: f32,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct VendorStockItemData {
+    pub prefab_id: StaticId,
+    pub amount: u32,
+    pub max: u32,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct VendorData {
     pub market_id: Option<StaticId>,
+    /// max amount of each prefab with the tag, vendors without stock have unlimited items
     pub stock: Option<Vec<VendorStockData>>,
+    pub restock_time: Option<f32>,
+    /// current stock of items that are not full
+    pub stock_items: Option<Vec<VendorStockItemData>>,
+    /// amount of random generated items kept in the vendor inventory to sell
+    pub random_stock: Option<u32>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -212,12 +532,17 @@ pub struct RandomRoomsData {
     pub height: u32,
     pub levels: u32,
     pub spawns: Vec<RandomRoomsSpawnData>,
+    /// amount of random generated items placed in each level
+    pub random_items: Option<u32>,
     pub generated: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ZoneData {
     pub random_rooms: Option<RandomRoomsData>,
+    pub pvp: Option<PvpData>,
+    /// rooms inside the zone can see the sky
+    pub outdoor: Option<bool>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -260,6 +585,16 @@ pub struct ObjData {
     pub ai: Option<AiData>,
     pub extractable: Option<ExtractableData>,
     pub travel: Option<Travel>,
+    pub auction: Option<AuctionData>,
+    pub vault: Option<VaultData>,
+    pub recipe: Option<RecipeData>,
+    pub dialogue: Option<DialogueData>,
+    /// dialogue used when someone talk with this obj
+    pub dialogue_id: Option<StaticId>,
+    pub quest: Option<QuestData>,
+    pub quest_log: Option<QuestLogData>,
+    pub script: Option<ScriptData>,
+    pub weather: Option<WeatherData>,
 }
 
 impl ObjData {
@@ -291,6 +626,15 @@ impl ObjData {
             ai: None,
             extractable: None,
             travel: None,
+            auction: None,
+            vault: None,
+            recipe: None,
+            dialogue: None,
+            dialogue_id: None,
+            quest: None,
+            quest_log: None,
+            script: None,
+            weather: None,
         }
     }
 
@@ -306,6 +650,19 @@ pub struct CfgData {
     pub money_id: Option<StaticId>,
     pub tick: Option<u32>,
     pub total_time: Option<f64>,
+    /// materials and items used to generate random items
+    pub random_items: Option<random_stuff::Conf>,
+    pub calendar: Option<CalendarData>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CalendarData {
+    pub seconds_per_hour: Option<f32>,
+    pub hours_per_day: Option<u32>,
+    pub days_per_season: Option<u32>,
+    pub seasons: Option<Vec<String>>,
+    pub sunrise: Option<u32>,
+    pub sunset: Option<u32>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -315,8 +672,18 @@ pub struct SpawnData {
     pub time_min: f32,
     pub time_max: f32,
     pub locations_id: Option<Vec<StaticId>>,
+    /// spawn in any room of the zone
+    pub zone_id: Option<StaticId>,
+    /// spawn in any room in this distance of the spawn room
+    pub radius: Option<u32>,
     pub next_spawn: Option<f64>,
     pub ai_overwrite: Option<AiData>,
+    /// prefabs spawned with the mob that follow it
+    pub followers: Option<Vec<StaticId>>,
+    /// do not spawn in rooms with players
+    pub avoid_players: Option<bool>,
+    /// only spawn during the "day" or "night"
+    pub period: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -340,6 +707,9 @@ pub struct PlayerData {
     pub id: StaticId,
     pub login: String,
     pub avatar_id: StaticId,
+    pub pvp: Option<bool>,
+    /// where the next avatar will be respawn
+    pub respawn_room_id: Option<StaticId>,
 }
 
 // TODO: rename to snapshot data?
@@ -363,13 +733,23 @@ pub struct AiCommandAggressivePatrolHomeData {
     pub distance: u32,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AiCommandRoamData {
+    pub distance: u32,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AiData {
     pub command_aggressive: Option<bool>,
+    pub command_passive: Option<bool>,
     pub command_follow_and_protect: Option<ObjId>,
     pub command_haul: Option<AiCommandHaulData>,
     pub commandable: Option<bool>,
     pub command_aggressive_patrol_home: Option<AiCommandAggressivePatrolHomeData>,
+    pub command_roam: Option<AiCommandRoamData>,
+    pub command_extract: Option<ObjId>,
+    /// room where the mob patrol around, when empty the first location is used
+    pub home_id: Option<ObjId>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
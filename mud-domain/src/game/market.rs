@@ -1,6 +1,8 @@
 use crate::errors::{Error, Result};
+use crate::game::loader::dto::MarketAuctionData;
+use crate::game::prices::Money;
 use crate::game::tags::TagId;
-use commons::ObjId;
+use commons::{DeltaTime, ObjId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -15,15 +17,57 @@ pub struct MarketTrade {
     // pub change_per_cycle: Option<f32>,
 }
 
+pub const AUCTION_DURATION: DeltaTime = DeltaTime(3600.0);
+
+/// Fees charged by auction houses that use this market
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MarketAuction {
+    pub listing_fee: Money,
+    pub sale_fee: f32,
+    pub duration: DeltaTime,
+}
+
+impl MarketAuction {
+    pub fn new() -> Self {
+        MarketAuction {
+            listing_fee: Money(0),
+            sale_fee: 0.0,
+            duration: AUCTION_DURATION,
+        }
+    }
+
+    pub fn parse(data: &MarketAuctionData) -> Self {
+        let default = MarketAuction::new();
+        MarketAuction {
+            listing_fee: data.listing_fee.map(Money).unwrap_or(default.listing_fee),
+            sale_fee: data.sale_fee.unwrap_or(default.sale_fee),
+            duration: data.duration.map(DeltaTime).unwrap_or(default.duration),
+        }
+    }
+
+    pub fn to_data(&self) -> MarketAuctionData {
+        MarketAuctionData {
+            listing_fee: Some(self.listing_fee.as_u32()),
+            sale_fee: Some(self.sale_fee),
+            duration: Some(self.duration.as_seconds_f32()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Market {
     pub id: ObjId,
     pub trades: Vec<MarketTrade>,
+    pub auction: Option<MarketAuction>,
 }
 
 impl Market {
     pub fn new(id: ObjId) -> Self {
-        Market { id, trades: vec![] }
+        Market {
+            id,
+            trades: vec![],
+            auction: None,
+        }
     }
 }
 
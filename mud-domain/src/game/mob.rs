@@ -7,16 +7,20 @@ use crate::errors::Error::InvalidStateFailure;
 use crate::errors::{AsResult, Error, Result, ResultError};
 use crate::game::domain::{Attribute, Rd};
 use crate::game::inventory_service;
-use crate::game::item::ItemPrefabId;
+use crate::game::item::{ItemPrefabId, Weapon};
 use crate::game::labels::Labels;
+use crate::game::loader::dto::{DamageData, ResistanceData};
 use crate::game::location;
 use crate::game::location::Locations;
+use crate::game::needs::{EffectKind, MobEffect, Needs, NEEDS_EMPTY_PENALTY};
 use crate::game::outputs::Outputs;
 use crate::game::room::RoomId;
 use crate::game::{avatars, combat, comm};
 use serde::{Deserialize, Serialize};
 
 pub const EXTRACT_TIME: DeltaTime = DeltaTime(5.0);
+/// Time between each step when walking
+pub const WALK_STEP_TIME: DeltaTime = DeltaTime(1.0);
 
 pub type MobId = ObjId;
 pub type Xp = u32;
@@ -25,8 +29,19 @@ pub type Xp = u32;
 #[derive(Clone, Debug, Copy, Deserialize, Serialize, PartialEq)]
 pub enum MobCommand {
     None,
-    Kill { target_id: MobId },
-    Extract { target_id: ObjId },
+    Kill {
+        target_id: MobId,
+    },
+    Extract {
+        target_id: ObjId,
+    },
+    Craft {
+        recipe_id: ObjId,
+    },
+    /// walk step by step until reach the room
+    Walk {
+        target_id: RoomId,
+    },
 }
 
 impl MobCommand {
@@ -46,12 +61,136 @@ pub enum MobAction {
     Combat,
     Resting,
     Extracting,
+    Crafting,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum DamageType {
+    Slash,
+    Pierce,
+    Blunt,
+    Fire,
+    Energy,
+}
+
+impl DamageType {
+    pub fn parse(value: &str) -> Result<DamageType> {
+        match value {
+            "slash" => Ok(DamageType::Slash),
+            "pierce" => Ok(DamageType::Pierce),
+            "blunt" => Ok(DamageType::Blunt),
+            "fire" => Ok(DamageType::Fire),
+            "energy" => Ok(DamageType::Energy),
+            other => Err(Error::InvalidArgumentFailureStr(format!(
+                "invalid damage type {:?}",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DamageType::Slash => "slash",
+            DamageType::Pierce => "pierce",
+            DamageType::Blunt => "blunt",
+            DamageType::Fire => "fire",
+            DamageType::Energy => "energy",
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Damage {
     pub min: u32,
     pub max: u32,
+    pub kind: DamageType,
+}
+
+impl Damage {
+    pub fn new(min: u32, max: u32) -> Self {
+        Damage {
+            min,
+            max,
+            kind: DamageType::Blunt,
+        }
+    }
+
+    pub fn parse(data: &DamageData) -> Result<Self> {
+        Ok(Damage {
+            min: data.min,
+            max: data.max,
+            kind: DamageType::parse(data.kind.as_str())?,
+        })
+    }
+
+    pub fn to_data(&self) -> DamageData {
+        DamageData {
+            min: self.min,
+            max: self.max,
+            kind: self.kind.as_str().to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Resistance {
+    pub kind: DamageType,
+    /// percentage of the damage absorbed, from 0 to 100
+    pub percent: u32,
+    /// damage reduction applied after the percentage
+    pub rd: Rd,
+}
+
+impl Resistance {
+    pub fn parse(data: &ResistanceData) -> Result<Self> {
+        Ok(Resistance {
+            kind: DamageType::parse(data.kind.as_str())?,
+            percent: data.percent.unwrap_or(0),
+            rd: data.rd.unwrap_or(0),
+        })
+    }
+
+    pub fn parse_list(data: &Option<Vec<ResistanceData>>) -> Result<Vec<Self>> {
+        data.iter().flatten().map(Resistance::parse).collect()
+    }
+
+    pub fn to_data(&self) -> ResistanceData {
+        ResistanceData {
+            kind: self.kind.as_str().to_string(),
+            percent: if self.percent == 0 {
+                None
+            } else {
+                Some(self.percent)
+            },
+            rd: if self.rd == 0 { None } else { Some(self.rd) },
+        }
+    }
+
+    pub fn to_data_list(list: &Vec<Resistance>) -> Option<Vec<ResistanceData>> {
+        if list.is_empty() {
+            None
+        } else {
+            Some(list.iter().map(|i| i.to_data()).collect())
+        }
+    }
+
+    /// return how much of the damage is delivered
+    pub fn apply(&self, damage: u32) -> u32 {
+        let percent = self.percent.min(100);
+        let absorbed = damage * percent / 100;
+        (damage - absorbed).saturating_sub(self.rd)
+    }
+}
+
+/// merge a resistance into the list, summing values of same kind
+pub fn add_resistance(resistances: &mut Vec<Resistance>, resistance: &Resistance) {
+    match resistances.iter_mut().find(|i| i.kind == resistance.kind) {
+        Some(current) => {
+            current.percent = (current.percent + resistance.percent).min(100);
+            current.rd += resistance.rd;
+        }
+        None => resistances.push(resistance.clone()),
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -72,6 +211,9 @@ pub struct Attributes {
     pub attack: Attribute,
     pub defense: Attribute,
     pub damage: Damage,
+    /// additional damages of other types, like a flaming sword
+    pub extra_damages: Vec<Damage>,
+    pub resistances: Vec<Resistance>,
     pub pv: Pv,
     pub attack_calm_down: DeltaTime,
     pub rd: Rd,
@@ -82,7 +224,9 @@ impl Attributes {
         Attributes {
             attack: 10,
             defense: 10,
-            damage: Damage { min: 1, max: 1 },
+            damage: Damage::new(1, 1),
+            extra_damages: vec![],
+            resistances: vec![],
             pv: Pv {
                 current: 10,
                 max: 10,
@@ -101,6 +245,10 @@ pub struct MobState {
     // after this total time can heal
     pub heal_calm_down: TotalTime,
     pub extract_calm_down: TotalTime,
+    // when current craft will be complete
+    pub craft_complete: TotalTime,
+    // after this total time can do next walk step
+    pub walk_calm_down: TotalTime,
     pub action: MobAction,
 }
 
@@ -110,17 +258,29 @@ impl MobState {
             attack_calm_down: TotalTime(0.0),
             heal_calm_down: TotalTime(0.0),
             extract_calm_down: TotalTime(0.0),
+            craft_complete: TotalTime(0.0),
+            walk_calm_down: TotalTime(0.0),
             action: MobAction::None,
         }
     }
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DamageResult {
+    pub kind: DamageType,
+    pub total: u32,
+    /// how much damage was really caused after resistances
+    pub deliver: u32,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AttackResult {
     pub success: bool,
     pub damage_total: u32,
     /// how much damage was really caused
     pub damage_deliver: u32,
+    /// damage breakdown per type
+    pub damages: Vec<DamageResult>,
     pub attack_value: u32,
     pub defense_value: u32,
     pub attack_dice: u32,
@@ -134,6 +294,7 @@ impl AttackResult {
             success: false,
             damage_total: 0,
             damage_deliver: 0,
+            damages: vec![],
             attack_value: attack,
             defense_value: defense,
             attack_dice: 0,
@@ -152,6 +313,13 @@ pub struct Mob {
     pub xp: Xp,
     pub state: MobState,
     pub followers: Vec<ObjId>,
+    /// automatically flee from combat when pv drops to this value
+    pub wimpy: Option<u32>,
+    /// hunger and thirst, mobs without needs never starve
+    pub needs: Option<Needs>,
+    pub effects: Vec<MobEffect>,
+    /// probability to drop a random generated item when killed
+    pub random_loot: Option<f32>,
 }
 
 impl Mob {
@@ -164,6 +332,10 @@ impl Mob {
             xp: 0,
             state: MobState::new(),
             followers: Default::default(),
+            wimpy: None,
+            needs: None,
+            effects: vec![],
+            random_loot: None,
         }
     }
 
@@ -185,6 +357,13 @@ impl Mob {
         self.state.action == MobAction::Combat
     }
 
+    pub fn is_wimpy(&self) -> bool {
+        match self.wimpy {
+            Some(wimpy) => self.attributes.pv.current <= wimpy as i32,
+            None => false,
+        }
+    }
+
     pub fn is_resting(&self) -> bool {
         self.state.action == MobAction::Resting
     }
@@ -210,6 +389,27 @@ impl Mob {
         }
     }
 
+    pub fn set_action_craft(&mut self, recipe_id: ObjId, complete: TotalTime) -> Result<()> {
+        if !self.is_idle() {
+            Err(InvalidStateFailure)
+        } else {
+            self.command = MobCommand::Craft { recipe_id };
+            self.state.action = MobAction::Crafting;
+            self.state.craft_complete = complete;
+            Ok(())
+        }
+    }
+
+    /// walking do not change the mob action, any other action cancel it
+    pub fn set_action_walk(&mut self, target_id: RoomId) -> Result<()> {
+        if !self.is_idle() {
+            Err(InvalidStateFailure)
+        } else {
+            self.command = MobCommand::Walk { target_id };
+            Ok(())
+        }
+    }
+
     pub fn stop_rest(&mut self) -> Result<()> {
         match self.state.action {
             MobAction::Resting => {
@@ -312,6 +512,9 @@ impl MobRepository {
 
     pub fn add_follower(&mut self, id: MobId, follower_id: MobId) -> Result<()> {
         let mob = self.get_mut(id).as_result_str("mob not found")?;
+        if mob.followers.contains(&follower_id) {
+            return Ok(());
+        }
         log::info!("{:?} adding follower {:?}", id, follower_id);
         mob.followers.push(follower_id);
         Ok(())
@@ -340,11 +543,22 @@ pub fn search_mobs_at(
 
 /// get mob attributes summing items
 pub fn get_attributes_with_bonus(container: &Container, mob_id: MobId) -> Result<Attributes> {
-    let mut attributes = container
-        .mobs
-        .get(mob_id)
-        .ok_or(Error::NotFoundFailure)
-        .map(|mob| mob.attributes.clone())?;
+    let mob = container.mobs.get(mob_id).ok_or(Error::NotFoundFailure)?;
+    let mut attributes = mob.attributes.clone();
+
+    for effect in &mob.effects {
+        let value = match effect.kind {
+            EffectKind::Attack => &mut attributes.attack,
+            EffectKind::Defense => &mut attributes.defense,
+        };
+        *value = (*value as i32 + effect.amount).max(0) as u32;
+    }
+
+    if let Some(needs) = &mob.needs {
+        let penalty = needs.count_empty() * NEEDS_EMPTY_PENALTY;
+        attributes.attack = attributes.attack.saturating_sub(penalty);
+        attributes.defense = attributes.defense.saturating_sub(penalty);
+    }
 
     container
         .equips
@@ -352,15 +566,35 @@ pub fn get_attributes_with_bonus(container: &Container, mob_id: MobId) -> Result
         .into_iter()
         .map(|item_id| container.items.get(item_id).unwrap())
         .for_each(|item| {
+            // worn items provide reduced bonus, broken ones provide nothing
+            let mult = item
+                .durability
+                .as_ref()
+                .map(|durability| durability.bonus_mult())
+                .unwrap_or(1.0);
+
+            if mult <= 0.0 {
+                return;
+            }
+
+            let scale = |value: u32| (value as f32 * mult) as u32;
+
             if let Some(armor) = item.armor.as_ref() {
-                attributes.rd += armor.rd;
-                attributes.defense = armor.defense.apply(attributes.defense);
+                attributes.rd += scale(armor.rd);
+                attributes.defense = armor.defense.scale(mult).apply(attributes.defense);
+                for resistance in &armor.resistances {
+                    add_resistance(&mut attributes.resistances, resistance);
+                }
             }
 
             if let Some(weapon) = item.weapon.as_ref() {
-                attributes.attack = weapon.attack.apply(attributes.attack);
-                attributes.damage.max += weapon.damage.max;
-                attributes.damage.min += weapon.damage.min;
+                attributes.attack = weapon.attack.scale(mult).apply(attributes.attack);
+                attributes.damage.max += scale(weapon.damage.max);
+                attributes.damage.min += scale(weapon.damage.min);
+                attributes.damage.kind = weapon.damage.kind;
+                attributes
+                    .extra_damages
+                    .extend(weapon.extra_damages.iter().cloned());
                 attributes.attack_calm_down = weapon.calm_down;
             }
         });
@@ -368,14 +602,29 @@ pub fn get_attributes_with_bonus(container: &Container, mob_id: MobId) -> Result
     Ok(attributes)
 }
 
+/// get the first equipped weapon
+pub fn get_weapon(container: &Container, mob_id: MobId) -> Option<&Weapon> {
+    container
+        .equips
+        .get(mob_id)
+        .into_iter()
+        .flat_map(|item_id| container.items.get(item_id))
+        .flat_map(|item| item.weapon.as_ref())
+        .next()
+}
+
 pub fn system_run(container: &mut Container) {
     let mut attacks = vec![];
     let mut extracts = vec![];
+    let mut crafts = vec![];
+    let mut walks = vec![];
 
     for mob in container.mobs.list() {
         match mob.command {
             MobCommand::Kill { target_id } => attacks.push((mob.id, target_id)),
             MobCommand::Extract { target_id } => extracts.push((mob.id, target_id)),
+            MobCommand::Craft { recipe_id } => crafts.push((mob.id, recipe_id)),
+            MobCommand::Walk { target_id } => walks.push((mob.id, target_id)),
             _ => {}
         };
     }
@@ -388,6 +637,22 @@ pub fn system_run(container: &mut Container) {
         };
     }
 
+    // execute crafts
+    for (mob_id, recipe_id) in &crafts {
+        match super::actions_craft::tick_craft(container, *mob_id, *recipe_id) {
+            Err(err) => log::warn!("{:?} fail to execute craft: {:?}", mob_id, err),
+            _ => {}
+        };
+    }
+
+    // execute walks
+    for (mob_id, target_id) in &walks {
+        match super::pathfinding::tick_walk(container, *mob_id, *target_id) {
+            Err(err) => log::warn!("{:?} fail to execute walk: {:?}", mob_id, err),
+            _ => {}
+        };
+    }
+
     // execute attacks
     for (mob_id, target_id) in &attacks {
         match super::combat::tick_attack(container, *mob_id, *target_id) {
@@ -0,0 +1,277 @@
+use crate::errors::{Error, Result};
+use crate::game::loader::dto::{ConsumableData, EffectData, MobNeedsData};
+use commons::{DeltaTime, TotalTime};
+use serde::{Deserialize, Serialize};
+
+pub const NEEDS_MAX: f32 = 100.0;
+/// below this value mob start to feel hungry or thirsty
+pub const NEEDS_LOW: f32 = 25.0;
+/// how much hunger is lost per second
+pub const HUNGER_DRAIN: f32 = 0.01;
+/// how much thirst is lost per second
+pub const THIRST_DRAIN: f32 = 0.02;
+/// attack and defense penalty for each need that is empty
+pub const NEEDS_EMPTY_PENALTY: u32 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum NeedKind {
+    Hunger,
+    Thirst,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum NeedLevel {
+    Ok,
+    Low,
+    Empty,
+}
+
+impl NeedLevel {
+    pub fn from_value(value: f32) -> Self {
+        if value <= 0.0 {
+            NeedLevel::Empty
+        } else if value <= NEEDS_LOW {
+            NeedLevel::Low
+        } else {
+            NeedLevel::Ok
+        }
+    }
+}
+
+/// Meters that drain over time, full is satiated and 0 is starving
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Needs {
+    pub hunger: f32,
+    pub thirst: f32,
+}
+
+impl Needs {
+    pub fn new() -> Self {
+        Needs {
+            hunger: NEEDS_MAX,
+            thirst: NEEDS_MAX,
+        }
+    }
+
+    pub fn parse(data: &MobNeedsData) -> Self {
+        Needs {
+            hunger: data.hunger,
+            thirst: data.thirst,
+        }
+    }
+
+    pub fn to_data(&self) -> MobNeedsData {
+        MobNeedsData {
+            hunger: self.hunger,
+            thirst: self.thirst,
+        }
+    }
+
+    pub fn get(&self, kind: NeedKind) -> f32 {
+        match kind {
+            NeedKind::Hunger => self.hunger,
+            NeedKind::Thirst => self.thirst,
+        }
+    }
+
+    pub fn restore(&mut self, hunger: f32, thirst: f32) {
+        self.hunger = (self.hunger + hunger).min(NEEDS_MAX).max(0.0);
+        self.thirst = (self.thirst + thirst).min(NEEDS_MAX).max(0.0);
+    }
+
+    /// drain needs by the elapsed time, returns the needs that changed level
+    pub fn drain(&mut self, delta: DeltaTime) -> Vec<(NeedKind, NeedLevel)> {
+        let mut changes = vec![];
+
+        for (kind, rate) in &[
+            (NeedKind::Hunger, HUNGER_DRAIN),
+            (NeedKind::Thirst, THIRST_DRAIN),
+        ] {
+            let value = match kind {
+                NeedKind::Hunger => &mut self.hunger,
+                NeedKind::Thirst => &mut self.thirst,
+            };
+
+            let before = NeedLevel::from_value(*value);
+            *value = (*value - rate * delta.as_seconds_f32()).max(0.0);
+            let after = NeedLevel::from_value(*value);
+
+            if before != after {
+                changes.push((*kind, after));
+            }
+        }
+
+        changes
+    }
+
+    pub fn count_empty(&self) -> u32 {
+        [self.hunger, self.thirst]
+            .iter()
+            .filter(|value| NeedLevel::from_value(**value) == NeedLevel::Empty)
+            .count() as u32
+    }
+
+    pub fn has_empty(&self) -> bool {
+        self.count_empty() > 0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EffectKind {
+    Attack,
+    Defense,
+}
+
+impl EffectKind {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "attack" => Ok(EffectKind::Attack),
+            "defense" => Ok(EffectKind::Defense),
+            other => Err(Error::InvalidArgumentFailureStr(format!(
+                "invalid effect kind {:?}",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EffectKind::Attack => "attack",
+            EffectKind::Defense => "defense",
+        }
+    }
+}
+
+/// Temporary change in mob attributes
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Effect {
+    pub kind: EffectKind,
+    pub amount: i32,
+    pub duration: DeltaTime,
+}
+
+impl Effect {
+    pub fn parse(data: &EffectData) -> Result<Self> {
+        Ok(Effect {
+            kind: EffectKind::parse(data.kind.as_str())?,
+            amount: data.amount,
+            duration: DeltaTime(data.duration),
+        })
+    }
+
+    pub fn to_data(&self) -> EffectData {
+        EffectData {
+            kind: self.kind.as_str().to_string(),
+            amount: self.amount,
+            duration: self.duration.as_seconds_f32(),
+        }
+    }
+}
+
+/// Effect applied in a mob, effects are short lived and are not persisted
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MobEffect {
+    pub kind: EffectKind,
+    pub amount: i32,
+    pub expire: TotalTime,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ConsumableKind {
+    Food,
+    Drink,
+    Potion,
+}
+
+impl ConsumableKind {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "food" => Ok(ConsumableKind::Food),
+            "drink" => Ok(ConsumableKind::Drink),
+            "potion" => Ok(ConsumableKind::Potion),
+            other => Err(Error::InvalidArgumentFailureStr(format!(
+                "invalid consumable kind {:?}",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConsumableKind::Food => "food",
+            ConsumableKind::Drink => "drink",
+            ConsumableKind::Potion => "potion",
+        }
+    }
+}
+
+/// Item that can be eaten, drunk or quaffed
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Consumable {
+    pub kind: ConsumableKind,
+    pub heal: u32,
+    pub hunger: f32,
+    pub thirst: f32,
+    pub effect: Option<Effect>,
+}
+
+impl Consumable {
+    pub fn parse(data: &ConsumableData) -> Result<Self> {
+        Ok(Consumable {
+            kind: ConsumableKind::parse(data.kind.as_str())?,
+            heal: data.heal.unwrap_or(0),
+            hunger: data.hunger.unwrap_or(0.0),
+            thirst: data.thirst.unwrap_or(0.0),
+            effect: match &data.effect {
+                Some(effect_data) => Some(Effect::parse(effect_data)?),
+                None => None,
+            },
+        })
+    }
+
+    pub fn to_data(&self) -> ConsumableData {
+        ConsumableData {
+            kind: self.kind.as_str().to_string(),
+            heal: if self.heal > 0 { Some(self.heal) } else { None },
+            hunger: if self.hunger != 0.0 {
+                Some(self.hunger)
+            } else {
+                None
+            },
+            thirst: if self.thirst != 0.0 {
+                Some(self.thirst)
+            } else {
+                None
+            },
+            effect: self.effect.as_ref().map(|effect| effect.to_data()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_needs_drain_should_report_level_changes() {
+        let mut needs = Needs::new();
+        needs.thirst = NEEDS_LOW + 1.0;
+
+        let changes = needs.drain(DeltaTime(100.0));
+        assert_eq!(vec![(NeedKind::Thirst, NeedLevel::Low)], changes);
+
+        let changes = needs.drain(DeltaTime(100_000.0));
+        assert_eq!(
+            vec![
+                (NeedKind::Hunger, NeedLevel::Empty),
+                (NeedKind::Thirst, NeedLevel::Empty)
+            ],
+            changes
+        );
+        assert_eq!(2, needs.count_empty());
+
+        needs.restore(200.0, 10.0);
+        assert_eq!(NEEDS_MAX, needs.hunger);
+        assert_eq!(0, needs.count_empty());
+    }
+}
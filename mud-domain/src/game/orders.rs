@@ -0,0 +1,289 @@
+use crate::errors::{AsResult, Result};
+use crate::game::actions_command::{self, RequestCommand};
+use crate::game::comm;
+use crate::game::container::Container;
+use crate::game::mob::MobId;
+use commons::ObjId;
+
+/// Who a spoken order is for
+#[derive(Debug, Clone, PartialEq)]
+pub enum Addressee {
+    /// anyone under the speaker command
+    All,
+    /// label as used in any other command, repeated labels are numbered like "mercenary.2"
+    Label(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Order {
+    pub addressee: Addressee,
+    pub command: RequestCommand,
+}
+
+/// Parse orders like "all follow me", "mercenary.1 wait here" or "bot, extract". Haul is not
+/// supported by speech as it requires explicit ids.
+pub fn parse(msg: &str) -> Option<Order> {
+    let lower = msg.to_ascii_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|word| word.trim_matches(|c: char| c == '!' || c == '?' || c == ','))
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let (addressee, rest) = words.split_first()?;
+    let addressee = parse_addressee(addressee)?;
+
+    let rest: Vec<&str> = rest
+        .iter()
+        .cloned()
+        .filter(|word| *word != "please")
+        .collect();
+
+    let command = match rest.as_slice() {
+        ["follow", "me"] | ["follow"] | ["come"] | ["come", "with", "me"] => {
+            RequestCommand::FollowMe
+        }
+        ["wait"] | ["wait", "here"] | ["stay"] | ["stay", "here"] | ["stop"] => {
+            RequestCommand::Idle
+        }
+        ["guard"] | ["guard", "here"] | ["guard", "this", "place"] => RequestCommand::Guard,
+        ["extract"] | ["extract", "here"] | ["mine"] | ["mine", "here"] => RequestCommand::Extract,
+        _ => return None,
+    };
+
+    Some(Order { addressee, command })
+}
+
+fn parse_addressee(word: &str) -> Option<Addressee> {
+    if word == "all" || word == "everyone" {
+        return Some(Addressee::All);
+    }
+
+    // first of repeated labels has no number
+    let label = word.strip_suffix(".1").unwrap_or(word);
+    if label.ends_with(".0") {
+        return None;
+    }
+
+    Some(Addressee::Label(label.to_string()))
+}
+
+/// Listeners in the room with a commandable ai
+fn list_listeners(container: &Container, room_id: ObjId, mob_id: MobId) -> Vec<ObjId> {
+    container
+        .locations
+        .list_at(room_id)
+        .filter(|id| *id != mob_id)
+        .filter(|id| {
+            container
+                .ai
+                .get(*id)
+                .map(|ai| ai.commandable)
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+/// Mobs in the room hear what was said and obey if it is a order from its owner
+pub fn hear(container: &mut Container, mob_id: MobId, msg: &str) -> Result<()> {
+    let order = match parse(msg) {
+        Some(order) => order,
+        None => return Ok(()),
+    };
+
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let listeners = list_listeners(container, room_id, mob_id);
+
+    let targets: Vec<ObjId> = match &order.addressee {
+        // only own units answer to a general order
+        Addressee::All => listeners
+            .into_iter()
+            .filter(|id| container.ownership.get_owner(*id) == Some(mob_id))
+            .collect(),
+        Addressee::Label(label) => container
+            .labels
+            .search(&listeners, label)
+            .first()
+            .cloned()
+            .into_iter()
+            .collect(),
+    };
+
+    for target_id in targets {
+        let reply = obey(container, mob_id, target_id, order.command, room_id)?;
+        let label = container.labels.get_label_f(target_id);
+        container
+            .outputs
+            .broadcast(None, room_id, comm::say_someone_said(label, reply));
+    }
+
+    Ok(())
+}
+
+fn obey(
+    container: &mut Container,
+    mob_id: MobId,
+    target_id: ObjId,
+    command: RequestCommand,
+    room_id: ObjId,
+) -> Result<&'static str> {
+    if container.ownership.get_owner(target_id) != Some(mob_id) {
+        return Ok(comm::order_refuse_not_owner());
+    }
+
+    let available = actions_command::list_commands_for(container, target_id)?;
+    if !available.contains(&command) {
+        return Ok(comm::order_refuse_can_not());
+    }
+
+    log::info!("{:?} order {:?} to {:?}", mob_id, command, target_id);
+
+    match command {
+        RequestCommand::Idle => {
+            actions_command::set_command_idle(container, target_id)?;
+            Ok(comm::order_wait_ack())
+        }
+        RequestCommand::FollowMe => {
+            actions_command::set_command_follow(container, target_id, mob_id)?;
+            Ok(comm::order_follow_ack())
+        }
+        RequestCommand::Guard => {
+            actions_command::set_command_guard(container, target_id, room_id)?;
+            Ok(comm::order_guard_ack())
+        }
+        RequestCommand::Extract => {
+            let extractable_id = container
+                .locations
+                .list_at(room_id)
+                .find(|id| container.extractables.exist(*id));
+
+            match extractable_id {
+                Some(extractable_id) => {
+                    actions_command::set_command_extract(
+                        container,
+                        target_id,
+                        room_id,
+                        extractable_id,
+                    )?;
+                    Ok(comm::order_extract_ack())
+                }
+                None => Ok(comm::order_refuse_nothing_to_extract()),
+            }
+        }
+        RequestCommand::Haul => Ok(comm::order_refuse_can_not()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::ai::{Ai, AiCommand};
+    use crate::game::builder;
+    use crate::game::outputs::Output;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Some(Order {
+                addressee: Addressee::All,
+                command: RequestCommand::FollowMe,
+            }),
+            parse("all follow me")
+        );
+
+        assert_eq!(
+            Some(Order {
+                addressee: Addressee::Label("mercenary".to_string()),
+                command: RequestCommand::Idle,
+            }),
+            parse("Mercenary.1 wait here!")
+        );
+
+        assert_eq!(
+            Some(Order {
+                addressee: Addressee::Label("bot".to_string()),
+                command: RequestCommand::Guard,
+            }),
+            parse("bot, please guard here")
+        );
+
+        assert_eq!(None, parse("hello everyone"));
+        assert_eq!(None, parse("mercenary.0 wait"));
+        assert_eq!(None, parse(""));
+    }
+
+    fn add_commandable(container: &mut Container, label: &str, room_id: ObjId) -> MobId {
+        let mob_id = builder::add_mob(container, label, room_id);
+        let mut ai = Ai::new(mob_id);
+        ai.commandable = true;
+        container.ai.add_or_update(ai).unwrap();
+        mob_id
+    }
+
+    fn take_says(container: &mut Container) -> Vec<String> {
+        container
+            .outputs
+            .take()
+            .into_iter()
+            .filter_map(|output| match output {
+                Output::Broadcast { msg, .. } => Some(msg),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hear_should_obey_only_the_owner() {
+        let mut container = Container::new();
+        let room_id = builder::add_room(&mut container, "room");
+        let player_id = builder::add_mob(&mut container, "player", room_id);
+        let other_id = builder::add_mob(&mut container, "other", room_id);
+        let mercenary1_id = add_commandable(&mut container, "mercenary", room_id);
+        let mercenary2_id = add_commandable(&mut container, "mercenary", room_id);
+        container.ownership.set_owner(mercenary1_id, player_id);
+        container.ownership.set_owner(mercenary2_id, player_id);
+
+        hear(&mut container, player_id, "all follow me").unwrap();
+        for mercenary_id in vec![mercenary1_id, mercenary2_id] {
+            assert_eq!(
+                AiCommand::FollowAndProtect {
+                    target_id: player_id
+                },
+                container.ai.get(mercenary_id).unwrap().command
+            );
+        }
+        assert_eq!(2, take_says(&mut container).len());
+
+        hear(&mut container, player_id, "mercenary.2 guard here").unwrap();
+        assert_eq!(
+            AiCommand::AggressivePatrolHome { distance: 0 },
+            container.ai.get(mercenary2_id).unwrap().command
+        );
+        assert_eq!(
+            AiCommand::FollowAndProtect {
+                target_id: player_id
+            },
+            container.ai.get(mercenary1_id).unwrap().command
+        );
+
+        take_says(&mut container);
+        hear(&mut container, other_id, "mercenary wait").unwrap();
+        assert_eq!(
+            vec![comm::say_someone_said(
+                "mercenary",
+                comm::order_refuse_not_owner()
+            )],
+            take_says(&mut container)
+        );
+        assert_eq!(
+            AiCommand::FollowAndProtect {
+                target_id: player_id
+            },
+            container.ai.get(mercenary1_id).unwrap().command
+        );
+
+        // general orders are ignored by who is not the owner
+        hear(&mut container, other_id, "all wait").unwrap();
+        assert!(take_says(&mut container).is_empty());
+    }
+}
@@ -0,0 +1,473 @@
+use crate::errors::{AsResult, Error, Result};
+use crate::game::comm;
+use crate::game::container::Container;
+use crate::game::domain::Dir;
+use crate::game::mob::{MobCommand, MobId, WALK_STEP_TIME};
+use crate::game::room::RoomId;
+use crate::game::{actions, space_utils};
+use commons::{ObjId, TimeTrigger};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// A single move between two rooms
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PathStep {
+    /// move through a room exit
+    Move { dir: Dir, to_id: RoomId },
+    /// exit from a room with `can_exit` into the parent room, like a airlock
+    Out { to_id: RoomId },
+    /// enter into the object, like a ship, in the room
+    Enter { target_id: ObjId, to_id: RoomId },
+}
+
+impl PathStep {
+    pub fn get_to_id(&self) -> RoomId {
+        match self {
+            PathStep::Move { to_id, .. } => *to_id,
+            PathStep::Out { to_id } => *to_id,
+            PathStep::Enter { to_id, .. } => *to_id,
+        }
+    }
+}
+
+/// Cache of computed paths, it is invalidated any time the rooms change. Paths that pass
+/// through ships are checked before use, once ships can move without any change in the rooms
+#[derive(Clone, Debug, Default)]
+pub struct Paths {
+    version: u32,
+    index: HashMap<(RoomId, RoomId), Vec<PathStep>>,
+}
+
+impl Paths {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn get(&mut self, version: u32, from_id: RoomId, to_id: RoomId) -> Option<&Vec<PathStep>> {
+        if self.version != version {
+            log::debug!("rooms changed, clearing {} cached paths", self.index.len());
+            self.index.clear();
+            self.version = version;
+        }
+
+        self.index.get(&(from_id, to_id))
+    }
+
+    fn add(&mut self, from_id: RoomId, to_id: RoomId, path: Vec<PathStep>) {
+        self.index.insert((from_id, to_id), path);
+    }
+
+    fn remove(&mut self, from_id: RoomId, to_id: RoomId) {
+        self.index.remove(&(from_id, to_id));
+    }
+}
+
+/// All moves that can be done from the room
+pub fn list_steps(container: &Container, room_id: RoomId) -> Vec<PathStep> {
+    let room = match container.rooms.get(room_id) {
+        Some(room) => room,
+        None => return vec![],
+    };
+
+    let mut steps: Vec<PathStep> = room
+        .exits
+        .iter()
+        .map(|(dir, to_id)| PathStep::Move {
+            dir: *dir,
+            to_id: *to_id,
+        })
+        .collect();
+
+    if let Some(to_id) = get_out_room(container, room_id) {
+        steps.push(PathStep::Out { to_id });
+    }
+
+    for target_id in container.locations.list_at(room_id) {
+        if container.mobs.exists(target_id) || container.items.exists(target_id) {
+            continue;
+        }
+
+        if let Some(to_id) = get_enter_room(container, target_id) {
+            steps.push(PathStep::Enter { target_id, to_id });
+        }
+    }
+
+    steps
+}
+
+/// Find the shortest path between two rooms, the result is empty when both rooms are the same
+pub fn find_path(
+    container: &mut Container,
+    from_id: RoomId,
+    to_id: RoomId,
+) -> Option<Vec<PathStep>> {
+    let version = container.rooms.version();
+
+    if let Some(path) = container.paths.get(version, from_id, to_id).cloned() {
+        if is_path_valid(container, from_id, &path) {
+            return Some(path);
+        }
+
+        log::debug!(
+            "cached path from {:?} to {:?} is no more valid",
+            from_id,
+            to_id
+        );
+        container.paths.remove(from_id, to_id);
+    }
+
+    let path = search(container, from_id, to_id)?;
+    container.paths.add(from_id, to_id, path.clone());
+    Some(path)
+}
+
+/// Map of all rooms that can be reached from `room_id` with at most `distance` steps
+pub fn rooms_within(container: &Container, room_id: RoomId, distance: u32) -> HashMap<RoomId, u32> {
+    let mut visited = HashMap::new();
+    let mut queue = VecDeque::new();
+    visited.insert(room_id, 0);
+    queue.push_back(room_id);
+
+    while let Some(current_id) = queue.pop_front() {
+        let current_distance = visited[&current_id];
+        if current_distance >= distance {
+            continue;
+        }
+
+        for step in list_steps(container, current_id) {
+            let next_id = step.get_to_id();
+            if !visited.contains_key(&next_id) {
+                visited.insert(next_id, current_distance + 1);
+                queue.push_back(next_id);
+            }
+        }
+    }
+
+    visited
+}
+
+/// Rooms already visited by the mob matching the label, mobs can only find the way into them
+pub fn search_known_rooms(container: &Container, mob_id: MobId, label: &str) -> Vec<RoomId> {
+    let mut candidates: Vec<RoomId> = container
+        .memories
+        .get(mob_id)
+        .map(|memory| {
+            memory
+                .know_ids
+                .iter()
+                .filter(|id| container.rooms.exists(**id))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    candidates.sort();
+
+    container.labels.search(&candidates, label)
+}
+
+/// Short description of the step, like the command a player would use
+pub fn describe_step(container: &Container, step: &PathStep) -> String {
+    match step {
+        PathStep::Move { dir, .. } => dir.as_str().to_string(),
+        PathStep::Out { .. } => "out".to_string(),
+        PathStep::Enter { target_id, .. } => {
+            format!("enter {}", container.labels.get_label_f(*target_id))
+        }
+    }
+}
+
+/// Execute the step as if it was a command from the mob
+pub fn execute_step(container: &mut Container, mob_id: MobId, step: &PathStep) -> Result<()> {
+    match step {
+        PathStep::Move { dir, .. } => actions::move_dir(container, mob_id, *dir),
+        PathStep::Out { .. } => actions::out(container, mob_id),
+        PathStep::Enter { target_id, .. } => actions::enter_do(container, mob_id, *target_id),
+    }
+}
+
+/// Move the mob one step in direction of the room
+pub fn move_towards(container: &mut Container, mob_id: MobId, to_id: RoomId) -> Result<()> {
+    let from_id = container.locations.get(mob_id).as_result()?;
+    let path = find_path(container, from_id, to_id)
+        .as_result_string(|| format!("no path from {:?} to {:?}", from_id, to_id))?;
+
+    match path.first() {
+        Some(step) => execute_step(container, mob_id, step),
+        None => Ok(()),
+    }
+}
+
+/// Start to walk the mob into the room, one step is executed by time
+pub fn walk_to(container: &mut Container, mob_id: MobId, to_id: RoomId) -> Result<()> {
+    let from_id = container.locations.get(mob_id).as_result()?;
+    let to_label = container.labels.get_label_f(to_id).to_string();
+
+    let path = match find_path(container, from_id, to_id) {
+        Some(path) => path,
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::path_not_found(&to_label));
+            return Err(Error::NotFoundFailure);
+        }
+    };
+
+    if path.is_empty() {
+        container
+            .outputs
+            .private(mob_id, comm::path_already_there(&to_label));
+        return Ok(());
+    }
+
+    let mob = container.mobs.get_mut(mob_id).as_result()?;
+    if mob.set_action_walk(to_id).is_err() {
+        container.outputs.private(mob_id, comm::walk_fail_busy());
+        return Err(Error::InvalidStateFailure);
+    }
+
+    container
+        .outputs
+        .private(mob_id, comm::walk_start(&to_label, path.len()));
+
+    Ok(())
+}
+
+/// Execute the next walk step when ready, the walk is cancelled if the path gets blocked
+pub fn tick_walk(container: &mut Container, mob_id: MobId, to_id: RoomId) -> Result<()> {
+    let total = container.time.total;
+    let mob = container.mobs.get_mut(mob_id).as_result()?;
+    if !mob.is_idle() {
+        log::debug!("{:?} is busy, stopping to walk", mob_id);
+        mob.command = MobCommand::None;
+        return Ok(());
+    }
+
+    if !TimeTrigger::should_trigger(mob.state.walk_calm_down, total) {
+        return Ok(());
+    }
+
+    let from_id = container.locations.get(mob_id).as_result()?;
+    let to_label = container.labels.get_label_f(to_id).to_string();
+
+    let step = match find_path(container, from_id, to_id) {
+        Some(path) => path.first().cloned(),
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::path_not_found(&to_label));
+            container.mobs.cancel_command(mob_id)?;
+            return Err(Error::NotFoundFailure);
+        }
+    };
+
+    let step = match step {
+        Some(step) => step,
+        None => {
+            container
+                .outputs
+                .private(mob_id, comm::walk_arrived(&to_label));
+            container.mobs.cancel_command(mob_id)?;
+            return Ok(());
+        }
+    };
+
+    if let Err(e) = execute_step(container, mob_id, &step) {
+        container.mobs.cancel_command(mob_id)?;
+        return Err(e);
+    }
+
+    let mob = container.mobs.get_mut(mob_id).as_result()?;
+    mob.state.walk_calm_down = TimeTrigger::next(WALK_STEP_TIME, total);
+
+    if step.get_to_id() == to_id {
+        container
+            .outputs
+            .private(mob_id, comm::walk_arrived(&to_label));
+        container.mobs.cancel_command(mob_id)?;
+    }
+
+    Ok(())
+}
+
+/// Breadth first search, every step has the same cost
+fn search(container: &Container, from_id: RoomId, to_id: RoomId) -> Option<Vec<PathStep>> {
+    if !container.rooms.exists(from_id) || !container.rooms.exists(to_id) {
+        return None;
+    }
+
+    let mut previous: HashMap<RoomId, (RoomId, PathStep)> = HashMap::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(from_id);
+
+    while let Some(current_id) = queue.pop_front() {
+        if current_id == to_id {
+            let mut path = vec![];
+            let mut id = to_id;
+            while id != from_id {
+                let (prev_id, step) = previous.remove(&id)?;
+                path.push(step);
+                id = prev_id;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        for step in list_steps(container, current_id) {
+            let next_id = step.get_to_id();
+            if next_id == from_id || previous.contains_key(&next_id) {
+                continue;
+            }
+
+            previous.insert(next_id, (current_id, step));
+            queue.push_back(next_id);
+        }
+    }
+
+    None
+}
+
+fn is_path_valid(container: &Container, from_id: RoomId, path: &Vec<PathStep>) -> bool {
+    let mut current_id = from_id;
+    for step in path {
+        let valid = match step {
+            // exits are covered by the rooms version
+            PathStep::Move { .. } => true,
+            PathStep::Out { to_id } => get_out_room(container, current_id) == Some(*to_id),
+            PathStep::Enter { target_id, to_id } => {
+                container.locations.get(*target_id) == Some(current_id)
+                    && get_enter_room(container, *target_id) == Some(*to_id)
+            }
+        };
+
+        if !valid {
+            return false;
+        }
+
+        current_id = step.get_to_id();
+    }
+
+    true
+}
+
+/// same rules as `actions::get_out_target`
+fn get_out_room(container: &Container, room_id: RoomId) -> Option<RoomId> {
+    if !container.rooms.get(room_id)?.can_exit {
+        return None;
+    }
+
+    container
+        .locations
+        .list_parents(room_id)
+        .into_iter()
+        .find(|id| container.rooms.exists(*id))
+}
+
+/// same rules as `actions::enter_do`
+fn get_enter_room(container: &Container, target_id: ObjId) -> Option<RoomId> {
+    space_utils::find_children_rooms_with_can_exit(container, target_id)
+        .first()
+        .cloned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::builder;
+
+    #[test]
+    fn test_find_path_should_use_exits_and_ships() {
+        let mut container = Container::new();
+        let room1_id = builder::add_room(&mut container, "room1");
+        let room2_id = builder::add_room(&mut container, "room2");
+        let room3_id = builder::add_room(&mut container, "room3");
+        builder::add_portal(&mut container, room1_id, room2_id, Dir::N);
+        builder::add_portal(&mut container, room2_id, room3_id, Dir::E);
+
+        assert_eq!(Some(vec![]), find_path(&mut container, room1_id, room1_id));
+
+        let path = find_path(&mut container, room1_id, room3_id).unwrap();
+        assert_eq!(
+            vec![
+                PathStep::Move {
+                    dir: Dir::N,
+                    to_id: room2_id
+                },
+                PathStep::Move {
+                    dir: Dir::E,
+                    to_id: room3_id
+                }
+            ],
+            path
+        );
+
+        // ship landed in room3
+        let ship_id = container.objects.create();
+        container.locations.set(ship_id, room3_id);
+        let airlock_id = builder::add_room(&mut container, "airlock");
+        container.locations.set(airlock_id, ship_id);
+        container
+            .rooms
+            .update(airlock_id, |room| room.can_exit = true)
+            .unwrap();
+
+        let path = find_path(&mut container, room1_id, airlock_id).unwrap();
+        assert_eq!(
+            Some(&PathStep::Enter {
+                target_id: ship_id,
+                to_id: airlock_id
+            }),
+            path.last()
+        );
+
+        let path = find_path(&mut container, airlock_id, room1_id).unwrap();
+        assert_eq!(Some(&PathStep::Out { to_id: room3_id }), path.first());
+
+        // ship take off to somewhere else
+        let space_id = builder::add_room(&mut container, "space");
+        container.locations.set(ship_id, space_id);
+        // rooms version did not change, but cached path is no more valid
+        assert_eq!(None, find_path(&mut container, room1_id, airlock_id));
+    }
+
+    #[test]
+    fn test_find_path_should_be_invalidated_when_exits_change() {
+        let mut container = Container::new();
+        let room1_id = builder::add_room(&mut container, "room1");
+        let room2_id = builder::add_room(&mut container, "room2");
+        let room3_id = builder::add_room(&mut container, "room3");
+        builder::add_portal(&mut container, room1_id, room2_id, Dir::N);
+        builder::add_portal(&mut container, room2_id, room3_id, Dir::N);
+
+        assert_eq!(
+            2,
+            find_path(&mut container, room1_id, room3_id).unwrap().len()
+        );
+
+        container
+            .rooms
+            .remove_portal(room2_id, room3_id, Dir::N)
+            .unwrap();
+        assert_eq!(None, find_path(&mut container, room1_id, room3_id));
+
+        builder::add_portal(&mut container, room1_id, room3_id, Dir::S);
+        assert_eq!(
+            1,
+            find_path(&mut container, room1_id, room3_id).unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_rooms_within() {
+        let mut container = Container::new();
+        let room1_id = builder::add_room(&mut container, "room1");
+        let room2_id = builder::add_room(&mut container, "room2");
+        let room3_id = builder::add_room(&mut container, "room3");
+        builder::add_portal(&mut container, room1_id, room2_id, Dir::N);
+        builder::add_portal(&mut container, room2_id, room3_id, Dir::N);
+
+        let rooms = rooms_within(&container, room1_id, 1);
+        assert_eq!(2, rooms.len());
+        assert_eq!(Some(&1), rooms.get(&room2_id));
+        assert!(rooms_within(&container, room1_id, 2).contains_key(&room3_id));
+    }
+}
@@ -10,6 +10,10 @@ pub struct Player {
     pub id: PlayerId,
     pub login: String,
     pub mob_id: MobId,
+    /// opt-in to fight against other players
+    pub pvp: bool,
+    /// where the next avatar will be respawn, defined when the avatar die
+    pub respawn_room_id: Option<ObjId>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +53,8 @@ impl PlayerRepository {
             id: player_id,
             login: login,
             mob_id: avatar_id,
+            pvp: false,
+            respawn_room_id: None,
         };
 
         self.index.insert(player_id, player);
@@ -73,6 +79,19 @@ impl PlayerRepository {
         self.index.get(&player_id).map(|player| player.mob_id)
     }
 
+    pub fn update<F>(&mut self, player_id: PlayerId, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut Player),
+    {
+        self.index
+            .get_mut(&player_id)
+            .map(|player| {
+                f(player);
+                log::debug!("{:?} updated", player);
+            })
+            .ok_or(Error::NotFoundFailure)
+    }
+
     pub fn set_mob(&mut self, player_id: PlayerId, mob_id: MobId) -> Result<()> {
         self.index
             .get_mut(&player_id)
@@ -0,0 +1,362 @@
+use crate::errors::{Error, Result};
+use crate::game::container::Container;
+use crate::game::loader::dto::PvpData;
+use crate::game::mob::MobId;
+use crate::game::room::RoomId;
+use commons::{DeltaTime, ObjId, TotalTime};
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How long a attack from a player allow the victim to fight back
+pub const CONSENT_TIME: DeltaTime = DeltaTime(120.0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PvpPolicy {
+    /// default, players can not attack other players
+    Disabled,
+    /// no one can attack players, including aggressive mobs
+    Safe,
+    /// players that have opt-in pvp can attack each other
+    Allowed,
+    /// anyone can attack anyone
+    Arena,
+}
+
+impl PvpPolicy {
+    pub fn parse(value: &str) -> Result<PvpPolicy> {
+        match value {
+            "disabled" => Ok(PvpPolicy::Disabled),
+            "safe" => Ok(PvpPolicy::Safe),
+            "allowed" | "pvp" => Ok(PvpPolicy::Allowed),
+            "arena" => Ok(PvpPolicy::Arena),
+            other => Err(Error::InvalidArgumentFailureStr(format!(
+                "invalid pvp policy {:?}",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PvpPolicy::Disabled => "disabled",
+            PvpPolicy::Safe => "safe",
+            PvpPolicy::Allowed => "allowed",
+            PvpPolicy::Arena => "arena",
+        }
+    }
+}
+
+/// Rules applied to rooms and zones, rooms without rules inherit from parent zones
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PvpRules {
+    pub policy: PvpPolicy,
+    /// where players that die here respawn, when None the default initial room is used
+    pub respawn_room_id: Option<RoomId>,
+    /// players that die here keep its items instead to drop into a corpse
+    pub keep_items: bool,
+}
+
+impl PvpRules {
+    pub fn new() -> Self {
+        PvpRules {
+            policy: PvpPolicy::Disabled,
+            respawn_room_id: None,
+            keep_items: false,
+        }
+    }
+
+    pub fn parse(data: &PvpData, respawn_room_id: Option<RoomId>) -> Result<Self> {
+        Ok(PvpRules {
+            policy: match &data.policy {
+                Some(value) => PvpPolicy::parse(value.as_str())?,
+                None => PvpPolicy::Disabled,
+            },
+            respawn_room_id,
+            keep_items: data.keep_items.unwrap_or(false),
+        })
+    }
+
+    pub fn to_data(&self) -> PvpData {
+        PvpData {
+            policy: Some(self.policy.as_str().to_string()),
+            respawn_room_id: self.respawn_room_id.map(|id| id.into()),
+            keep_items: if self.keep_items { Some(true) } else { None },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PvpDenied {
+    /// target is in a safe location
+    Safe,
+    /// pvp is not allowed in this location
+    Disabled,
+    /// attacker or target has not opt-in for pvp
+    NotFlagged,
+}
+
+/// Keep track of player attacks against other players, a player that was attacked can
+/// fight back even without pvp flag
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Aggressions {
+    /// key = victim, value = list of aggressors and time when the consent expires
+    index: HashMap<MobId, Vec<(MobId, TotalTime)>>,
+}
+
+impl Aggressions {
+    pub fn new() -> Self {
+        Aggressions {
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, aggressor_id: MobId, victim_id: MobId, total_time: TotalTime) {
+        let expiration = total_time + CONSENT_TIME;
+        let list = self.index.entry(victim_id).or_insert(vec![]);
+        list.retain(|(id, _)| *id != aggressor_id);
+        list.push((aggressor_id, expiration));
+        log::debug!(
+            "{:?} was attacked by {:?}, consent until {:?}",
+            victim_id,
+            aggressor_id,
+            expiration
+        );
+    }
+
+    /// true if the victim_id was attacked by the aggressor_id
+    pub fn was_attacked_by(
+        &self,
+        victim_id: MobId,
+        aggressor_id: MobId,
+        total_time: TotalTime,
+    ) -> bool {
+        self.index
+            .get(&victim_id)
+            .map(|list| {
+                list.iter()
+                    .any(|(id, expiration)| *id == aggressor_id && expiration.is_after(total_time))
+            })
+            .unwrap_or(false)
+    }
+
+    pub fn remove(&mut self, id: ObjId) {
+        self.index.remove(&id);
+        for list in self.index.values_mut() {
+            list.retain(|(aggressor_id, _)| *aggressor_id != id);
+        }
+    }
+}
+
+/// Search the pvp rules for the location, first the room and later all parents zones
+pub fn resolve_rules(container: &Container, location_id: ObjId) -> PvpRules {
+    for id in container.locations.list_parents_inclusive(location_id) {
+        if let Some(rules) = container.rooms.get(id).and_then(|room| room.pvp.as_ref()) {
+            return rules.clone();
+        }
+
+        if let Some(rules) = container.zones.get(id).and_then(|zone| zone.pvp.as_ref()) {
+            return rules.clone();
+        }
+    }
+
+    PvpRules::new()
+}
+
+fn is_player(container: &Container, mob_id: MobId) -> bool {
+    container
+        .mobs
+        .get(mob_id)
+        .map(|mob| mob.is_avatar)
+        .unwrap_or(false)
+}
+
+fn is_flagged(container: &Container, mob_id: MobId) -> bool {
+    container
+        .players
+        .find_from_mob(mob_id)
+        .and_then(|player_id| container.players.get(player_id))
+        .map(|player| player.pvp)
+        .unwrap_or(false)
+}
+
+/// Check if the pvp rules of the target location allow the attack. Only attacks against
+/// players are restricted.
+pub fn check_attack(
+    container: &Container,
+    mob_id: MobId,
+    target_id: MobId,
+) -> std::result::Result<(), PvpDenied> {
+    if !is_player(container, target_id) {
+        return Ok(());
+    }
+
+    let location_id = match container.locations.get(target_id) {
+        Some(location_id) => location_id,
+        None => return Ok(()),
+    };
+
+    let attacker_is_player = is_player(container, mob_id);
+    let rules = resolve_rules(container, location_id);
+
+    match rules.policy {
+        PvpPolicy::Safe => Err(PvpDenied::Safe),
+        PvpPolicy::Arena => Ok(()),
+        _ if !attacker_is_player => Ok(()),
+        PvpPolicy::Disabled => Err(PvpDenied::Disabled),
+        PvpPolicy::Allowed => {
+            let consent =
+                container
+                    .aggressions
+                    .was_attacked_by(mob_id, target_id, container.time.total);
+
+            if consent || (is_flagged(container, mob_id) && is_flagged(container, target_id)) {
+                Ok(())
+            } else {
+                Err(PvpDenied::NotFlagged)
+            }
+        }
+    }
+}
+
+/// Register a attack, if is a player attacking other player, victim receive consent to fight back
+pub fn on_attack(container: &mut Container, mob_id: MobId, target_id: MobId) {
+    if is_player(container, mob_id) && is_player(container, target_id) {
+        let total_time = container.time.total;
+        container.aggressions.add(mob_id, target_id, total_time);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::builder;
+    use crate::game::player::Player;
+    use crate::game::zone::Zone;
+
+    struct Scenery {
+        container: Container,
+        room_id: RoomId,
+        player1_id: MobId,
+        player2_id: MobId,
+        mob_id: MobId,
+    }
+
+    fn setup(zone_policy: Option<PvpPolicy>) -> Scenery {
+        let mut container = Container::new();
+        let zone_id = container.objects.create();
+        let mut zone = Zone::new(zone_id);
+        zone.pvp = zone_policy.map(|policy| PvpRules {
+            policy,
+            ..PvpRules::new()
+        });
+        container.zones.add(zone).unwrap();
+
+        let room_id = builder::add_room(&mut container, "room");
+        container.locations.set(room_id, zone_id);
+
+        let mut players = vec![];
+        for label in &["player1", "player2"] {
+            let mob_id = builder::add_mob(&mut container, label, room_id);
+            container
+                .mobs
+                .update(mob_id, |mob| mob.is_avatar = true)
+                .unwrap();
+            let player_id = container.objects.create();
+            container
+                .players
+                .create(player_id, label.to_string(), mob_id);
+            players.push(mob_id);
+        }
+
+        let mob_id = builder::add_mob(&mut container, "wolf", room_id);
+
+        Scenery {
+            container,
+            room_id,
+            player1_id: players[0],
+            player2_id: players[1],
+            mob_id,
+        }
+    }
+
+    fn set_flag(container: &mut Container, mob_id: MobId, value: bool) {
+        let player_id = container.players.find_from_mob(mob_id).unwrap();
+        container
+            .players
+            .update(player_id, |player: &mut Player| player.pvp = value)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_pvp_disabled_by_default() {
+        let s = setup(None);
+        assert_eq!(
+            Err(PvpDenied::Disabled),
+            check_attack(&s.container, s.player1_id, s.player2_id)
+        );
+        assert_eq!(Ok(()), check_attack(&s.container, s.mob_id, s.player2_id));
+        assert_eq!(Ok(()), check_attack(&s.container, s.player1_id, s.mob_id));
+    }
+
+    #[test]
+    fn test_pvp_safe_zone_protect_players_from_everyone() {
+        let s = setup(Some(PvpPolicy::Safe));
+        assert_eq!(
+            Err(PvpDenied::Safe),
+            check_attack(&s.container, s.player1_id, s.player2_id)
+        );
+        assert_eq!(
+            Err(PvpDenied::Safe),
+            check_attack(&s.container, s.mob_id, s.player2_id)
+        );
+    }
+
+    #[test]
+    fn test_pvp_room_rules_overwrite_zone_rules() {
+        let mut s = setup(Some(PvpPolicy::Safe));
+        s.container
+            .rooms
+            .update(s.room_id, |room| {
+                room.pvp = Some(PvpRules {
+                    policy: PvpPolicy::Arena,
+                    ..PvpRules::new()
+                })
+            })
+            .unwrap();
+
+        assert_eq!(
+            Ok(()),
+            check_attack(&s.container, s.player1_id, s.player2_id)
+        );
+    }
+
+    #[test]
+    fn test_pvp_allowed_requires_both_flags_or_consent() {
+        let mut s = setup(Some(PvpPolicy::Allowed));
+        assert_eq!(
+            Err(PvpDenied::NotFlagged),
+            check_attack(&s.container, s.player1_id, s.player2_id)
+        );
+
+        set_flag(&mut s.container, s.player1_id, true);
+        assert_eq!(
+            Err(PvpDenied::NotFlagged),
+            check_attack(&s.container, s.player1_id, s.player2_id)
+        );
+
+        set_flag(&mut s.container, s.player2_id, true);
+        assert_eq!(
+            Ok(()),
+            check_attack(&s.container, s.player1_id, s.player2_id)
+        );
+
+        // player 2 without flag can fight back after being attacked
+        set_flag(&mut s.container, s.player2_id, false);
+        on_attack(&mut s.container, s.player1_id, s.player2_id);
+        assert_eq!(
+            Ok(()),
+            check_attack(&s.container, s.player2_id, s.player1_id)
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use crate::errors::{Error, Result};
+use crate::game::loader::dto::{
+    CanLoad, CanSnapshot, ObjData, QuestData, QuestLogData, QuestObjectiveData, QuestProgressData,
+    QuestRewardData, StaticId,
+};
+use crate::game::loader::LoadingCtx;
+use crate::game::mob::Xp;
+use crate::game::prices::Money;
+use crate::game::room::RoomId;
+use commons::{ObjId, PlayerId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub type QuestId = ObjId;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum QuestObjective {
+    Kill {
+        prefab_id: StaticId,
+        amount: u32,
+    },
+    /// give items of the prefab to the target
+    Deliver {
+        prefab_id: StaticId,
+        target_id: ObjId,
+        amount: u32,
+    },
+    Reach {
+        room_id: RoomId,
+    },
+    Extract {
+        prefab_id: StaticId,
+        amount: u32,
+    },
+}
+
+impl QuestObjective {
+    pub fn get_amount(&self) -> u32 {
+        match self {
+            QuestObjective::Kill { amount, .. } => *amount,
+            QuestObjective::Deliver { amount, .. } => *amount,
+            QuestObjective::Reach { .. } => 1,
+            QuestObjective::Extract { amount, .. } => *amount,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuestReward {
+    pub xp: Xp,
+    pub money: Money,
+    pub items: Vec<StaticId>,
+}
+
+/// Quest definition, label and description are the object ones
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Quest {
+    pub id: QuestId,
+    pub objectives: Vec<QuestObjective>,
+    pub reward: QuestReward,
+}
+
+/// Quest in progress, keep the current amount of each objective
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuestProgress {
+    pub quest_id: QuestId,
+    pub progress: Vec<u32>,
+}
+
+impl QuestProgress {
+    pub fn new(quest: &Quest) -> Self {
+        QuestProgress {
+            quest_id: quest.id,
+            progress: quest.objectives.iter().map(|_| 0).collect(),
+        }
+    }
+
+    pub fn is_complete(&self, quest: &Quest) -> bool {
+        quest
+            .objectives
+            .iter()
+            .zip(self.progress.iter())
+            .all(|(objective, progress)| *progress >= objective.get_amount())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuestLog {
+    pub id: PlayerId,
+    pub active: Vec<QuestProgress>,
+    pub completed: Vec<QuestId>,
+}
+
+impl QuestLog {
+    pub fn new(id: PlayerId) -> Self {
+        QuestLog {
+            id,
+            active: vec![],
+            completed: vec![],
+        }
+    }
+
+    pub fn is_active(&self, quest_id: QuestId) -> bool {
+        self.active.iter().any(|i| i.quest_id == quest_id)
+    }
+
+    pub fn is_completed(&self, quest_id: QuestId) -> bool {
+        self.completed.contains(&quest_id)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Quests {
+    index: HashMap<QuestId, Quest>,
+    logs: HashMap<PlayerId, QuestLog>,
+}
+
+impl Quests {
+    pub fn new() -> Self {
+        Quests {
+            index: HashMap::new(),
+            logs: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, quest: Quest) -> Result<()> {
+        if self.index.contains_key(&quest.id) {
+            return Err(Error::ConflictException);
+        }
+        self.index.insert(quest.id, quest);
+        Ok(())
+    }
+
+    pub fn get(&self, id: QuestId) -> Option<&Quest> {
+        self.index.get(&id)
+    }
+
+    pub fn remove(&mut self, id: ObjId) {
+        self.index.remove(&id);
+        self.logs.remove(&id);
+    }
+
+    pub fn get_log(&self, player_id: PlayerId) -> Option<&QuestLog> {
+        self.logs.get(&player_id)
+    }
+
+    pub fn get_log_mut(&mut self, player_id: PlayerId) -> Option<&mut QuestLog> {
+        self.logs.get_mut(&player_id)
+    }
+
+    /// get the player quest log, creating a empty one if player has none
+    pub fn get_or_create_log(&mut self, player_id: PlayerId) -> &mut QuestLog {
+        self.logs
+            .entry(player_id)
+            .or_insert_with(|| QuestLog::new(player_id))
+    }
+
+    pub fn list_logs<'a>(&'a self) -> impl Iterator<Item = &'a QuestLog> + 'a {
+        self.logs.values()
+    }
+}
+
+fn parse_objective(
+    id: QuestId,
+    data: &QuestObjectiveData,
+    resolve: &dyn Fn(StaticId) -> ObjId,
+) -> Result<QuestObjective> {
+    let amount = data.amount.unwrap_or(1);
+
+    match (data.kind.as_str(), data.prefab_id, data.target_id) {
+        ("kill", Some(prefab_id), None) => Ok(QuestObjective::Kill { prefab_id, amount }),
+        ("deliver", Some(prefab_id), Some(target_id)) => Ok(QuestObjective::Deliver {
+            prefab_id,
+            target_id: resolve(target_id),
+            amount,
+        }),
+        ("reach", None, Some(room_id)) => Ok(QuestObjective::Reach {
+            room_id: resolve(room_id),
+        }),
+        ("extract", Some(prefab_id), None) => Ok(QuestObjective::Extract { prefab_id, amount }),
+        _ => Err(Error::InvalidArgumentFailureStr(format!(
+            "quest {:?} has invalid objective {:?}",
+            id, data
+        ))),
+    }
+}
+
+fn objective_to_data(objective: &QuestObjective) -> QuestObjectiveData {
+    match objective {
+        QuestObjective::Kill { prefab_id, amount } => QuestObjectiveData {
+            kind: "kill".to_string(),
+            prefab_id: Some(*prefab_id),
+            target_id: None,
+            amount: Some(*amount),
+        },
+        QuestObjective::Deliver {
+            prefab_id,
+            target_id,
+            amount,
+        } => QuestObjectiveData {
+            kind: "deliver".to_string(),
+            prefab_id: Some(*prefab_id),
+            target_id: Some((*target_id).into()),
+            amount: Some(*amount),
+        },
+        QuestObjective::Reach { room_id } => QuestObjectiveData {
+            kind: "reach".to_string(),
+            prefab_id: None,
+            target_id: Some((*room_id).into()),
+            amount: None,
+        },
+        QuestObjective::Extract { prefab_id, amount } => QuestObjectiveData {
+            kind: "extract".to_string(),
+            prefab_id: Some(*prefab_id),
+            target_id: None,
+            amount: Some(*amount),
+        },
+    }
+}
+
+impl CanLoad for Quests {
+    fn load(&mut self, references: &LoadingCtx, obj_id: ObjId, data: &ObjData) -> Result<()> {
+        // references to objects in the same file are mapped to the created objects
+        let resolve = |id: StaticId| {
+            references
+                .id_map
+                .get(&id)
+                .cloned()
+                .unwrap_or(ObjId(id.as_u32()))
+        };
+
+        if let Some(quest_data) = &data.quest {
+            let objectives = quest_data
+                .objectives
+                .iter()
+                .map(|objective| parse_objective(obj_id, objective, &resolve))
+                .collect::<Result<Vec<_>>>()?;
+
+            let reward = quest_data.reward.as_ref();
+
+            self.add(Quest {
+                id: obj_id,
+                objectives,
+                reward: QuestReward {
+                    xp: reward.and_then(|i| i.xp).unwrap_or(0),
+                    money: Money(reward.and_then(|i| i.money).unwrap_or(0)),
+                    items: reward.and_then(|i| i.items.clone()).unwrap_or_default(),
+                },
+            })?;
+        }
+
+        if let Some(log_data) = &data.quest_log {
+            let log = self.get_or_create_log(obj_id);
+            log.active = log_data
+                .active
+                .iter()
+                .map(|progress| QuestProgress {
+                    quest_id: resolve(progress.quest_id),
+                    progress: progress.progress.clone(),
+                })
+                .collect();
+            log.completed = log_data.completed.iter().map(|id| resolve(*id)).collect();
+        }
+
+        Ok(())
+    }
+}
+
+impl CanSnapshot for Quests {
+    fn snapshot(&self, obj_id: ObjId, data: &mut ObjData) -> Result<()> {
+        if let Some(quest) = self.index.get(&obj_id) {
+            data.quest = Some(QuestData {
+                objectives: quest.objectives.iter().map(objective_to_data).collect(),
+                reward: Some(QuestRewardData {
+                    xp: Some(quest.reward.xp),
+                    money: Some(quest.reward.money.as_u32()),
+                    items: Some(quest.reward.items.clone()),
+                }),
+            });
+        }
+
+        if let Some(log) = self.logs.get(&obj_id) {
+            data.quest_log = Some(QuestLogData {
+                active: log
+                    .active
+                    .iter()
+                    .map(|progress| QuestProgressData {
+                        quest_id: progress.quest_id.into(),
+                        progress: progress.progress.clone(),
+                    })
+                    .collect(),
+                completed: log.completed.iter().map(|id| (*id).into()).collect(),
+            });
+        }
+
+        Ok(())
+    }
+}
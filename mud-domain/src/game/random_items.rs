@@ -0,0 +1,190 @@
+use crate::errors::{AsResult, Result};
+use crate::game::container::Container;
+use crate::game::loader::dto::{
+    DurabilityData, ItemArmorData, ItemData, ItemFlagsData, ItemWeaponData, ObjData, PriceData,
+    TagsData,
+};
+use crate::game::loader::{Loader, LoadingCtx};
+use crate::game::location::LocationId;
+use crate::game::mob::MobId;
+use commons::prob;
+use commons::ObjId;
+use rand::prelude::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use random_stuff::Conf;
+
+/// Price of a item with rarity 1.0, rare items are more expensive
+pub const RANDOM_ITEM_PRICE_BASE: f32 = 10.0;
+/// Min bonus required to add a suffix into generated item label
+pub const RANDOM_ITEM_SUFFIX_MIN_BONUS: i32 = 5;
+pub const RANDOM_ITEM_DURABILITY: u32 = 100;
+
+/// Generate a prefab-less item, same seed always generate the same item
+pub fn generate(conf: &Conf, seed: u64) -> ObjData {
+    let generated = random_stuff::generate_item(conf, seed);
+
+    let mut label = generated.name.to_lowercase();
+    if let Some(suffix) = get_suffix(&generated) {
+        label = format!("{} {}", label, suffix);
+    }
+
+    let mut item_data = ItemData::new();
+    let mut flags = ItemFlagsData::new();
+    flags.random = Some(true);
+    item_data.flags = Some(flags);
+    item_data.durability = Some(DurabilityData {
+        current: RANDOM_ITEM_DURABILITY,
+        max: RANDOM_ITEM_DURABILITY,
+    });
+
+    let kind_tag = if generated.damage_max > 0 {
+        item_data.weapon = Some(ItemWeaponData {
+            min: generated.damage_min.max(0) as u32,
+            max: generated.damage_max as u32,
+            calm_down: 1.0,
+            attack: generated.attack,
+            defense: generated.defense,
+            range: None,
+            ammo: None,
+            damage_type: None,
+            extra_damages: None,
+        });
+        "weapon"
+    } else {
+        item_data.armor = Some(ItemArmorData {
+            defense: generated.defense,
+            rd: generated.armor.max(0) as u32,
+            resistances: None,
+        });
+        "armor"
+    };
+
+    let price = (RANDOM_ITEM_PRICE_BASE / generated.rarity).ceil() as u32;
+
+    let mut data = ObjData::new();
+    data.desc = Some(format!("A {}", label));
+    data.label = Some(label);
+    data.item = Some(item_data);
+    data.price = Some(PriceData::new(price.max(1)));
+    data.tags = Some(TagsData {
+        values: vec![
+            "item".to_string(),
+            kind_tag.to_string(),
+            generated.kind.clone(),
+        ],
+    });
+    data
+}
+
+/// Generate a new item at the location using the configured materials and items
+pub fn spawn_at(container: &mut Container, seed: u64, location_id: LocationId) -> Result<ObjId> {
+    let data = {
+        let conf = container
+            .config
+            .random_items
+            .as_ref()
+            .as_exception_str("random items are not configured".to_string())?;
+        generate(conf, seed)
+    };
+
+    let obj_id = container.objects.create();
+    Loader::apply_data(container, obj_id, &data, &LoadingCtx::default())?;
+    container.locations.set(obj_id, location_id);
+
+    log::debug!(
+        "{:?} random item {:?} generated at {:?} with seed {}",
+        obj_id,
+        data.label,
+        location_id,
+        seed
+    );
+
+    Ok(obj_id)
+}
+
+/// Roll the mob random loot, the generated item is placed in the mob inventory
+pub fn roll_loot(container: &mut Container, mob_id: MobId) -> Result<Option<ObjId>> {
+    let prob = match container.mobs.get(mob_id).and_then(|mob| mob.random_loot) {
+        Some(prob) => prob,
+        None => return Ok(None),
+    };
+
+    let mut rng = StdRng::seed_from_u64(prob::seed_for(mob_id, container.time.tick));
+    if rng.gen::<f32>() >= prob {
+        return Ok(None);
+    }
+
+    spawn_at(container, rng.next_u64(), mob_id).map(Some)
+}
+
+fn get_suffix(item: &random_stuff::Item) -> Option<&'static str> {
+    [
+        (item.attack, "of precision"),
+        (item.defense, "of protection"),
+        (item.hp, "of vitality"),
+    ]
+    .iter()
+    .filter(|(bonus, _)| *bonus >= RANDOM_ITEM_SUFFIX_MIN_BONUS)
+    .max_by_key(|(bonus, _)| *bonus)
+    .map(|(_, suffix)| *suffix)
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::game::builder;
+
+    pub fn load_conf() -> Conf {
+        hocon::HoconLoader::new()
+            .load_str(include_str!("../../../data/random-sample/items.conf"))
+            .unwrap()
+            .resolve()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate_should_be_deterministic() {
+        let conf = load_conf();
+
+        for seed in 0..20 {
+            let data = generate(&conf, seed);
+            let other = generate(&conf, seed);
+            assert_eq!(data.label, other.label);
+            assert_eq!(data.price.unwrap().price, other.price.unwrap().price);
+
+            let item = data.item.unwrap();
+            assert!(item.weapon.is_some() != item.armor.is_some());
+            assert!(data.prefab_id.is_none());
+        }
+    }
+
+    #[test]
+    fn test_spawn_at_should_create_item() {
+        let mut container = Container::new();
+        container.config.random_items = Some(load_conf());
+        let room_id = container.objects.create();
+
+        let item_id = spawn_at(&mut container, 1, room_id).unwrap();
+        let item = container.items.get(item_id).unwrap();
+        assert!(item.can_equip());
+        assert!(item.durability.is_some());
+        assert!(item.flags.is_random);
+        assert_eq!(Some(room_id), container.locations.get(item_id));
+        assert!(container.prices.get(item_id).is_some());
+        assert!(container.labels.get_label(item_id).is_some());
+    }
+
+    #[test]
+    fn test_roll_loot_should_generate_item_into_mob_inventory() {
+        let mut container = Container::new();
+        container.config.random_items = Some(load_conf());
+        let room_id = builder::add_room(&mut container, "room");
+        let mob_id = builder::add_mob(&mut container, "wolf", room_id);
+
+        assert_eq!(None, roll_loot(&mut container, mob_id).unwrap());
+
+        container.mobs.get_mut(mob_id).unwrap().random_loot = Some(1.0);
+        let item_id = roll_loot(&mut container, mob_id).unwrap().unwrap();
+        assert_eq!(Some(mob_id), container.locations.get(item_id));
+    }
+}
@@ -32,6 +32,8 @@ pub struct RandomRoomsCfg {
     pub height: u32,
     pub levels: u32,
     pub spawns: Vec<RandomRoomsSpawnCfg>,
+    /// amount of random generated items placed in each level
+    pub random_items: u32,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
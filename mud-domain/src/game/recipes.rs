@@ -0,0 +1,166 @@
+use crate::errors::{Error, Result};
+use crate::game::loader::dto::{RecipeData, RecipeInputData, RecipeOutputData, StaticId};
+use crate::game::tags::{TagId, Tags};
+use commons::{DeltaTime, ObjId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub type RecipeId = ObjId;
+
+pub const CRAFT_TIME: DeltaTime = DeltaTime(5.0);
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RecipeInputKind {
+    Prefab(StaticId),
+    Tag(TagId),
+}
+
+/// Items consumed by the recipe
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecipeInput {
+    pub kind: RecipeInputKind,
+    pub amount: u32,
+}
+
+/// Items produced by the recipe
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecipeOutput {
+    pub prefab_id: StaticId,
+    pub amount: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Recipe {
+    pub id: RecipeId,
+    pub inputs: Vec<RecipeInput>,
+    pub outputs: Vec<RecipeOutput>,
+    pub time: DeltaTime,
+    /// a object with this tag must be in the same room
+    pub workstation: Option<TagId>,
+    /// a item with this tag must be in the mob inventory, it is not consumed
+    pub tool: Option<TagId>,
+}
+
+impl Recipe {
+    pub fn new(id: RecipeId) -> Self {
+        Recipe {
+            id,
+            inputs: vec![],
+            outputs: vec![],
+            time: CRAFT_TIME,
+            workstation: None,
+            tool: None,
+        }
+    }
+
+    pub fn parse(tags: &mut Tags, id: RecipeId, data: &RecipeData) -> Result<Self> {
+        let mut inputs = vec![];
+        for input_data in &data.inputs {
+            let kind = match (input_data.prefab_id, &input_data.tag) {
+                (Some(prefab_id), None) => RecipeInputKind::Prefab(prefab_id),
+                (None, Some(tag)) => RecipeInputKind::Tag(tags.get_id(tag.as_str())),
+                _ => {
+                    return Err(Error::InvalidArgumentFailureStr(format!(
+                        "recipe {:?} input require prefab_id or tag",
+                        id
+                    )))
+                }
+            };
+
+            inputs.push(RecipeInput {
+                kind,
+                amount: input_data.amount.unwrap_or(1),
+            });
+        }
+
+        Ok(Recipe {
+            id,
+            inputs,
+            outputs: data
+                .outputs
+                .iter()
+                .map(|output_data| RecipeOutput {
+                    prefab_id: output_data.prefab_id,
+                    amount: output_data.amount.unwrap_or(1),
+                })
+                .collect(),
+            time: data.time.map(DeltaTime).unwrap_or(CRAFT_TIME),
+            workstation: data.workstation.as_ref().map(|tag| tags.get_id(tag)),
+            tool: data.tool.as_ref().map(|tag| tags.get_id(tag)),
+        })
+    }
+
+    pub fn to_data(&self, tags: &Tags) -> RecipeData {
+        let resolve = |tag_id: TagId| tags.get_str(tag_id).map(|tag| tag.to_string());
+
+        RecipeData {
+            inputs: self
+                .inputs
+                .iter()
+                .map(|input| match input.kind {
+                    RecipeInputKind::Prefab(prefab_id) => RecipeInputData {
+                        prefab_id: Some(prefab_id),
+                        tag: None,
+                        amount: Some(input.amount),
+                    },
+                    RecipeInputKind::Tag(tag_id) => RecipeInputData {
+                        prefab_id: None,
+                        tag: resolve(tag_id),
+                        amount: Some(input.amount),
+                    },
+                })
+                .collect(),
+            outputs: self
+                .outputs
+                .iter()
+                .map(|output| RecipeOutputData {
+                    prefab_id: output.prefab_id,
+                    amount: Some(output.amount),
+                })
+                .collect(),
+            time: Some(self.time.as_seconds_f32()),
+            workstation: self.workstation.and_then(resolve),
+            tool: self.tool.and_then(resolve),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Recipes {
+    index: HashMap<RecipeId, Recipe>,
+}
+
+impl Recipes {
+    pub fn new() -> Self {
+        Recipes {
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, recipe: Recipe) -> Result<()> {
+        if self.index.contains_key(&recipe.id) {
+            return Err(Error::ConflictException);
+        }
+        self.index.insert(recipe.id, recipe);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, id: RecipeId) -> Option<Recipe> {
+        self.index.remove(&id)
+    }
+
+    pub fn get(&self, id: RecipeId) -> Option<&Recipe> {
+        self.index.get(&id)
+    }
+
+    pub fn exist(&self, id: RecipeId) -> bool {
+        self.index.contains_key(&id)
+    }
+
+    /// all recipes sorted by id
+    pub fn list_ids(&self) -> Vec<RecipeId> {
+        let mut list: Vec<_> = self.index.keys().cloned().collect();
+        list.sort();
+        list
+    }
+}
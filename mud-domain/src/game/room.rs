@@ -1,5 +1,6 @@
 use super::domain::Dir;
 use crate::errors::{Error, Result};
+use crate::game::pvp::PvpRules;
 use commons::ObjId;
 
 use serde::{Deserialize, Serialize};
@@ -13,6 +14,14 @@ pub struct Room {
     pub exits: Vec<(Dir, RoomId)>,
     /// Used to implement Airlock or cave exit, if player exits, it will move to parent room
     pub can_exit: bool,
+    /// Players can access their vaults
+    pub is_bank: bool,
+    /// Overwrite zone pvp rules
+    pub pvp: Option<PvpRules>,
+    /// Overwrite zone outdoor flag
+    pub outdoor: Option<bool>,
+    /// Replace the description during the night when outdoor
+    pub night_desc: Option<String>,
 }
 
 impl Room {
@@ -21,6 +30,10 @@ impl Room {
             id,
             exits: vec![],
             can_exit: false,
+            is_bank: false,
+            pvp: None,
+            outdoor: None,
+            night_desc: None,
         }
     }
 }
@@ -52,33 +65,51 @@ impl Room {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoomRepository {
     index: HashMap<RoomId, Room>,
+    /// increased on any change, used to invalidate cached paths
+    version: u32,
 }
 
 impl RoomRepository {
     pub fn new() -> Self {
         RoomRepository {
             index: HashMap::new(),
+            version: 0,
         }
     }
 
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
     pub fn add(&mut self, room: Room) {
         if self.index.contains_key(&room.id) {
             panic!("room already exists");
         }
         log::debug!("{:?} added", room);
         self.index.insert(room.id, room);
+        self.version += 1;
     }
 
     pub fn remove(&mut self, id: ObjId) -> Option<Room> {
         log::debug!("{:?} removed", id);
-        self.index.remove(&id)
+        let room = self.index.remove(&id);
+        if room.is_some() {
+            self.version += 1;
+        }
+        room
     }
 
     pub fn get(&self, id: RoomId) -> Option<&Room> {
         self.index.get(&id)
     }
 
+    pub fn list<'a>(&'a self) -> impl Iterator<Item = &RoomId> + 'a {
+        self.index.keys()
+    }
+
     pub fn add_portal(&mut self, room1_id: RoomId, room2_id: RoomId, dir: Dir) {
+        self.version += 1;
+
         let room1 = self.index.get_mut(&room1_id).unwrap();
         room1.exits.push((dir, room2_id));
         log::debug!(
@@ -110,6 +141,7 @@ impl RoomRepository {
     where
         F: FnOnce(&mut Room),
     {
+        self.version += 1;
         self.index
             .get_mut(&room_id)
             .ok_or(Error::NotFoundFailure)
@@ -120,6 +152,8 @@ impl RoomRepository {
     }
 
     pub fn remove_portal(&mut self, room1_id: RoomId, room2_id: RoomId, dir: Dir) -> Result<()> {
+        self.version += 1;
+
         self.index
             .get_mut(&room1_id)
             .ok_or(Error::NotFoundFailure)
@@ -0,0 +1,190 @@
+use crate::errors::{Error, Result};
+use crate::game::loader::dto::{CanLoad, CanSnapshot, ObjData, ScriptData};
+use crate::game::loader::LoadingCtx;
+use commons::{DeltaTime, ObjId, TotalTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ScriptHook {
+    Enter,
+    Leave,
+    Say,
+    Use,
+    Death,
+    Timer,
+}
+
+/// Rhai source code of each hook of a object
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Script {
+    pub id: ObjId,
+    pub hooks: HashMap<ScriptHook, String>,
+    pub timer_interval: Option<DeltaTime>,
+    /// not defined until the first tick after the script is loaded
+    pub next_timer: Option<TotalTime>,
+    /// objects referenced by name from the script
+    pub refs: HashMap<String, ObjId>,
+}
+
+impl Script {
+    pub fn get_code(&self, hook: ScriptHook) -> Option<&str> {
+        self.hooks.get(&hook).map(|code| code.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Scripts {
+    index: HashMap<ObjId, Script>,
+    /// last known room of each mob, used to trigger enter and leave hooks
+    mob_rooms: HashMap<ObjId, ObjId>,
+}
+
+impl Scripts {
+    pub fn new() -> Self {
+        Scripts {
+            index: HashMap::new(),
+            mob_rooms: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, script: Script) -> Result<()> {
+        if self.index.contains_key(&script.id) {
+            return Err(Error::ConflictException);
+        }
+        self.index.insert(script.id, script);
+        Ok(())
+    }
+
+    pub fn get(&self, id: ObjId) -> Option<&Script> {
+        self.index.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: ObjId) -> Option<&mut Script> {
+        self.index.get_mut(&id)
+    }
+
+    pub fn get_code(&self, id: ObjId, hook: ScriptHook) -> Option<&str> {
+        self.index.get(&id).and_then(|script| script.get_code(hook))
+    }
+
+    pub fn has_hook(&self, id: ObjId, hook: ScriptHook) -> bool {
+        self.get_code(id, hook).is_some()
+    }
+
+    pub fn list_timers<'a>(&'a self) -> impl Iterator<Item = &'a Script> + 'a {
+        self.index
+            .values()
+            .filter(|script| script.timer_interval.is_some())
+    }
+
+    pub fn remove(&mut self, id: ObjId) {
+        self.index.remove(&id);
+        self.mob_rooms.remove(&id);
+    }
+
+    pub fn get_mob_room(&self, mob_id: ObjId) -> Option<ObjId> {
+        self.mob_rooms.get(&mob_id).cloned()
+    }
+
+    /// Update the known room of a mob without trigger any hook
+    pub fn set_mob_room(&mut self, mob_id: ObjId, room_id: Option<ObjId>) {
+        match room_id {
+            Some(room_id) => self.mob_rooms.insert(mob_id, room_id),
+            None => self.mob_rooms.remove(&mob_id),
+        };
+    }
+}
+
+fn data_hooks(data: &ScriptData) -> Vec<(ScriptHook, &Option<String>)> {
+    vec![
+        (ScriptHook::Enter, &data.on_enter),
+        (ScriptHook::Leave, &data.on_leave),
+        (ScriptHook::Say, &data.on_say),
+        (ScriptHook::Use, &data.on_use),
+        (ScriptHook::Death, &data.on_death),
+        (ScriptHook::Timer, &data.on_timer),
+    ]
+}
+
+impl CanLoad for Scripts {
+    fn load(&mut self, references: &LoadingCtx, obj_id: ObjId, data: &ObjData) -> Result<()> {
+        let script_data = match &data.script {
+            Some(script_data) => script_data,
+            None => return Ok(()),
+        };
+
+        let mut hooks = HashMap::new();
+        for (hook, code) in data_hooks(script_data) {
+            if let Some(code) = code {
+                // fail on load instead of when the hook is triggered
+                rhai::Engine::new_raw().compile(code).map_err(|err| {
+                    Error::InvalidArgumentFailureStr(format!(
+                        "{:?} script {:?} fail to compile: {}",
+                        obj_id, hook, err
+                    ))
+                })?;
+
+                hooks.insert(hook, code.clone());
+            }
+        }
+
+        // references to objects in the same file are mapped to the created objects
+        let refs = script_data
+            .refs
+            .iter()
+            .flatten()
+            .map(|(name, static_id)| {
+                let ref_id = references
+                    .id_map
+                    .get(static_id)
+                    .cloned()
+                    .unwrap_or(ObjId(static_id.as_u32()));
+                (name.clone(), ref_id)
+            })
+            .collect();
+
+        self.add(Script {
+            id: obj_id,
+            hooks,
+            timer_interval: script_data.timer_interval.map(DeltaTime),
+            next_timer: script_data.next_timer.map(TotalTime),
+            refs,
+        })
+    }
+}
+
+impl CanSnapshot for Scripts {
+    fn snapshot(&self, obj_id: ObjId, data: &mut ObjData) -> Result<()> {
+        let script = match self.index.get(&obj_id) {
+            Some(script) => script,
+            None => return Ok(()),
+        };
+
+        let get = |hook| script.get_code(hook).map(|code| code.to_string());
+
+        data.script = Some(ScriptData {
+            on_enter: get(ScriptHook::Enter),
+            on_leave: get(ScriptHook::Leave),
+            on_say: get(ScriptHook::Say),
+            on_use: get(ScriptHook::Use),
+            on_death: get(ScriptHook::Death),
+            on_timer: get(ScriptHook::Timer),
+            timer_interval: script.timer_interval.map(|i| i.as_seconds_f32()),
+            next_timer: script.next_timer.map(|i| i.as_seconds_f64()),
+            refs: if script.refs.is_empty() {
+                None
+            } else {
+                Some(
+                    script
+                        .refs
+                        .iter()
+                        .map(|(name, ref_id)| (name.clone(), (*ref_id).into()))
+                        .collect(),
+                )
+            },
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,578 @@
+//! Execute objects scripts on game hooks.
+//!
+//! Scripts never touch the container, they can only read label and tags of the objects involved in
+//! the hook and request changes that are applied after the script finish. Changes applied by
+//! scripts do not trigger new hooks.
+//!
+//! Enter and leave hooks are triggered by the script system once per tick for each mob that
+//! changed of room, independent of how it moved.
+//!
+//! Constants available to the scripts:
+//!
+//! - `self_id`: object that own the script
+//! - `actor_id`: mob that triggered the hook, -1 for timers
+//! - `room_id`: room where the hook happens, -1 if the object is not in a room
+//! - `message`: what was said for say hooks, empty for others
+//! - each name defined in the script `refs` with the referenced object id
+//!
+//! Functions:
+//!
+//! - `send(obj_id, msg)`: private message to a mob
+//! - `broadcast(room_id, msg)`: message to everyone in the room
+//! - `move_to(obj_id, room_id)`: teleport a object into a room
+//! - `spawn_at(prefab_id, location_id)`: spawn a prefab at location
+//! - `set_tag(obj_id, tag)` and `remove_tag(obj_id, tag)`
+//! - `has_tag(obj_id, tag)` and `label(obj_id)`: only for self, actor and room
+use crate::errors::{AsResult, Error, Result};
+use crate::game::container::Container;
+use crate::game::item::ItemId;
+use crate::game::loader::dto::StaticId;
+use crate::game::loader::Loader;
+use crate::game::mob::MobId;
+use crate::game::room::RoomId;
+use crate::game::scripts::ScriptHook;
+use crate::game::{actions, comm};
+use commons::ObjId;
+use rhai::{Engine, EvalAltResult, Scope};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Limits of a script execution, a broken script must not freeze the game
+const MAX_OPERATIONS: u64 = 10_000;
+const MAX_CALL_LEVELS: usize = 16;
+const MAX_STRING_SIZE: usize = 4096;
+const MAX_ARRAY_SIZE: usize = 256;
+const MAX_MAP_SIZE: usize = 256;
+
+/// Changes requested by a script
+#[derive(Debug, Clone, PartialEq)]
+enum ScriptCommand {
+    Send {
+        obj_id: ObjId,
+        msg: String,
+    },
+    Broadcast {
+        room_id: RoomId,
+        msg: String,
+    },
+    Move {
+        obj_id: ObjId,
+        room_id: RoomId,
+    },
+    Spawn {
+        prefab_id: StaticId,
+        location_id: ObjId,
+    },
+    SetTag {
+        obj_id: ObjId,
+        tag: String,
+    },
+    RemoveTag {
+        obj_id: ObjId,
+        tag: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct ScriptCtx {
+    self_id: ObjId,
+    actor_id: Option<MobId>,
+    room_id: Option<RoomId>,
+    message: Option<String>,
+}
+
+/// label and tags of a object readable by the script
+#[derive(Debug, Clone)]
+struct KnownObj {
+    label: String,
+    tags: Vec<String>,
+}
+
+pub fn on_enter(container: &mut Container, room_id: RoomId, mob_id: MobId) {
+    run(
+        container,
+        ScriptHook::Enter,
+        ScriptCtx {
+            self_id: room_id,
+            actor_id: Some(mob_id),
+            room_id: Some(room_id),
+            message: None,
+        },
+    );
+}
+
+pub fn on_leave(container: &mut Container, room_id: RoomId, mob_id: MobId) {
+    run(
+        container,
+        ScriptHook::Leave,
+        ScriptCtx {
+            self_id: room_id,
+            actor_id: Some(mob_id),
+            room_id: Some(room_id),
+            message: None,
+        },
+    );
+}
+
+/// Executed for the room and each other object in the room
+pub fn on_say(container: &mut Container, mob_id: MobId, msg: &str) {
+    let room_id = match container.locations.get(mob_id) {
+        Some(room_id) => room_id,
+        None => return,
+    };
+
+    let mut candidates = vec![room_id];
+    candidates.extend(
+        container
+            .locations
+            .list_at(room_id)
+            .filter(|id| *id != mob_id),
+    );
+
+    for obj_id in candidates {
+        run(
+            container,
+            ScriptHook::Say,
+            ScriptCtx {
+                self_id: obj_id,
+                actor_id: Some(mob_id),
+                room_id: Some(room_id),
+                message: Some(msg.to_string()),
+            },
+        );
+    }
+}
+
+/// Executed before the mob is removed
+pub fn on_death(container: &mut Container, mob_id: MobId, killer_id: Option<MobId>) {
+    let room_id = find_room(container, mob_id);
+    run(
+        container,
+        ScriptHook::Death,
+        ScriptCtx {
+            self_id: mob_id,
+            actor_id: killer_id,
+            room_id,
+            message: None,
+        },
+    );
+}
+
+pub fn on_timer(container: &mut Container, obj_id: ObjId) {
+    let room_id = find_room(container, obj_id);
+    run(
+        container,
+        ScriptHook::Timer,
+        ScriptCtx {
+            self_id: obj_id,
+            actor_id: None,
+            room_id,
+            message: None,
+        },
+    );
+}
+
+/// Items can only be used if they have a use script
+pub fn use_item(container: &mut Container, mob_id: MobId, item_id: ItemId) -> Result<()> {
+    let item_label = container.labels.get_label_f(item_id).to_string();
+
+    if !container.scripts.has_hook(item_id, ScriptHook::Use) {
+        container
+            .outputs
+            .private(mob_id, comm::use_nothing_happens(&item_label));
+        return Err(Error::InvalidArgumentFailure);
+    }
+
+    let room_id = container.locations.get(mob_id).as_result()?;
+    let mob_label = container.labels.get_label_f(mob_id);
+    container
+        .outputs
+        .message(mob_id, room_id, comm::use_item(mob_label, &item_label));
+
+    run(
+        container,
+        ScriptHook::Use,
+        ScriptCtx {
+            self_id: item_id,
+            actor_id: Some(mob_id),
+            room_id: Some(room_id),
+            message: None,
+        },
+    );
+
+    Ok(())
+}
+
+fn find_room(container: &Container, obj_id: ObjId) -> Option<RoomId> {
+    container
+        .locations
+        .list_parents_inclusive(obj_id)
+        .into_iter()
+        .find(|id| container.rooms.exists(*id))
+}
+
+fn run(container: &mut Container, hook: ScriptHook, ctx: ScriptCtx) {
+    let (code, refs) = match container.scripts.get(ctx.self_id) {
+        Some(script) => match script.get_code(hook) {
+            Some(code) => (code.to_string(), script.refs.clone()),
+            None => return,
+        },
+        None => return,
+    };
+
+    log::debug!("{:?} running script {:?}", ctx.self_id, hook);
+
+    let commands = match execute(container, &code, &refs, &ctx) {
+        Ok(commands) => commands,
+        Err(err) => {
+            log::warn!("{:?} script {:?} failed: {}", ctx.self_id, hook, err);
+            return;
+        }
+    };
+
+    for command in commands {
+        if let Err(err) = apply(container, &command) {
+            log::warn!(
+                "{:?} script {:?} fail to apply {:?}: {:?}",
+                ctx.self_id,
+                hook,
+                command,
+                err
+            );
+        }
+    }
+}
+
+fn to_script_id(obj_id: Option<ObjId>) -> i64 {
+    obj_id.map(|id| id.as_u32() as i64).unwrap_or(-1)
+}
+
+fn to_obj_id(id: i64) -> std::result::Result<ObjId, Box<EvalAltResult>> {
+    if id < 0 || id > u32::MAX as i64 {
+        return Err(format!("invalid object id {}", id).into());
+    }
+    Ok(ObjId(id as u32))
+}
+
+fn execute(
+    container: &Container,
+    code: &str,
+    refs: &HashMap<String, ObjId>,
+    ctx: &ScriptCtx,
+) -> std::result::Result<Vec<ScriptCommand>, Box<EvalAltResult>> {
+    let mut known = HashMap::new();
+    for obj_id in vec![Some(ctx.self_id), ctx.actor_id, ctx.room_id]
+        .into_iter()
+        .flatten()
+    {
+        let tags = container
+            .tags
+            .get_tags(obj_id)
+            .map(|tags| tags.iter().cloned().collect())
+            .and_then(|tags| container.tags.resolve_strings(&tags))
+            .unwrap_or_default();
+
+        known.insert(
+            obj_id.as_u32() as i64,
+            KnownObj {
+                label: container.labels.get_label_f(obj_id).to_string(),
+                tags,
+            },
+        );
+    }
+
+    let commands = Rc::new(RefCell::new(vec![]));
+    let engine = create_engine(Rc::new(known), commands.clone());
+
+    let mut scope = Scope::new();
+    for (name, ref_id) in refs {
+        scope.push_constant(name.as_str(), to_script_id(Some(*ref_id)));
+    }
+    scope.push_constant("self_id", to_script_id(Some(ctx.self_id)));
+    scope.push_constant("actor_id", to_script_id(ctx.actor_id));
+    scope.push_constant("room_id", to_script_id(ctx.room_id));
+    scope.push_constant("message", ctx.message.clone().unwrap_or_default());
+
+    engine.run_with_scope(&mut scope, code)?;
+
+    let result = commands.replace(vec![]);
+    Ok(result)
+}
+
+fn create_engine(
+    known: Rc<HashMap<i64, KnownObj>>,
+    commands: Rc<RefCell<Vec<ScriptCommand>>>,
+) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    engine.set_max_array_size(MAX_ARRAY_SIZE);
+    engine.set_max_map_size(MAX_MAP_SIZE);
+    engine.disable_symbol("eval");
+    engine.on_print(|text| log::debug!("script print: {}", text));
+    engine.on_debug(|text, _, _| log::debug!("script debug: {}", text));
+
+    let push = move |command: ScriptCommand| commands.borrow_mut().push(command);
+
+    let cmd = push.clone();
+    engine.register_fn("send", move |obj_id: i64, msg: &str| {
+        cmd(ScriptCommand::Send {
+            obj_id: to_obj_id(obj_id)?,
+            msg: msg.to_string(),
+        });
+        Ok::<(), Box<EvalAltResult>>(())
+    });
+
+    let cmd = push.clone();
+    engine.register_fn("broadcast", move |room_id: i64, msg: &str| {
+        cmd(ScriptCommand::Broadcast {
+            room_id: to_obj_id(room_id)?,
+            msg: msg.to_string(),
+        });
+        Ok::<(), Box<EvalAltResult>>(())
+    });
+
+    let cmd = push.clone();
+    engine.register_fn("move_to", move |obj_id: i64, room_id: i64| {
+        cmd(ScriptCommand::Move {
+            obj_id: to_obj_id(obj_id)?,
+            room_id: to_obj_id(room_id)?,
+        });
+        Ok::<(), Box<EvalAltResult>>(())
+    });
+
+    let cmd = push.clone();
+    engine.register_fn("spawn_at", move |prefab_id: i64, location_id: i64| {
+        cmd(ScriptCommand::Spawn {
+            prefab_id: StaticId(to_obj_id(prefab_id)?.as_u32()),
+            location_id: to_obj_id(location_id)?,
+        });
+        Ok::<(), Box<EvalAltResult>>(())
+    });
+
+    let cmd = push.clone();
+    engine.register_fn("set_tag", move |obj_id: i64, tag: &str| {
+        cmd(ScriptCommand::SetTag {
+            obj_id: to_obj_id(obj_id)?,
+            tag: tag.to_string(),
+        });
+        Ok::<(), Box<EvalAltResult>>(())
+    });
+
+    let cmd = push;
+    engine.register_fn("remove_tag", move |obj_id: i64, tag: &str| {
+        cmd(ScriptCommand::RemoveTag {
+            obj_id: to_obj_id(obj_id)?,
+            tag: tag.to_string(),
+        });
+        Ok::<(), Box<EvalAltResult>>(())
+    });
+
+    let objs = known.clone();
+    engine.register_fn("has_tag", move |obj_id: i64, tag: &str| {
+        objs.get(&obj_id)
+            .map(|obj| obj.tags.iter().any(|i| i == tag))
+            .unwrap_or(false)
+    });
+
+    let objs = known;
+    engine.register_fn("label", move |obj_id: i64| {
+        objs.get(&obj_id)
+            .map(|obj| obj.label.clone())
+            .unwrap_or_default()
+    });
+
+    engine
+}
+
+fn apply(container: &mut Container, command: &ScriptCommand) -> Result<()> {
+    match command {
+        ScriptCommand::Send { obj_id, msg } => {
+            container.outputs.private(*obj_id, msg.clone());
+        }
+        ScriptCommand::Broadcast { room_id, msg } => {
+            container.outputs.broadcast(None, *room_id, msg.clone());
+        }
+        ScriptCommand::Move { obj_id, room_id } => {
+            if !container.objects.exists(*obj_id) || !container.rooms.exists(*room_id) {
+                return Err(Error::InvalidArgumentFailure);
+            }
+
+            container.locations.set(*obj_id, *room_id);
+
+            if container.mobs.exists(*obj_id) {
+                container.scripts.set_mob_room(*obj_id, Some(*room_id));
+                container.memories.add(*obj_id, *room_id)?;
+                actions::look(container, *obj_id)?;
+            }
+        }
+        ScriptCommand::Spawn {
+            prefab_id,
+            location_id,
+        } => {
+            if !container.objects.exists(*location_id) {
+                return Err(Error::InvalidArgumentFailure);
+            }
+
+            let spawn_id = Loader::spawn_at(container, *prefab_id, *location_id)?;
+            if container.mobs.exists(spawn_id) && container.rooms.exists(*location_id) {
+                container.scripts.set_mob_room(spawn_id, Some(*location_id));
+            }
+        }
+        ScriptCommand::SetTag { obj_id, tag } => {
+            if !container.objects.exists(*obj_id) {
+                return Err(Error::InvalidArgumentFailure);
+            }
+
+            let tag_id = container.tags.get_id(tag);
+            container.tags.add(*obj_id, tag_id);
+        }
+        ScriptCommand::RemoveTag { obj_id, tag } => {
+            if let Some(tag_id) = container.tags.find_id(tag) {
+                container.tags.remove_tag(*obj_id, tag_id);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::loader::Loader;
+    use crate::game::system::script_system;
+    use commons::TotalTime;
+
+    const SCRIPTS: &str = r##"
+objects.0 {
+  id: 0
+  label: "room"
+  room {}
+  script {
+    on_timer: """set_tag(self_id, "ticked");"""
+    timer_interval: 2.0
+  }
+}
+objects.1 {
+  id: 1
+  label: "broken"
+  parent: 0
+  script {
+    on_timer: """set_tag(room_id, "broken"); loop {}"""
+    timer_interval: 1.0
+  }
+}
+"##;
+
+    fn has_tag(container: &Container, obj_id: ObjId, tag: &str) -> bool {
+        container
+            .tags
+            .find_id(tag)
+            .map(|tag_id| container.tags.has(obj_id, tag_id))
+            .unwrap_or(false)
+    }
+
+    #[test]
+    fn test_timer_scripts() {
+        let mut container = Container::new();
+        let lc = Loader::load_hocon(&mut container, SCRIPTS).unwrap();
+        let room_id = lc.get(0);
+
+        // first tick only schedule the timer
+        script_system::run(&mut container);
+        assert!(!has_tag(&container, room_id, "ticked"));
+
+        container.time.total = TotalTime(1.0);
+        script_system::run(&mut container);
+        assert!(!has_tag(&container, room_id, "ticked"));
+
+        container.time.total = TotalTime(2.0);
+        script_system::run(&mut container);
+        assert!(has_tag(&container, room_id, "ticked"));
+
+        // scripts that exceed the limits are aborted without apply any change
+        assert!(!has_tag(&container, room_id, "broken"));
+    }
+
+    const ENTER_AND_LEAVE: &str = r##"
+objects.0 {
+  id: 0
+  label: "street"
+  room {}
+  script {
+    on_leave: """set_tag(actor_id, "left");"""
+  }
+}
+objects.1 {
+  id: 1
+  label: "house"
+  room {}
+  script {
+    on_enter: """set_tag(actor_id, "entered");"""
+  }
+}
+objects.2 {
+  id: 2
+  label: "cat"
+  parent: 0
+  mob {
+    attack: 1
+    damage_max: 1
+    damage_min: 1
+    defense: 1
+    pv: 1
+    pv_max: 1
+    xp: 1
+  }
+}
+prefabs.3 {
+  id: 3
+  label: "dog"
+  mob {
+    attack: 1
+    damage_max: 1
+    damage_min: 1
+    defense: 1
+    pv: 1
+    pv_max: 1
+    xp: 1
+  }
+}
+"##;
+
+    #[test]
+    fn test_enter_and_leave_scripts_for_any_room_change() {
+        let mut container = Container::new();
+        let lc = Loader::load_hocon(&mut container, ENTER_AND_LEAVE).unwrap();
+        let house_id = lc.get(1);
+        let cat_id = lc.get(2);
+
+        // mobs loaded with the game do not trigger hooks
+        script_system::init(&mut container);
+        script_system::run(&mut container);
+        assert!(!has_tag(&container, cat_id, "left"));
+        assert!(!has_tag(&container, cat_id, "entered"));
+
+        // moved without walk, like enter, out or ship travel
+        container.locations.set(cat_id, house_id);
+        script_system::run(&mut container);
+        assert!(has_tag(&container, cat_id, "left"));
+        assert!(has_tag(&container, cat_id, "entered"));
+
+        // spawn
+        let dog_id = Loader::spawn_at(&mut container, StaticId(3), house_id).unwrap();
+        script_system::run(&mut container);
+        assert!(has_tag(&container, dog_id, "entered"));
+        assert!(!has_tag(&container, dog_id, "left"));
+    }
+
+    #[test]
+    fn test_load_should_fail_for_invalid_scripts() {
+        let mut container = Container::new();
+        let invalid = SCRIPTS.replace("loop {}", "loop {");
+        assert!(Loader::load_hocon(&mut container, &invalid).is_err());
+    }
+}
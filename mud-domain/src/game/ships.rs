@@ -83,6 +83,8 @@ pub enum LandState {
     Running {
         stage: u32,
         complete_time: TotalTime,
+        /// waiting for the weather at the landing zone, only announced when it starts
+        holding: bool,
     },
 }
 
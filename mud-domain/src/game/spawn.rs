@@ -3,9 +3,11 @@ use super::mob::*;
 use super::room::RoomId;
 use crate::errors::{Error, Result};
 use crate::game::ai::AiCommand;
+use crate::game::calendar::DayPeriod;
 use crate::game::loader::dto::AiData;
 use crate::game::loader::{dto::StaticId, Loader};
 use crate::game::location::LocationId;
+use crate::game::zone::ZoneId;
 use commons::*;
 
 use rand::Rng;
@@ -41,11 +43,19 @@ pub struct SpawnBuilder {
     pub prefab_id: StaticId,
     pub next: Option<TotalTime>,
     pub ai_overwrite: Option<AiData>,
+    pub followers: Vec<StaticId>,
+    pub avoid_players: bool,
+    pub period: Option<DayPeriod>,
 }
 
 impl SpawnBuilder {
     pub fn create_spawn(&self, id: ObjId) -> Spawn {
-        Spawn::new(id, self.prefab_id, self.max, self.delay_min, self.delay_max)
+        let mut spawn = Spawn::new(id, self.prefab_id, self.max, self.delay_min, self.delay_max);
+        spawn.ai_overwrite = self.ai_overwrite.clone();
+        spawn.followers = self.followers.clone();
+        spawn.avoid_players = self.avoid_players;
+        spawn.period = self.period;
+        spawn
     }
 }
 
@@ -58,7 +68,17 @@ pub struct Spawn {
     pub next: TotalTime,
     /// zones and rooms are valid, when empty, parent objects is used
     pub locations_id: Vec<LocationId>,
+    /// any room inside the zone is a valid location
+    pub zone_id: Option<ZoneId>,
+    /// any room in this distance from the spawn room is a valid location
+    pub radius: Option<u32>,
     pub ai_overwrite: Option<AiData>,
+    /// prefabs spawned together that follow the spawned mob, they do not count for max
+    pub followers: Vec<StaticId>,
+    /// never spawn in a room where there is a player
+    pub avoid_players: bool,
+    /// only spawn during this period of the day
+    pub period: Option<DayPeriod>,
 }
 
 impl Spawn {
@@ -79,7 +99,12 @@ impl Spawn {
             prefab_id: prefab_id,
             next: TotalTime(0.0),
             locations_id: vec![],
+            zone_id: None,
+            radius: None,
             ai_overwrite: None,
+            followers: vec![],
+            avoid_players: false,
+            period: None,
         }
     }
 }
@@ -4,33 +4,55 @@ use crate::game::ai::AiCommand;
 use crate::game::combat;
 use crate::game::container::Container;
 use crate::game::location::Locations;
-use crate::game::mob::{MobCommand, MobRepository};
+use crate::game::mob::{MobCommand, MobId, MobRepository};
 use crate::game::ownership::Ownerships;
+use crate::game::pathfinding;
+use crate::game::room::RoomId;
+use commons::prob;
 use commons::ObjId;
+use rand::prelude::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Chance in each tick of a idle patrolling mob to move into next room
+pub const PATROL_MOVE_CHANCE: f64 = 0.2;
 
 pub fn run(container: &mut Container) {
     let mut to_drop = vec![];
 
-    for ai in container.ai.list() {
-        let result = match ai.command {
-            AiCommand::Aggressive => run_aggressive(
-                &mut container.mobs,
-                &container.locations,
-                &container.ownership,
-                ai.id,
-            ),
-            AiCommand::Extract { .. } => {
+    let ais: Vec<(ObjId, AiCommand)> = container
+        .ai
+        .list()
+        .map(|ai| (ai.id, ai.command.clone()))
+        .collect();
+
+    for (ai_id, command) in ais {
+        let result = match command {
+            AiCommand::Aggressive => run_aggressive(container, ai_id).map(|_| ()),
+            AiCommand::Passive => run_passive(container, ai_id).map(|_| ()),
+            AiCommand::AggressivePatrolHome { distance } => {
+                run_aggressive_patrol_home(container, ai_id, distance)
+            }
+            AiCommand::Roam { distance } => run_roam(container, ai_id, distance),
+            AiCommand::FollowAndProtect { target_id } => {
+                run_follow_and_protect(container, ai_id, target_id)
+            }
+            AiCommand::Hauler { from, to } => run_hauler(container, ai_id, from, to),
+            AiCommand::Extract { from } => {
                 // drop any item
-                let items: Vec<ObjId> = container.locations.list_at(ai.id).collect();
-                to_drop.push((ai.id, items));
-                Ok(())
+                let items: Vec<ObjId> = container.locations.list_at(ai_id).collect();
+                if items.is_empty() {
+                    resume_extract(container, ai_id, from)
+                } else {
+                    to_drop.push((ai_id, items));
+                    Ok(())
+                }
             }
             _ => Ok(()),
         };
 
         match result {
             Err(e) => {
-                log::warn!("fail to run ai for {:?}: {:?}", ai.id, e);
+                log::warn!("fail to run ai for {:?}: {:?}", ai_id, e);
             }
             _ => {}
         }
@@ -38,7 +60,7 @@ pub fn run(container: &mut Container) {
 
     for (obj_id, items) in to_drop {
         for item_id in items {
-            match game::actions_items::do_drop(container, obj_id, item_id) {
+            match game::actions_items::do_drop(container, obj_id, item_id, None) {
                 Err(e) => log::warn!("{:?} fail to drop {:?}: {:?}", obj_id, item_id, e),
                 _ => {}
             }
@@ -46,29 +68,245 @@ pub fn run(container: &mut Container) {
     }
 }
 
-fn run_aggressive(
-    mobs: &mut MobRepository,
-    locations: &Locations,
-    owners: &Ownerships,
-    mob_id: ObjId,
+/// Return true if a attack was started
+fn run_aggressive(container: &mut Container, mob_id: ObjId) -> Result<bool> {
+    let mob = container.mobs.get(mob_id).as_result_str("mob not found")?;
+
+    if !mob.command.is_idle() {
+        return Ok(false);
+    }
+
+    let location_id = container
+        .locations
+        .get(mob_id)
+        .as_result_str("mob has no location")?;
+
+    let target_id = container
+        .locations
+        .list_at(location_id)
+        .find(|target_id| combat::is_valid_attack_target(container, mob_id, *target_id));
+
+    match target_id {
+        Some(target_id) => {
+            set_attack(container, mob_id, target_id)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Combat is already returned when attacked, passive only need to resume fight against anyone
+/// still attacking it, like when the attacker come back after a flee
+///
+/// Return true if a attack was started
+fn run_passive(container: &mut Container, mob_id: MobId) -> Result<bool> {
+    let mob = container.mobs.get(mob_id).as_result_str("mob not found")?;
+    if !mob.command.is_idle() {
+        return Ok(false);
+    }
+
+    match combat::list_attackers(container, mob_id).into_iter().next() {
+        Some(attacker_id) => {
+            set_attack(container, mob_id, attacker_id)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+fn run_aggressive_patrol_home(
+    container: &mut Container,
+    mob_id: MobId,
+    distance: u32,
+) -> Result<()> {
+    run_patrol(container, mob_id, distance, run_aggressive)
+}
+
+fn run_roam(container: &mut Container, mob_id: MobId, distance: u32) -> Result<()> {
+    run_patrol(container, mob_id, distance, run_passive)
+}
+
+/// Keep moving randomly into distance from home, `act` is called first in each tick and the mob
+/// only move if it return false
+fn run_patrol(
+    container: &mut Container,
+    mob_id: MobId,
+    distance: u32,
+    act: fn(&mut Container, MobId) -> Result<bool>,
+) -> Result<()> {
+    let mob = container.mobs.get(mob_id).as_result_str("mob not found")?;
+    if !mob.command.is_idle() || !mob.is_idle() {
+        return Ok(());
+    }
+
+    let location_id = container
+        .locations
+        .get(mob_id)
+        .as_result_str("mob has no location")?;
+
+    let ai = container.ai.get_mut(mob_id).as_result()?;
+    let home_id = *ai.home_id.get_or_insert(location_id);
+
+    // out of patrol area, ignore anyone until be back
+    let valid_rooms = pathfinding::rooms_within(container, home_id, distance);
+    if !valid_rooms.contains_key(&location_id) {
+        log::debug!("{:?} out of patrol area, returning home", mob_id);
+        return pathfinding::move_towards(container, mob_id, home_id);
+    }
+
+    if act(container, mob_id)? {
+        return Ok(());
+    }
+
+    let mut rng = StdRng::seed_from_u64(prob::seed_for(mob_id, container.time.tick));
+    if distance == 0 || !rng.gen_bool(PATROL_MOVE_CHANCE) {
+        return Ok(());
+    }
+
+    let candidates: Vec<_> = pathfinding::list_steps(container, location_id)
+        .into_iter()
+        .filter(|step| valid_rooms.contains_key(&step.get_to_id()))
+        .collect();
+
+    if candidates.is_empty() {
+        return Ok(());
+    }
+
+    let step = &candidates[rng.gen_range(0..candidates.len())];
+    log::debug!("{:?} patrolling to {:?}", mob_id, step);
+    pathfinding::execute_step(container, mob_id, step)
+}
+
+fn run_follow_and_protect(
+    container: &mut Container,
+    mob_id: MobId,
+    target_id: ObjId,
 ) -> Result<()> {
-    let mob = mobs.get(mob_id).as_result_str("mob not found")?;
+    let target_location_id = match container.locations.get(target_id) {
+        Some(location_id) if container.mobs.exists(target_id) => location_id,
+        _ => {
+            log::info!("{:?} follow target {:?} is gone, idling", mob_id, target_id);
+            let ai = container.ai.get_mut(mob_id).as_result()?;
+            ai.command = AiCommand::Idle;
+            return Ok(());
+        }
+    };
 
+    let mob = container.mobs.get(mob_id).as_result_str("mob not found")?;
     if !mob.command.is_idle() {
         return Ok(());
     }
 
-    let location_id = locations.get(mob_id).as_result_str("mob has no location")?;
+    let location_id = container
+        .locations
+        .get(mob_id)
+        .as_result_str("mob has no location")?;
 
-    for target_id in locations.list_at(location_id) {
-        if combat::is_valid_attack_target(&mobs, &owners, mob_id, target_id) {
-            let mob = mobs
-                .get_mut(mob_id)
-                .as_result_string(|| format!("mob {:?} not found", mob_id).into())?;
-            match mob.set_action_attack(target_id) {
-                Ok(()) => log::info!("{:?} aggressive attack {:?}", mob_id, target_id),
-                Err(_e) => log::warn!("{:?} fail to attack {:?}", mob_id, target_id),
+    if location_id != target_location_id {
+        if mob.is_idle() {
+            pathfinding::move_towards(container, mob_id, target_location_id)?;
+        }
+        return Ok(());
+    }
+
+    // protect by joining the fight of the target or against who is attacking it
+    let enemy_id = match container.mobs.get(target_id).map(|target| &target.command) {
+        Some(MobCommand::Kill {
+            target_id: enemy_id,
+        }) => Some(*enemy_id),
+        _ => None,
+    }
+    .or_else(|| {
+        combat::list_attackers(container, target_id)
+            .into_iter()
+            .next()
+    })
+    .filter(|enemy_id| combat::is_valid_attack_target(container, mob_id, *enemy_id));
+
+    if let Some(enemy_id) = enemy_id {
+        set_attack(container, mob_id, enemy_id)?;
+    }
+
+    Ok(())
+}
+
+/// Storages can be a room or a container, items carried by the mob that are not equipped are
+/// considered cargo. Money is never hauled.
+fn run_hauler(container: &mut Container, mob_id: MobId, from: ObjId, to: ObjId) -> Result<()> {
+    let mob = container.mobs.get(mob_id).as_result_str("mob not found")?;
+    if !mob.command.is_idle() || !mob.is_idle() {
+        return Ok(());
+    }
+
+    let location_id = container
+        .locations
+        .get(mob_id)
+        .as_result_str("mob has no location")?;
+
+    let cargo: Vec<ObjId> = container
+        .locations
+        .list_at(mob_id)
+        .filter(|item_id| {
+            container
+                .items
+                .get(*item_id)
+                .map(|item| !item.flags.is_money)
+                .unwrap_or(false)
+        })
+        .filter(|item_id| !container.equips.is_equipped(mob_id, *item_id))
+        .collect();
+
+    if !cargo.is_empty() {
+        let to_room_id = get_storage_room(container, to)?;
+        if location_id != to_room_id {
+            return pathfinding::move_towards(container, mob_id, to_room_id);
+        }
+
+        for item_id in cargo {
+            let result = if to == to_room_id {
+                game::actions_items::do_drop(container, mob_id, item_id, None)
+            } else {
+                game::actions_items::do_put(container, mob_id, item_id, to, None)
+            };
+
+            if let Err(e) = result {
+                log::warn!("{:?} fail to unload {:?}: {:?}", mob_id, item_id, e);
             }
+        }
+
+        return Ok(());
+    }
+
+    let from_room_id = get_storage_room(container, from)?;
+    let to_haul: Vec<ObjId> = container
+        .locations
+        .list_at(from)
+        .filter(|item_id| {
+            container
+                .items
+                .get(*item_id)
+                .map(|item| !item.flags.is_stuck && !item.flags.is_money)
+                .unwrap_or(false)
+        })
+        .collect();
+
+    if to_haul.is_empty() {
+        return Ok(());
+    }
+
+    if location_id != from_room_id {
+        return pathfinding::move_towards(container, mob_id, from_room_id);
+    }
+
+    let inventory_id = if from == from_room_id {
+        None
+    } else {
+        Some(from)
+    };
+
+    for item_id in to_haul {
+        if let Err(e) = game::actions_items::do_pickup(container, mob_id, item_id, inventory_id) {
+            log::debug!("{:?} fail to load {:?}: {:?}", mob_id, item_id, e);
             break;
         }
     }
@@ -76,6 +314,50 @@ fn run_aggressive(
     Ok(())
 }
 
+/// Extraction stop when inventory is full or when reloaded, start it again
+fn resume_extract(container: &mut Container, mob_id: MobId, extractable_id: ObjId) -> Result<()> {
+    let mob = container.mobs.get(mob_id).as_result_str("mob not found")?;
+    if !mob.command.is_idle() || !mob.is_idle() {
+        return Ok(());
+    }
+
+    let location_id = container
+        .locations
+        .get(mob_id)
+        .as_result_str("mob has no location")?;
+
+    if container.locations.get(extractable_id) != Some(location_id) {
+        return Ok(());
+    }
+
+    game::actions::extract(container, mob_id, location_id, extractable_id)
+}
+
+fn set_attack(container: &mut Container, mob_id: MobId, target_id: MobId) -> Result<()> {
+    let mob = container
+        .mobs
+        .get_mut(mob_id)
+        .as_result_string(|| format!("mob {:?} not found", mob_id).into())?;
+    match mob.set_action_attack(target_id) {
+        Ok(()) => log::info!("{:?} ai attack {:?}", mob_id, target_id),
+        Err(_e) => log::warn!("{:?} fail to attack {:?}", mob_id, target_id),
+    }
+    Ok(())
+}
+
+fn get_storage_room(container: &Container, storage_id: ObjId) -> Result<RoomId> {
+    if container.rooms.exists(storage_id) {
+        return Ok(storage_id);
+    }
+
+    container
+        .locations
+        .list_parents(storage_id)
+        .into_iter()
+        .find(|id| container.rooms.exists(*id))
+        .as_result_string(|| format!("storage {:?} is not in a room", storage_id))
+}
+
 #[cfg(test)]
 mod test {
     use crate::game::loader::Loader;
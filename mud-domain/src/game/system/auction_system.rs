@@ -0,0 +1,13 @@
+use crate::game::actions_auction;
+use crate::game::container::Container;
+
+/// Close expired auction listings
+pub fn run(container: &mut Container) {
+    let total_time = container.time.total;
+
+    for item_id in container.auctions.list_expired(total_time) {
+        if let Err(e) = actions_auction::expire(container, item_id) {
+            log::warn!("{:?} fail to expire auction: {:?}", item_id, e);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::game::calendar::{self, DayPeriod};
+use crate::game::comm;
+use crate::game::container::Container;
+use crate::game::room::RoomId;
+use commons::TotalTime;
+
+/// Announce sunrise and sunset to all outdoor rooms
+pub fn run(container: &mut Container) {
+    let now = container.time.total;
+    let previous = TotalTime(now.as_seconds_f64() - container.time.delta.as_seconds_f32() as f64);
+
+    let calendar = &container.config.calendar;
+    let period = calendar.period(now);
+    if calendar.period(previous) == period {
+        return;
+    }
+
+    log::debug!("day period changed to {:?}", period);
+
+    let msg = match period {
+        DayPeriod::Day => comm::calendar_sunrise(),
+        DayPeriod::Night => comm::calendar_sunset(),
+    };
+
+    let mut rooms: Vec<RoomId> = container
+        .rooms
+        .list()
+        .cloned()
+        .filter(|room_id| calendar::is_outdoor(container, *room_id))
+        .collect();
+    rooms.sort();
+
+    for room_id in rooms {
+        container.outputs.broadcast(None, room_id, msg.clone());
+    }
+}
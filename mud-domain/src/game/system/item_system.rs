@@ -19,9 +19,7 @@ impl System for DecaySystem {
         let to_remove: Vec<ObjId> = container
             .triggers
             .list(EventKind::Decay)
-            .map(|event| match event {
-                Event::Obj { obj_id, .. } => *obj_id,
-            })
+            .map(|event| event.get_obj_id())
             .collect();
 
         for obj_id in to_remove {
@@ -4,12 +4,20 @@ use crate::game::outputs::Outputs;
 use crate::game::system::item_system::DecaySystem;
 
 pub mod ai_system;
+pub mod auction_system;
 pub mod avatars_systems;
+pub mod calendar_system;
 pub mod item_system;
+pub mod needs_system;
+pub mod quest_system;
 pub mod random_room_generators_system;
 pub mod rest_system;
+pub mod script_system;
 pub mod ship_system;
 pub mod spawn_system;
+pub mod trade_system;
+pub mod vendor_system;
+pub mod weather_system;
 
 trait System {
     fn tick(&mut self, container: &mut Container) -> Result<()>;
@@ -20,7 +28,9 @@ pub struct Systems {
 }
 
 impl Systems {
-    pub fn new(_container: &mut Container) -> Self {
+    pub fn new(container: &mut Container) -> Self {
+        script_system::init(container);
+
         Systems {
             decay_system: DecaySystem::new(),
         }
@@ -32,14 +42,22 @@ impl Systems {
             .timer
             .tick(container.time.total, &mut container.triggers);
         // execute jobs
+        calendar_system::run(container);
+        weather_system::run(container);
         self.decay_system.tick(container).unwrap();
         spawn_system::run(container);
         ai_system::run(container);
         super::mob::system_run(container);
+        needs_system::run(container);
         rest_system::run(container);
         ship_system::tick(container);
         random_room_generators_system::run(container);
         avatars_systems::run(container);
+        vendor_system::run(container);
+        trade_system::run(container);
+        auction_system::run(container);
+        quest_system::run(container);
+        script_system::run(container);
         container.triggers.clear();
     }
 }
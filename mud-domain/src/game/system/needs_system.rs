@@ -0,0 +1,31 @@
+use crate::game::comm;
+use crate::game::container::Container;
+
+/// drain mobs needs and remove expired effects
+pub fn run(container: &mut Container) {
+    let total_time = container.time.total;
+    let delta = container.time.delta;
+    let mut messages = vec![];
+
+    for mob in container.mobs.list_mut() {
+        if let Some(needs) = mob.needs.as_mut() {
+            for (kind, level) in needs.drain(delta) {
+                messages.push((mob.id, comm::needs_changed(kind, level)));
+            }
+        }
+
+        let mob_id = mob.id;
+        mob.effects.retain(|effect| {
+            if total_time.is_after(effect.expire) {
+                messages.push((mob_id, comm::effect_expired(effect.kind)));
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    for (mob_id, msg) in messages {
+        container.outputs.private(mob_id, msg);
+    }
+}
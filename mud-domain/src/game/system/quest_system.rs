@@ -0,0 +1,286 @@
+use crate::errors::Result;
+use crate::game::container::Container;
+use crate::game::loader::dto::StaticId;
+use crate::game::quests::{QuestId, QuestObjective};
+use crate::game::triggers::{Event, EventKind};
+use crate::game::{actions_quest, comm};
+use commons::{unwrap_or_continue, ObjId, PlayerId};
+
+/// Something a player did that can count for a quest objective
+#[derive(Debug, Clone, PartialEq)]
+enum Progress {
+    Kill(StaticId),
+    Deliver {
+        prefab_id: StaticId,
+        target_id: ObjId,
+        amount: u32,
+    },
+    Extract(StaticId),
+}
+
+impl Progress {
+    fn get_amount(&self) -> u32 {
+        match self {
+            Progress::Deliver { amount, .. } => *amount,
+            _ => 1,
+        }
+    }
+}
+
+/// Update quests progress from events and players location
+pub fn run(container: &mut Container) {
+    let mut progress: Vec<(PlayerId, Progress)> = vec![];
+
+    for event in container.triggers.list(EventKind::Killed) {
+        if let Event::Killed {
+            prefab_id: Some(prefab_id),
+            killer_id: Some(killer_id),
+            ..
+        } = event
+        {
+            let player_id = unwrap_or_continue!(find_player(container, *killer_id));
+            progress.push((player_id, Progress::Kill(*prefab_id)));
+        }
+    }
+
+    for event in container.triggers.list(EventKind::Give) {
+        if let Event::Give {
+            obj_id,
+            from_id,
+            item_id,
+            amount,
+        } = event
+        {
+            let player_id = unwrap_or_continue!(container.players.find_from_mob(*from_id));
+            let prefab_id = unwrap_or_continue!(container.objects.get_prefab_id(*item_id));
+            progress.push((
+                player_id,
+                Progress::Deliver {
+                    prefab_id,
+                    target_id: *obj_id,
+                    amount: *amount,
+                },
+            ));
+        }
+    }
+
+    for event in container.triggers.list(EventKind::Extract) {
+        if let Event::Extract { obj_id, item_id } = event {
+            let player_id = unwrap_or_continue!(find_player(container, *obj_id));
+            let prefab_id = unwrap_or_continue!(container.objects.get_prefab_id(*item_id));
+            progress.push((player_id, Progress::Extract(prefab_id)));
+        }
+    }
+
+    let mut updated: Vec<(PlayerId, QuestId, usize, u32)> = vec![];
+
+    for (player_id, progress) in progress {
+        let log = unwrap_or_continue!(container.quests.get_log(player_id));
+        for quest_progress in &log.active {
+            let quest = unwrap_or_continue!(container.quests.get(quest_progress.quest_id));
+            for (index, objective) in quest.objectives.iter().enumerate() {
+                if is_progress_of(objective, &progress) {
+                    updated.push((player_id, quest.id, index, progress.get_amount()));
+                }
+            }
+        }
+    }
+
+    // reach objectives are checked by the avatar current location
+    for log in container.quests.list_logs() {
+        let mob_id = unwrap_or_continue!(container.players.get_mob(log.id));
+        let room_id = unwrap_or_continue!(container.locations.get(mob_id));
+
+        for quest_progress in &log.active {
+            let quest = unwrap_or_continue!(container.quests.get(quest_progress.quest_id));
+            for (index, objective) in quest.objectives.iter().enumerate() {
+                match objective {
+                    QuestObjective::Reach { room_id: target_id }
+                        if *target_id == room_id
+                            && quest_progress.progress.get(index) == Some(&0) =>
+                    {
+                        updated.push((log.id, quest.id, index, 1));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    for (player_id, quest_id, index, amount) in updated {
+        if let Err(err) = add_progress(container, player_id, quest_id, index, amount) {
+            log::warn!(
+                "{:?} fail to update quest {:?} progress: {:?}",
+                player_id,
+                quest_id,
+                err
+            );
+        }
+    }
+}
+
+/// Kills and extractions from followers count to the owner
+fn find_player(container: &Container, mob_id: ObjId) -> Option<PlayerId> {
+    container.players.find_from_mob(mob_id).or_else(|| {
+        container
+            .ownership
+            .get_owner(mob_id)
+            .and_then(|owner_id| container.players.find_from_mob(owner_id))
+    })
+}
+
+fn is_progress_of(objective: &QuestObjective, progress: &Progress) -> bool {
+    match (objective, progress) {
+        (QuestObjective::Kill { prefab_id, .. }, Progress::Kill(killed_id)) => {
+            prefab_id == killed_id
+        }
+        (
+            QuestObjective::Deliver {
+                prefab_id,
+                target_id,
+                ..
+            },
+            Progress::Deliver {
+                prefab_id: given_id,
+                target_id: receiver_id,
+                ..
+            },
+        ) => prefab_id == given_id && target_id == receiver_id,
+        (QuestObjective::Extract { prefab_id, .. }, Progress::Extract(extracted_id)) => {
+            prefab_id == extracted_id
+        }
+        _ => false,
+    }
+}
+
+fn add_progress(
+    container: &mut Container,
+    player_id: PlayerId,
+    quest_id: QuestId,
+    index: usize,
+    amount: u32,
+) -> Result<()> {
+    let quest = container.quests.get(quest_id).cloned();
+    let quest = match quest {
+        Some(quest) => quest,
+        None => return Ok(()),
+    };
+
+    let objective = &quest.objectives[index];
+    let log = match container.quests.get_log_mut(player_id) {
+        Some(log) => log,
+        None => return Ok(()),
+    };
+
+    let quest_progress = match log.active.iter_mut().find(|i| i.quest_id == quest_id) {
+        Some(quest_progress) => quest_progress,
+        // already completed by a previous event
+        None => return Ok(()),
+    };
+
+    let current = match quest_progress.progress.get_mut(index) {
+        Some(current) if *current < objective.get_amount() => {
+            *current = (*current + amount).min(objective.get_amount());
+            *current
+        }
+        _ => return Ok(()),
+    };
+    let is_complete = quest_progress.is_complete(&quest);
+
+    if let Some(mob_id) = container.players.get_mob(player_id) {
+        let objective_desc = actions_quest::describe_objective(container, objective, current);
+        let quest_label = container.labels.get_label_f(quest_id);
+        container
+            .outputs
+            .private(mob_id, comm::quest_progress(quest_label, &objective_desc));
+    }
+
+    if is_complete {
+        actions_quest::complete(container, player_id, quest_id)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::inventory::Inventory;
+    use crate::game::loader::Loader;
+    use crate::game::mob::MobId;
+    use crate::game::{actions_items, builder};
+
+    const SCENERY: &str = r##"
+objects.0 {
+  id: 0
+  label: "village"
+  room {}
+}
+objects.1 {
+  id: 1
+  label: "tanner"
+  parent: 0
+  mob {
+    attack: 1
+    damage_max: 1
+    damage_min: 1
+    defense: 1
+    pv: 10
+    pv_max: 10
+    xp: 0
+  }
+  inventory {}
+}
+prefabs.2 {
+  id: 2
+  label: "pelt"
+  item { stackable: true }
+}
+objects.3 {
+  id: 3
+  label: "pelts for the tanner"
+  quest {
+    objectives: [
+      {kind: "deliver", prefab_id: 2, target_id: 1, amount: 5}
+    ]
+  }
+}
+"##;
+
+    fn give_pelts(container: &mut Container, mob_id: MobId, target_id: MobId, amount: u32) {
+        let item_id = Loader::spawn_at(container, StaticId(2), mob_id).unwrap();
+        container.items.get_mut(item_id).unwrap().amount = amount;
+        actions_items::do_give(container, mob_id, item_id, target_id, None).unwrap();
+    }
+
+    #[test]
+    fn test_deliver_should_count_the_given_amount() {
+        let mut container = Container::new();
+        let lc = Loader::load_hocon(&mut container, SCENERY).unwrap();
+        let tanner_id = lc.get(1);
+        let quest_id = lc.get(3);
+
+        let mob_id = builder::add_mob(&mut container, "player", lc.get(0));
+        container.inventories.add(Inventory::new(mob_id)).unwrap();
+        let player_id = container.objects.create();
+        container
+            .players
+            .create(player_id, "player".to_string(), mob_id);
+        actions_quest::start(&mut container, mob_id, quest_id).unwrap();
+
+        give_pelts(&mut container, mob_id, tanner_id, 3);
+        run(&mut container);
+        container.triggers.clear();
+
+        let log = container.quests.get_log(player_id).unwrap();
+        assert_eq!(vec![3], log.active[0].progress);
+
+        // the stack merged into the tanner pelts only count the given amount
+        give_pelts(&mut container, mob_id, tanner_id, 4);
+        run(&mut container);
+
+        let log = container.quests.get_log(player_id).unwrap();
+        assert!(log.active.is_empty());
+        assert!(log.is_completed(quest_id));
+    }
+}
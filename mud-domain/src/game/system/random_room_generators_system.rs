@@ -4,13 +4,14 @@ use crate::game::domain::Dir;
 use crate::game::labels::{Label, Labels};
 use crate::game::location::Locations;
 use crate::game::obj::Objects;
+use crate::game::random_items;
 use crate::game::random_rooms::{RandomRoomsCfg, RandomRoomsSpawnCfg};
 use crate::game::room::{Room, RoomId, RoomRepository};
 use crate::game::spawn::Spawns;
 use crate::random_grid::*;
 use commons::ObjId;
 use rand::prelude::StdRng;
-use rand::{Rng, SeedableRng};
+use rand::{Rng, RngCore, SeedableRng};
 use std::collections::HashSet;
 use std::io::repeat;
 
@@ -25,6 +26,9 @@ pub fn init(container: &mut Container) {
     let spawns = &mut container.spawns;
     let zones = &container.zones;
 
+    // random items are created after all rooms are generated
+    let mut pending_items = vec![];
+
     for rr in random_rooms_repo.list_mut() {
         if rr.generated {
             continue;
@@ -110,6 +114,12 @@ pub fn init(container: &mut Container) {
             )
             .expect("fail to generate spawns");
 
+            // choose rooms for random items
+            for _ in 0..rr.cfg.random_items {
+                let room_id = rooms_ids[rng.gen_range(0..rooms_ids.len())];
+                pending_items.push((room_id, rng.next_u64()));
+            }
+
             // set variables for next iteration
             previous_down = rooms_grid.get_down_portal();
             previous_rooms_ids = Some(rooms_ids);
@@ -117,6 +127,12 @@ pub fn init(container: &mut Container) {
 
         rr.generated = true;
     }
+
+    for (room_id, seed) in pending_items {
+        if let Err(err) = random_items::spawn_at(container, seed, room_id) {
+            log::warn!("{:?} fail to generate random item: {:?}", room_id, err);
+        }
+    }
 }
 
 fn create_spawns(
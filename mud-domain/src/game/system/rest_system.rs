@@ -4,6 +4,14 @@ use crate::game::container::Container;
 use crate::game::mob::Mob;
 use commons::{TimeTrigger, TotalTime};
 
+#[derive(Debug, PartialEq)]
+enum RestResult {
+    None,
+    Healed,
+    /// mob is too hungry or thirsty to recover and stop resting
+    NeedsEmpty,
+}
+
 pub fn run(container: &mut Container) {
     let total_time = container.time.total;
 
@@ -14,28 +22,72 @@ pub fn run(container: &mut Container) {
 
         let mob_id = mob.id;
 
-        if update_resting(mob, total_time) {
-            if mob.attributes.pv.is_damaged() {
+        match update_resting(mob, total_time) {
+            RestResult::Healed if mob.attributes.pv.is_damaged() => {
                 let msg = comm::rest_healing(mob.attributes.pv.current);
                 container.outputs.private(mob_id, msg);
-            } else {
-                container.outputs.private(mob_id, comm::rest_healed());
             }
+            RestResult::Healed => container.outputs.private(mob_id, comm::rest_healed()),
+            RestResult::NeedsEmpty => container.outputs.private(mob_id, comm::rest_needs_empty()),
+            RestResult::None => {}
         }
     }
 }
 
-fn update_resting(mob: &mut Mob, total: TotalTime) -> bool {
+fn update_resting(mob: &mut Mob, total: TotalTime) -> RestResult {
     if !mob.attributes.pv.is_damaged() {
-        return false;
+        return RestResult::None;
     }
 
     match TimeTrigger::check_trigger(mob.attributes.pv.heal_rate, mob.state.heal_calm_down, total) {
         Some(next) => {
             mob.state.heal_calm_down = next;
-            mob.attributes.pv.current += 1;
-            true
+
+            let needs_empty = mob
+                .needs
+                .as_ref()
+                .map(|needs| needs.has_empty())
+                .unwrap_or(false);
+
+            if needs_empty {
+                let _ = mob.stop_rest();
+                RestResult::NeedsEmpty
+            } else {
+                mob.attributes.pv.current += 1;
+                RestResult::Healed
+            }
         }
-        None => false,
+        None => RestResult::None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::needs::Needs;
+    use commons::{DeltaTime, ObjId};
+
+    #[test]
+    fn test_update_resting_should_not_heal_with_empty_needs() {
+        let mut mob = Mob::new(ObjId(0));
+        mob.attributes.pv.current = 1;
+        mob.attributes.pv.max = 10;
+        mob.attributes.pv.heal_rate = DeltaTime(1.0);
+        mob.set_action_rest(TotalTime(0.0)).unwrap();
+
+        let mut needs = Needs::new();
+        needs.hunger = 0.0;
+        mob.needs = Some(needs);
+
+        assert_eq!(
+            RestResult::NeedsEmpty,
+            update_resting(&mut mob, TotalTime(2.0))
+        );
+        assert_eq!(1, mob.attributes.pv.current);
+        assert!(!mob.is_resting());
+
+        mob.needs.as_mut().unwrap().restore(50.0, 0.0);
+        assert_eq!(RestResult::Healed, update_resting(&mut mob, TotalTime(4.0)));
+        assert_eq!(2, mob.attributes.pv.current);
     }
 }
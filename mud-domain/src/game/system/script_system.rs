@@ -0,0 +1,92 @@
+use crate::game::container::Container;
+use crate::game::mob::MobId;
+use crate::game::room::RoomId;
+use crate::game::scripts_service;
+use commons::TimeTrigger;
+
+/// Execute enter and leave scripts for mobs that changed of room and timer scripts
+pub fn run(container: &mut Container) {
+    run_enter_and_leave(container);
+    run_timers(container);
+}
+
+fn get_mob_room(container: &Container, mob_id: MobId) -> Option<RoomId> {
+    container
+        .locations
+        .get(mob_id)
+        .filter(|location_id| container.rooms.exists(*location_id))
+}
+
+/// Register where each loaded mob is, so they don't trigger enter hooks on the first tick
+pub fn init(container: &mut Container) {
+    let mob_ids: Vec<MobId> = container.mobs.list().map(|mob| mob.id).collect();
+    for mob_id in mob_ids {
+        let room_id = get_mob_room(container, mob_id);
+        container.scripts.set_mob_room(mob_id, room_id);
+    }
+}
+
+/// Compare the room of each mob with the last known one, so any way a mob can change of room
+/// (walking, enter, out, ship travel, spawn, respawn) trigger the hooks
+fn run_enter_and_leave(container: &mut Container) {
+    let mob_ids: Vec<MobId> = container.mobs.list().map(|mob| mob.id).collect();
+
+    for mob_id in mob_ids {
+        // a previous hook could have removed the mob
+        if !container.mobs.exists(mob_id) {
+            continue;
+        }
+
+        let previous_room_id = container.scripts.get_mob_room(mob_id);
+        let room_id = get_mob_room(container, mob_id);
+        if previous_room_id == room_id {
+            continue;
+        }
+
+        container.scripts.set_mob_room(mob_id, room_id);
+
+        if let Some(previous_room_id) = previous_room_id {
+            scripts_service::on_leave(container, previous_room_id, mob_id);
+        }
+
+        if let Some(room_id) = room_id {
+            scripts_service::on_enter(container, room_id, mob_id);
+        }
+    }
+}
+
+/// Execute timer scripts, the first execution happens one interval after the script is loaded
+fn run_timers(container: &mut Container) {
+    let now = container.time.total;
+    let mut ready = vec![];
+
+    for script in container.scripts.list_timers() {
+        let is_ready = script
+            .next_timer
+            .map(|next| TimeTrigger::should_trigger(next, now))
+            .unwrap_or(true);
+
+        if is_ready {
+            ready.push(script.id);
+        }
+    }
+
+    for obj_id in ready {
+        let script = match container.scripts.get_mut(obj_id) {
+            Some(script) => script,
+            None => continue,
+        };
+
+        let interval = match script.timer_interval {
+            Some(interval) => interval,
+            None => continue,
+        };
+
+        let first_time = script.next_timer.is_none();
+        script.next_timer = Some(now + interval);
+
+        if !first_time {
+            scripts_service::on_timer(container, obj_id);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::game::astro_bodies::{AstroBody, AstroBodyKind};
 use crate::game::container::Container;
 use crate::game::ships::{LandState, LaunchState, MoveState, ShipCommand};
-use crate::game::{astro_bodies, comm};
+use crate::game::{astro_bodies, comm, weather};
 use crate::utils;
 use crate::utils::geometry;
 use commons::{DeltaTime, TotalTime};
@@ -18,6 +18,7 @@ pub fn tick(container: &mut Container) {
     let locations = &mut container.locations;
     let astros = &mut container.astro_bodies;
     let labels = &mut container.labels;
+    let weathers = &container.weathers;
 
     for ship in ships.list_all_mut() {
         let ship_id = ship.id;
@@ -169,6 +170,21 @@ pub fn tick(container: &mut Container) {
 
                 ShipCommand::Launch { target_id, state } => match state {
                     LaunchState::NotStarted => {
+                        // severe weather at the landing pad block the launch
+                        let weather = locations
+                            .get(ship_id)
+                            .and_then(|pad_id| {
+                                weather::find_weather_at(weathers, locations, pad_id)
+                            })
+                            .filter(|weather| weather.is_severe());
+
+                        if let Some(weather) = weather {
+                            ship.command = ShipCommand::Idle;
+                            let msg = comm::space_launch_aborted_weather(weather);
+                            container.outputs.broadcast_all(None, ship_id, msg);
+                            continue;
+                        }
+
                         // update ship command
                         ship.command = ShipCommand::Launch {
                             target_id: *target_id,
@@ -236,7 +252,32 @@ pub fn tick(container: &mut Container) {
                 },
 
                 ShipCommand::Land { target_id, state } => {
+                    // severe weather at the landing zone abort the landing before it starts and
+                    // hold the ship in its current stage while it is landing
+                    let weather = weather::find_weather_at(weathers, locations, *target_id)
+                        .filter(|weather| weather.is_severe());
+
                     match state {
+                        LandState::NotStarted if weather.is_some() => {
+                            let msg = comm::space_land_aborted_weather(weather.unwrap());
+                            ship.command = ShipCommand::Idle;
+                            container.outputs.broadcast_all(None, ship_id, msg);
+                        }
+
+                        LandState::Running {
+                            complete_time,
+                            holding,
+                            ..
+                        } if weather.is_some() => {
+                            *complete_time = total_time + DeltaTime(DEFAULT_TIME);
+
+                            if !*holding {
+                                *holding = true;
+                                let msg = comm::space_land_holding_weather(weather.unwrap());
+                                container.outputs.broadcast_all(None, ship_id, msg);
+                            }
+                        }
+
                         LandState::NotStarted => {
                             // update command
                             ship.command = ShipCommand::Land {
@@ -244,6 +285,7 @@ pub fn tick(container: &mut Container) {
                                 state: LandState::Running {
                                     stage: 0,
                                     complete_time: total_time + DeltaTime(DEFAULT_TIME),
+                                    holding: false,
                                 },
                             };
                         }
@@ -280,6 +322,7 @@ pub fn tick(container: &mut Container) {
                         LandState::Running {
                             stage,
                             complete_time,
+                            holding,
                         } => {
                             // send messages
                             let msg = match *stage {
@@ -300,6 +343,7 @@ pub fn tick(container: &mut Container) {
                             // update command
                             *stage += 1;
                             *complete_time = total_time + DeltaTime(DEFAULT_TIME);
+                            *holding = false;
 
                             container.outputs.broadcast_all(None, ship_id, msg);
                         }
@@ -1,18 +1,24 @@
 use commons::unwrap_or_continue;
 
 use crate::errors::*;
-use crate::game::comm;
+use crate::game::ai::Ai;
 use crate::game::container::Container;
+use crate::game::loader::dto::StaticId;
 use crate::game::loader::Loader;
-use crate::game::mob::MobRepository;
+use crate::game::location::LocationId;
+use crate::game::mob::MobId;
 use crate::game::spawn::{Spawn, SpawnId};
 use crate::game::timer::Timer;
 use crate::game::triggers::{Event, EventKind};
-use commons::{DeltaTime, TotalTime};
-use rand::{thread_rng, Rng};
+use crate::game::{actions_command, comm, pathfinding};
+use commons::prob;
+use commons::{DeltaTime, Tick, TotalTime};
+use rand::prelude::StdRng;
+use rand::{Rng, SeedableRng};
 
 pub fn run(container: &mut Container) {
     let total_time = container.time.total;
+    let tick = container.time.tick;
 
     // schedule all new spawns
     for spawn_id in container.spawns.take_added() {
@@ -24,102 +30,198 @@ pub fn run(container: &mut Container) {
             }
         };
 
-        schedule_first_spawn(&mut container.timer, total_time, spawn);
+        let mut rng = create_rng(spawn_id, tick);
+        schedule_first_spawn(&mut container.timer, &mut rng, total_time, spawn);
     }
 
     let mut mob_spawns = vec![];
 
     // process all already triggered spawns
-    for event in container.triggers.list(EventKind::Spawn) {
-        let spawn_id = event.get_obj_id();
-        let spawn = unwrap_or_continue!(container.spawns.get_mut(spawn_id));
+    let triggered: Vec<SpawnId> = container
+        .triggers
+        .list(EventKind::Spawn)
+        .map(|event| event.get_obj_id())
+        .collect();
 
-        let can_spawn_mobs = container.ownership.count(spawn.id) < spawn.max as usize;
+    for spawn_id in triggered {
+        let mut rng = create_rng(spawn_id, tick);
+        let spawn = unwrap_or_continue!(container.spawns.get(spawn_id));
 
-        if can_spawn_mobs {
-            let location_id = if spawn.locations_id.is_empty() {
-                container.locations.get(spawn.id)
+        let can_spawn_mobs = container.ownership.count(spawn.id) < spawn.max as usize;
+        let is_period = spawn
+            .period
+            .map(|period| container.config.calendar.period(total_time) == period)
+            .unwrap_or(true);
+
+        if !is_period {
+            log::debug!("{:?} can not spawn in this period of the day", spawn.id);
+        } else if can_spawn_mobs {
+            let locations = list_locations(container, spawn);
+            if locations.is_empty() {
+                log::debug!("{:?} Spawn has no location available", spawn.id);
             } else {
-                let index = thread_rng().gen_range(0..spawn.locations_id.len());
-                spawn.locations_id.get(index).cloned()
-            };
-
-            match location_id {
-                Some(location_id) => {
-                    let is_valid =
-                        container.rooms.exists(location_id) || container.items.exists(location_id);
-
-                    if is_valid {
-                        mob_spawns.push((
-                            spawn.id,
-                            location_id,
-                            spawn.prefab_id,
-                            spawn.ai_overwrite.clone(),
-                        ));
-                    } else {
-                        log::warn!(
-                            "{:?} Spawn parent {:?} is not a valid room or item.",
-                            spawn.id,
-                            location_id
-                        );
-                    }
-                }
-                None => log::warn!("{:?} Spawn has no parent", spawn.id),
-            };
+                let location_id = locations[rng.gen_range(0..locations.len())];
+                mob_spawns.push((spawn.clone(), location_id));
+            }
         } else {
             log::debug!("{:?} can not spawn, already own max objects", spawn.id);
         }
 
-        schedule_next_spawn(&mut container.timer, total_time, spawn);
+        let spawn = unwrap_or_continue!(container.spawns.get_mut(spawn_id));
+        schedule_next_spawn(&mut container.timer, &mut rng, total_time, spawn);
     }
 
     // spawn all mobs
-    for (spawn_id, room_id, mob_prefab_id, ai_overwrite) in mob_spawns {
-        let mob_id = match Loader::spawn_at(container, mob_prefab_id, room_id) {
-            Ok(mob_id) => mob_id,
-            Err(e) => {
-                log::warn!(
-                    "{:?} fail to spawn a {:?}: {:?}",
-                    spawn_id,
-                    mob_prefab_id,
-                    e
-                );
-                continue;
-            }
-        };
+    for (spawn, location_id) in mob_spawns {
+        if let Err(e) = spawn_group(container, &spawn, location_id) {
+            log::warn!(
+                "{:?} fail to spawn a {:?}: {:?}",
+                spawn.id,
+                spawn.prefab_id,
+                e
+            );
+        }
+    }
+}
+
+/// Same spawn in the same tick always take the same decisions
+fn create_rng(spawn_id: SpawnId, tick: Tick) -> StdRng {
+    StdRng::seed_from_u64(prob::seed_for(spawn_id, tick))
+}
+
+/// Rooms or items where the spawn can create its mobs, sorted to keep the selection deterministic
+fn list_locations(container: &Container, spawn: &Spawn) -> Vec<LocationId> {
+    let mut candidates = spawn.locations_id.clone();
+
+    if let Some(zone_id) = spawn.zone_id {
+        candidates.extend(
+            container
+                .locations
+                .list_deep_at(zone_id)
+                .into_iter()
+                .filter(|id| container.rooms.exists(*id)),
+        );
+    }
 
-        if let Some(ai_overwrite) = ai_overwrite {
-            Loader::apply_ai_data(&mut container.ai, mob_id, &ai_overwrite)
-                .expect("fail to apply ai");
+    if let Some(radius) = spawn.radius {
+        if let Some(room_id) = container.locations.get(spawn.id) {
+            candidates.extend(pathfinding::rooms_within(container, room_id, radius).into_keys());
         }
+    }
 
-        let mob_label = container.labels.get_label_f(mob_id);
+    if candidates.is_empty() {
+        candidates.extend(container.locations.get(spawn.id));
+    }
 
-        log::debug!("{:?} spawn created {:?} at {:?}", spawn_id, mob_id, room_id);
+    candidates.sort();
+    candidates.dedup();
+
+    candidates.retain(|location_id| {
+        let is_valid = container.rooms.exists(*location_id) || container.items.exists(*location_id);
+        if !is_valid {
+            log::warn!(
+                "{:?} Spawn parent {:?} is not a valid room or item.",
+                spawn.id,
+                location_id
+            );
+        }
+        is_valid
+    });
 
-        // TODO: move to ownership system
-        let spawn_msg = comm::spawn_mob(mob_label);
+    if spawn.avoid_players {
+        candidates.retain(|location_id| !has_players(container, *location_id));
+    }
 
-        // update spawn
-        container.ownership.set_owner(mob_id, spawn_id);
+    candidates
+}
 
-        // add outputs
-        container.outputs.broadcast(None, room_id, spawn_msg);
+/// Check if there is any player in the room of the location
+fn has_players(container: &Container, location_id: LocationId) -> bool {
+    let room_id = container
+        .locations
+        .list_parents_inclusive(location_id)
+        .into_iter()
+        .find(|id| container.rooms.exists(*id))
+        .unwrap_or(location_id);
+
+    container
+        .locations
+        .list_at(room_id)
+        .any(|id| container.players.find_from_mob(id).is_some())
+}
+
+/// Spawn the mob and its followers, followers are owned by the spawned mob and do not count for
+/// the spawn max while the leader is alive. Once the leader is killed, the followers are owned by
+/// the spawn.
+fn spawn_group(container: &mut Container, spawn: &Spawn, location_id: LocationId) -> Result<()> {
+    let mob_id = spawn_mob(container, spawn.id, spawn.prefab_id, location_id)?;
+
+    if let Some(ai_overwrite) = &spawn.ai_overwrite {
+        Loader::apply_ai_data(&mut container.ai, mob_id, ai_overwrite)?;
+    }
+
+    container.ownership.set_owner(mob_id, spawn.id);
+
+    for follower_prefab_id in &spawn.followers {
+        let follower_id = spawn_mob(container, spawn.id, *follower_prefab_id, location_id)?;
+
+        if !container.ai.exist(follower_id) {
+            container.ai.add_or_update(Ai::new(follower_id))?;
+        }
+
+        container.ownership.set_owner(follower_id, mob_id);
+        actions_command::set_command_follow(container, follower_id, mob_id)?;
     }
+
+    Ok(())
+}
+
+/// Followers of a killed spawned mob are passed to the spawn, so they keep counting for its max
+pub fn on_mob_killed(container: &mut Container, mob_id: MobId) {
+    let spawn_id = match container.ownership.get_owner(mob_id) {
+        Some(owner_id) if container.spawns.get(owner_id).is_some() => owner_id,
+        _ => return,
+    };
+
+    for follower_id in container.ownership.list(mob_id) {
+        container.ownership.set_owner(follower_id, spawn_id);
+    }
+}
+
+fn spawn_mob(
+    container: &mut Container,
+    spawn_id: SpawnId,
+    prefab_id: StaticId,
+    location_id: LocationId,
+) -> Result<MobId> {
+    let mob_id = Loader::spawn_at(container, prefab_id, location_id)?;
+    let mob_label = container.labels.get_label_f(mob_id);
+
+    log::debug!(
+        "{:?} spawn created {:?} at {:?}",
+        spawn_id,
+        mob_id,
+        location_id
+    );
+
+    // TODO: move to ownership system
+    let spawn_msg = comm::spawn_mob(mob_label);
+    container.outputs.broadcast(None, location_id, spawn_msg);
+
+    Ok(mob_id)
 }
 
-fn schedule_first_spawn(timer: &mut Timer, now: TotalTime, spawn: &mut Spawn) {
+fn schedule_first_spawn(timer: &mut Timer, rng: &mut StdRng, now: TotalTime, spawn: &mut Spawn) {
     if spawn.next.is_after(now) {
         // when adding a spawn, if there is already some spawn scheduled in the future, just add
         // the spawn to the trigger
         add_spawn_to_trigger(timer, spawn.next, spawn.id);
     } else {
-        schedule_next_spawn(timer, now, spawn);
+        schedule_next_spawn(timer, rng, now, spawn);
     }
 }
 
-fn schedule_next_spawn(timer: &mut Timer, now: TotalTime, spawn: &mut Spawn) {
-    let mut rng = rand::thread_rng();
+fn schedule_next_spawn(timer: &mut Timer, rng: &mut StdRng, now: TotalTime, spawn: &mut Spawn) {
     let range = rng.gen_range(spawn.delay.min.as_seconds_f32()..spawn.delay.max.as_seconds_f32());
     let next = now + DeltaTime(range);
     spawn.next = next;
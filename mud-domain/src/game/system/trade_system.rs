@@ -0,0 +1,24 @@
+use crate::game::comm;
+use crate::game::container::Container;
+use crate::game::mob::MobId;
+
+/// Cancel trades where any of the parties have left the room or no longer exists
+pub fn run(container: &mut Container) {
+    let invalid: Vec<MobId> = container
+        .trades
+        .list()
+        .filter(|offer| container.locations.get(offer.mob_id) != Some(offer.room_id))
+        .map(|offer| offer.mob_id)
+        .collect();
+
+    for mob_id in invalid {
+        if let Some((offer, _)) = container.trades.close(mob_id) {
+            log::debug!("{:?} trade cancelled, party left", mob_id);
+            for id in &[offer.mob_id, offer.partner_id] {
+                if container.mobs.exists(*id) {
+                    container.outputs.private(*id, comm::trade_cancelled());
+                }
+            }
+        }
+    }
+}
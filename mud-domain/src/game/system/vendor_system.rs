@@ -0,0 +1,45 @@
+use crate::game::actions_vendor;
+use crate::game::container::Container;
+use crate::game::triggers::{Event, EventKind};
+use commons::{unwrap_or_continue, ObjId};
+
+/// Restock vendors with limited or random stock periodically
+pub fn run(container: &mut Container) {
+    let total_time = container.time.total;
+
+    let mut to_schedule: Vec<ObjId> = container.vendors.take_added();
+
+    let restock: Vec<ObjId> = container
+        .triggers
+        .list(EventKind::Restock)
+        .map(|event| event.get_obj_id())
+        .collect();
+
+    for vendor_id in restock {
+        let vendor = unwrap_or_continue!(container.vendors.get_mut(vendor_id));
+        log::debug!("{:?} restocking", vendor_id);
+        vendor.stock.restock();
+        to_schedule.push(vendor_id);
+    }
+
+    for &vendor_id in &to_schedule {
+        if let Err(err) = actions_vendor::restock_random_items(container, vendor_id) {
+            log::warn!("{:?} fail to restock random items: {:?}", vendor_id, err);
+        }
+    }
+
+    for vendor_id in to_schedule {
+        let vendor = unwrap_or_continue!(container.vendors.get(vendor_id));
+        if !vendor.has_restock() {
+            continue;
+        }
+
+        container.timer.schedule(
+            total_time + vendor.stock.restock_time,
+            Event::Obj {
+                kind: EventKind::Restock,
+                obj_id: vendor_id,
+            },
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use crate::game::calendar;
+use crate::game::comm;
+use crate::game::container::Container;
+use crate::game::room::RoomId;
+use crate::game::timer::Timer;
+use crate::game::triggers::{Event, EventKind};
+use crate::game::weather::{self, Weather, WeatherKind};
+use commons::prob;
+use commons::{unwrap_or_continue, DeltaTime, ObjId, Tick, TotalTime};
+use rand::prelude::StdRng;
+use rand::{Rng, SeedableRng};
+
+pub fn run(container: &mut Container) {
+    let total_time = container.time.total;
+    let tick = container.time.tick;
+
+    // schedule all new weathers
+    for weather_id in container.weathers.take_added() {
+        let mut rng = create_rng(weather_id, tick);
+        let weather = unwrap_or_continue!(container.weathers.get_mut(weather_id));
+
+        if weather.next_change.is_after(total_time) {
+            add_weather_to_trigger(&mut container.timer, weather.next_change, weather_id);
+        } else {
+            schedule_next_change(&mut container.timer, &mut rng, total_time, weather);
+        }
+    }
+
+    // change all triggered weathers
+    let triggered: Vec<ObjId> = container
+        .triggers
+        .list(EventKind::Weather)
+        .map(|event| event.get_obj_id())
+        .collect();
+
+    for weather_id in triggered {
+        let mut rng = create_rng(weather_id, tick);
+        let weather = unwrap_or_continue!(container.weathers.get_mut(weather_id));
+
+        let previous = weather.current;
+        weather.current = weather.states[rng.gen_range(0..weather.states.len())];
+        let current = weather.current;
+        schedule_next_change(&mut container.timer, &mut rng, total_time, weather);
+
+        if previous != current {
+            log::debug!(
+                "{:?} weather changed from {:?} to {:?}",
+                weather_id,
+                previous,
+                current
+            );
+            announce(container, weather_id, current);
+        }
+    }
+}
+
+/// Same weather in the same tick always take the same decisions
+fn create_rng(weather_id: ObjId, tick: Tick) -> StdRng {
+    StdRng::seed_from_u64(prob::seed_for(weather_id, tick))
+}
+
+/// Send the new weather to all outdoor rooms that are not covered by a closer weather
+fn announce(container: &mut Container, weather_id: ObjId, kind: WeatherKind) {
+    let mut rooms: Vec<RoomId> = container
+        .locations
+        .list_deep_at(weather_id)
+        .into_iter()
+        .filter(|id| container.rooms.exists(*id))
+        .filter(|id| {
+            weather::find_weather_owner_at(&container.weathers, &container.locations, *id)
+                == Some(weather_id)
+        })
+        .filter(|id| calendar::is_outdoor(container, *id))
+        .collect();
+    rooms.sort();
+
+    let msg = comm::weather_changed(kind);
+    for room_id in rooms {
+        container.outputs.broadcast(None, room_id, msg.clone());
+    }
+}
+
+fn schedule_next_change(
+    timer: &mut Timer,
+    rng: &mut StdRng,
+    now: TotalTime,
+    weather: &mut Weather,
+) {
+    let delay = if weather.delay_min.as_seconds_f32() >= weather.delay_max.as_seconds_f32() {
+        weather.delay_min
+    } else {
+        DeltaTime(
+            rng.gen_range(weather.delay_min.as_seconds_f32()..weather.delay_max.as_seconds_f32()),
+        )
+    };
+
+    weather.next_change = now + delay;
+    add_weather_to_trigger(timer, weather.next_change, weather.id);
+}
+
+fn add_weather_to_trigger(timer: &mut Timer, next: TotalTime, weather_id: ObjId) {
+    timer.schedule(
+        next,
+        Event::Obj {
+            kind: EventKind::Weather,
+            obj_id: weather_id,
+        },
+    );
+    log::debug!("{:?} scheduling weather change at {:?}", weather_id, next);
+}
@@ -34,6 +34,14 @@ impl Tags {
         }
     }
 
+    /// like get_id, but do not create a new tag if not exists
+    pub fn find_id(&self, value: &str) -> Option<TagId> {
+        self.tags
+            .iter()
+            .find(|(_id, s)| s.as_str() == value)
+            .map(|(id, _)| *id)
+    }
+
     pub fn get_str(&self, tag_id: TagId) -> Option<&str> {
         self.tags
             .iter()
@@ -47,6 +55,13 @@ impl Tags {
         tags.insert(tag_id);
     }
 
+    pub fn remove_tag(&mut self, obj_id: ObjId, tag_id: TagId) {
+        if let Some(tags) = self.index.get_mut(&obj_id) {
+            log::info!("{:?} unassign tag {:?}", obj_id, tag_id);
+            tags.remove(&tag_id);
+        }
+    }
+
     pub fn get_tags(&self, obj_id: ObjId) -> Option<&HashSet<TagId>> {
         self.index.get(&obj_id)
     }
@@ -0,0 +1,148 @@
+use crate::errors::{Error, Result};
+use crate::game::item::ItemId;
+use crate::game::mob::MobId;
+use crate::game::prices::Money;
+use crate::game::room::RoomId;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// One side of a trade window, each party of a trade has its own offer
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TradeOffer {
+    pub mob_id: MobId,
+    pub partner_id: MobId,
+    /// where the trade was started, trade is cancelled if any party leave
+    pub room_id: RoomId,
+    pub items: Vec<ItemId>,
+    pub money: Money,
+    pub confirmed: bool,
+}
+
+impl TradeOffer {
+    pub fn new(mob_id: MobId, partner_id: MobId, room_id: RoomId) -> Self {
+        TradeOffer {
+            mob_id,
+            partner_id,
+            room_id,
+            items: vec![],
+            money: Money(0),
+            confirmed: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Trades {
+    index: HashMap<MobId, TradeOffer>,
+}
+
+impl Trades {
+    pub fn new() -> Self {
+        Trades {
+            index: HashMap::new(),
+        }
+    }
+
+    /// open a trade window between both mobs
+    pub fn open(&mut self, mob_id: MobId, partner_id: MobId, room_id: RoomId) -> Result<()> {
+        if mob_id == partner_id
+            || self.index.contains_key(&mob_id)
+            || self.index.contains_key(&partner_id)
+        {
+            return Err(Error::ConflictException);
+        }
+
+        log::debug!("{:?} open trade with {:?}", mob_id, partner_id);
+        self.index
+            .insert(mob_id, TradeOffer::new(mob_id, partner_id, room_id));
+        self.index
+            .insert(partner_id, TradeOffer::new(partner_id, mob_id, room_id));
+        Ok(())
+    }
+
+    /// close the trade window of the mob and its partner, returning both offers
+    pub fn close(&mut self, mob_id: MobId) -> Option<(TradeOffer, Option<TradeOffer>)> {
+        let offer = self.index.remove(&mob_id)?;
+        log::debug!("{:?} close trade with {:?}", mob_id, offer.partner_id);
+        let partner_offer = self.index.remove(&offer.partner_id);
+        Some((offer, partner_offer))
+    }
+
+    pub fn get(&self, mob_id: MobId) -> Option<&TradeOffer> {
+        self.index.get(&mob_id)
+    }
+
+    /// any change in a offer require both parties to confirm again
+    pub fn update<F>(&mut self, mob_id: MobId, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut TradeOffer),
+    {
+        let offer = self.index.get_mut(&mob_id).ok_or(Error::NotFoundFailure)?;
+        f(offer);
+        let partner_id = offer.partner_id;
+
+        for id in &[mob_id, partner_id] {
+            if let Some(offer) = self.index.get_mut(id) {
+                offer.confirmed = false;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn confirm(&mut self, mob_id: MobId) -> Result<()> {
+        let offer = self.index.get_mut(&mob_id).ok_or(Error::NotFoundFailure)?;
+        offer.confirmed = true;
+        Ok(())
+    }
+
+    pub fn is_confirmed(&self, mob_id: MobId) -> bool {
+        self.index
+            .get(&mob_id)
+            .map(|offer| offer.confirmed)
+            .unwrap_or(false)
+    }
+
+    /// returns true if the item is offered in any trade
+    pub fn is_offered(&self, item_id: ItemId) -> bool {
+        self.index
+            .values()
+            .any(|offer| offer.items.contains(&item_id))
+    }
+
+    pub fn list<'a>(&'a self) -> impl Iterator<Item = &'a TradeOffer> + 'a {
+        self.index.values()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use commons::ObjId;
+
+    #[test]
+    fn test_trades_changes_should_reset_confirmation() {
+        let mut trades = Trades::new();
+        let (a, b) = (ObjId(0), ObjId(1));
+
+        trades.open(a, b, ObjId(2)).unwrap();
+        assert!(trades.open(b, ObjId(3), ObjId(2)).is_err());
+
+        trades.confirm(a).unwrap();
+        trades.confirm(b).unwrap();
+        assert!(trades.is_confirmed(a) && trades.is_confirmed(b));
+
+        trades
+            .update(b, |offer| offer.items.push(ObjId(4)))
+            .unwrap();
+        assert!(!trades.is_confirmed(a));
+        assert!(!trades.is_confirmed(b));
+        assert!(trades.is_offered(ObjId(4)));
+
+        let (offer, partner) = trades.close(b).unwrap();
+        assert_eq!(b, offer.mob_id);
+        assert_eq!(a, partner.unwrap().mob_id);
+        assert!(trades.get(a).is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::game::loader::dto::StaticId;
 use commons::ObjId;
 
 use serde::{Deserialize, Serialize};
@@ -11,25 +12,60 @@ pub enum EventKind {
     Decay,
     Removed,
     Killed,
+    Restock,
+    /// Item was given from one mob to other
+    Give,
+    /// Mob extracted a item from a extractable
+    Extract,
+    /// Weather of a zone or astro body should change
+    Weather,
     /// Used now for last element
     Unknown,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Event {
-    Obj { kind: EventKind, obj_id: ObjId },
+    Obj {
+        kind: EventKind,
+        obj_id: ObjId,
+    },
+    /// obj_id is the receiver of the item
+    Give {
+        obj_id: ObjId,
+        from_id: ObjId,
+        item_id: ObjId,
+        /// given amount, the received item can be a stack merged with previous items
+        amount: u32,
+    },
+    /// obj_id was already removed when the event is processed
+    Killed {
+        obj_id: ObjId,
+        prefab_id: Option<StaticId>,
+        killer_id: Option<ObjId>,
+    },
+    /// obj_id is the mob that extracted the item
+    Extract {
+        obj_id: ObjId,
+        item_id: ObjId,
+    },
 }
 
 impl Event {
     pub fn get_kind(&self) -> EventKind {
         match self {
             Event::Obj { kind, .. } => *kind,
+            Event::Give { .. } => EventKind::Give,
+            Event::Killed { .. } => EventKind::Killed,
+            Event::Extract { .. } => EventKind::Extract,
         }
     }
 
     pub fn get_obj_id(&self) -> ObjId {
         match self {
             Event::Obj { obj_id, .. } => *obj_id,
+            Event::Give { obj_id, .. } => *obj_id,
+            Event::Killed { obj_id, .. } => *obj_id,
+            Event::Extract { obj_id, .. } => *obj_id,
         }
     }
 }
@@ -0,0 +1,123 @@
+use crate::errors::{Error, Result};
+use crate::game::loader::dto::{CanLoad, CanSnapshot, ObjData, VaultData};
+use crate::game::loader::LoadingCtx;
+use crate::game::prices::Money;
+use commons::{ObjId, PlayerId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Max amount of items a player can store in the vault
+pub const VAULT_MAX_ITEMS: usize = 20;
+
+/// Personal storage of a player, independent of the current avatar. Stored items are located at
+/// a dedicated storage object, so they are kept when the avatar dies and are never mixed with
+/// the items the player hold between death and respawn.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Vault {
+    pub id: PlayerId,
+    pub money: Money,
+    /// created when the first item is stored
+    pub storage_id: Option<ObjId>,
+}
+
+impl Vault {
+    pub fn new(id: PlayerId) -> Self {
+        Vault {
+            id,
+            money: Money(0),
+            storage_id: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Vaults {
+    index: HashMap<PlayerId, Vault>,
+}
+
+impl Vaults {
+    pub fn new() -> Self {
+        Vaults {
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn remove(&mut self, id: PlayerId) -> Option<Vault> {
+        self.index.remove(&id)
+    }
+
+    pub fn get(&self, id: PlayerId) -> Option<&Vault> {
+        self.index.get(&id)
+    }
+
+    /// get the player vault, creating a empty one if player has none
+    pub fn get_or_create(&mut self, id: PlayerId) -> &mut Vault {
+        self.index.entry(id).or_insert_with(|| Vault::new(id))
+    }
+
+    pub fn deposit(&mut self, id: PlayerId, amount: Money) {
+        let vault = self.get_or_create(id);
+        vault.money = Money(vault.money.as_u32() + amount.as_u32());
+    }
+
+    pub fn withdraw(&mut self, id: PlayerId, amount: Money) -> Result<()> {
+        let vault = self.index.get_mut(&id).ok_or(Error::NotFoundFailure)?;
+        if vault.money.as_u32() < amount.as_u32() {
+            return Err(Error::InvalidArgumentFailure);
+        }
+        vault.money = Money(vault.money.as_u32() - amount.as_u32());
+        Ok(())
+    }
+}
+
+impl CanLoad for Vaults {
+    fn load(&mut self, references: &LoadingCtx, obj_id: ObjId, data: &ObjData) -> Result<()> {
+        if let Some(vault_data) = &data.vault {
+            let mut vault = Vault::new(obj_id);
+            vault.money = Money(vault_data.money);
+            vault.storage_id = match vault_data.storage_id {
+                Some(static_id) => Some(*references.id_map.get(&static_id).ok_or_else(|| {
+                    Error::InvalidArgumentFailureStr(format!(
+                        "{:?} vault storage {:?} not found",
+                        obj_id, static_id
+                    ))
+                })?),
+                None => None,
+            };
+            self.index.insert(obj_id, vault);
+        }
+
+        Ok(())
+    }
+}
+
+impl CanSnapshot for Vaults {
+    fn snapshot(&self, obj_id: ObjId, data: &mut ObjData) -> Result<()> {
+        if let Some(vault) = self.index.get(&obj_id) {
+            data.vault = Some(VaultData {
+                money: vault.money.as_u32(),
+                storage_id: vault.storage_id.map(|id| id.into()),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vault_withdraw_should_fail_without_enough_money() {
+        let mut vaults = Vaults::new();
+        let player_id = ObjId(0);
+
+        assert!(vaults.withdraw(player_id, Money(1)).is_err());
+
+        vaults.deposit(player_id, Money(10));
+        assert!(vaults.withdraw(player_id, Money(11)).is_err());
+        vaults.withdraw(player_id, Money(4)).unwrap();
+        assert_eq!(Money(6), vaults.get(player_id).unwrap().money);
+    }
+}
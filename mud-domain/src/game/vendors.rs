@@ -1,15 +1,138 @@
-use commons::ObjId;
+use crate::game::loader::dto::StaticId;
+use crate::game::tags::TagId;
+use commons::{DeltaTime, ObjId};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub const RESTOCK_TIME: DeltaTime = DeltaTime(60.0);
+
+/// How much prices increase when stock is empty, 0.5 means 50% more expensive
+pub const STOCK_PRICE_VARIATION: f32 = 0.5;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VendorStockLimit {
+    pub tag_id: TagId,
+    /// max amount of each prefab with the tag
+    pub max: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VendorStockItem {
+    pub amount: u32,
+    pub max: u32,
+}
+
+/// Vendor without limits have unlimited stock
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct VendorStock {}
+pub struct VendorStock {
+    pub limits: Vec<VendorStockLimit>,
+    /// current stock of prefabs already traded, prefabs not here are full stock
+    pub items: HashMap<StaticId, VendorStockItem>,
+    pub restock_time: DeltaTime,
+}
+
+impl VendorStock {
+    pub fn new() -> Self {
+        VendorStock {
+            limits: vec![],
+            items: HashMap::new(),
+            restock_time: RESTOCK_TIME,
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.limits.is_empty()
+    }
+
+    /// max stock of a prefab by its tags, None if unlimited
+    pub fn get_max(&self, tags: &Vec<TagId>) -> Option<u32> {
+        if self.is_unlimited() {
+            return None;
+        }
+
+        Some(
+            self.limits
+                .iter()
+                .filter(|limit| tags.contains(&limit.tag_id))
+                .map(|limit| limit.max)
+                .max()
+                .unwrap_or(0),
+        )
+    }
+
+    /// current amount of a prefab, None if unlimited
+    pub fn get_amount(&self, static_id: StaticId, tags: &Vec<TagId>) -> Option<u32> {
+        let max = self.get_max(tags)?;
+        Some(
+            self.items
+                .get(&static_id)
+                .map(|item| item.amount)
+                .unwrap_or(max),
+        )
+    }
+
+    /// price multiplier by current stock, scarce items are more expensive
+    pub fn get_price_mult(&self, static_id: StaticId, tags: &Vec<TagId>) -> f32 {
+        match (self.get_amount(static_id, tags), self.get_max(tags)) {
+            (Some(amount), Some(max)) if max > 0 => {
+                let ratio = (amount as f32 / max as f32).min(1.0);
+                1.0 + (1.0 - ratio) * STOCK_PRICE_VARIATION
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// remove one item from stock, return false if there is no stock
+    pub fn take(&mut self, static_id: StaticId, tags: &Vec<TagId>) -> bool {
+        let max = match self.get_max(tags) {
+            Some(max) => max,
+            None => return true,
+        };
+
+        let item = self
+            .items
+            .entry(static_id)
+            .or_insert(VendorStockItem { amount: max, max });
+
+        if item.amount == 0 {
+            false
+        } else {
+            item.amount -= 1;
+            true
+        }
+    }
+
+    /// add one item into the stock, up to the max
+    pub fn put(&mut self, static_id: StaticId, tags: &Vec<TagId>) {
+        let max = match self.get_max(tags) {
+            Some(max) => max,
+            None => return,
+        };
+
+        if let Some(item) = self.items.get_mut(&static_id) {
+            item.amount = (item.amount + 1).min(max);
+        }
+    }
+
+    /// increase by one all items bellow max, items that are full are forget
+    pub fn restock(&mut self) {
+        for item in self.items.values_mut() {
+            if item.amount < item.max {
+                item.amount += 1;
+            }
+        }
+
+        self.items.retain(|_, item| item.amount < item.max);
+    }
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Vendor {
     pub id: ObjId,
     pub market_id: Option<ObjId>,
     pub stock: VendorStock,
+    /// amount of random generated items kept in the inventory to sell
+    pub random_stock: u32,
 }
 
 impl Vendor {
@@ -17,25 +140,34 @@ impl Vendor {
         Vendor {
             id,
             market_id: None,
-            stock: VendorStock {},
+            stock: VendorStock::new(),
+            random_stock: 0,
         }
     }
+
+    /// vendors with limited or random stock are periodically restocked
+    pub fn has_restock(&self) -> bool {
+        !self.stock.is_unlimited() || self.random_stock > 0
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Vendors {
     index: HashMap<ObjId, Vendor>,
+    added: Vec<ObjId>,
 }
 
 impl Vendors {
     pub fn new() -> Self {
         Vendors {
             index: HashMap::new(),
+            added: vec![],
         }
     }
 
     pub fn add(&mut self, vendor: Vendor) {
         assert!(!self.index.contains_key(&vendor.id));
+        self.added.push(vendor.id);
         self.index.insert(vendor.id, vendor);
     }
 
@@ -47,7 +179,53 @@ impl Vendors {
         self.index.get(&id)
     }
 
+    pub fn get_mut(&mut self, id: ObjId) -> Option<&mut Vendor> {
+        self.index.get_mut(&id)
+    }
+
     pub fn exist(&self, id: ObjId) -> bool {
         self.index.contains_key(&id)
     }
+
+    pub fn take_added(&mut self) -> Vec<ObjId> {
+        std::mem::replace(&mut self.added, vec![])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::tags::Tags;
+
+    #[test]
+    fn test_vendor_stock() {
+        let tag_id = Tags::new().get_id("item");
+        let tags = vec![tag_id];
+        let static_id = StaticId(1);
+
+        let mut stock = VendorStock::new();
+        assert_eq!(None, stock.get_amount(static_id, &tags));
+        assert!(stock.take(static_id, &tags));
+
+        stock.limits.push(VendorStockLimit { tag_id, max: 2 });
+        assert_eq!(Some(0), stock.get_max(&vec![]));
+        assert_eq!(Some(2), stock.get_amount(static_id, &tags));
+        assert_eq!(1.0, stock.get_price_mult(static_id, &tags));
+
+        assert!(stock.take(static_id, &tags));
+        assert!(stock.take(static_id, &tags));
+        assert!(!stock.take(static_id, &tags));
+        assert_eq!(Some(0), stock.get_amount(static_id, &tags));
+        assert_eq!(
+            1.0 + STOCK_PRICE_VARIATION,
+            stock.get_price_mult(static_id, &tags)
+        );
+
+        stock.put(static_id, &tags);
+        assert_eq!(Some(1), stock.get_amount(static_id, &tags));
+
+        stock.restock();
+        assert_eq!(Some(2), stock.get_amount(static_id, &tags));
+        assert!(stock.items.is_empty());
+    }
 }
@@ -0,0 +1,281 @@
+use crate::errors::{Error, Result};
+use crate::game::calendar;
+use crate::game::container::Container;
+use crate::game::loader::dto::{CanLoad, CanSnapshot, ObjData, WeatherData};
+use crate::game::loader::LoadingCtx;
+use crate::game::location::{LocationId, Locations};
+use commons::{DeltaTime, ObjId, TotalTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeatherKind {
+    Clear,
+    Rain,
+    Storm,
+    /// planets only
+    DustStorm,
+}
+
+impl WeatherKind {
+    pub fn parse(value: &str) -> Result<WeatherKind> {
+        match value {
+            "clear" => Ok(WeatherKind::Clear),
+            "rain" => Ok(WeatherKind::Rain),
+            "storm" => Ok(WeatherKind::Storm),
+            "dust_storm" => Ok(WeatherKind::DustStorm),
+            other => Err(Error::InvalidArgumentFailureStr(format!(
+                "invalid weather {:?}",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeatherKind::Clear => "clear",
+            WeatherKind::Rain => "rain",
+            WeatherKind::Storm => "storm",
+            WeatherKind::DustStorm => "dust_storm",
+        }
+    }
+
+    /// ships can not land or launch
+    pub fn is_severe(&self) -> bool {
+        match self {
+            WeatherKind::Storm | WeatherKind::DustStorm => true,
+            WeatherKind::Clear | WeatherKind::Rain => false,
+        }
+    }
+
+    /// reduction on the attack of mobs fighting under this weather
+    pub fn attack_penalty(&self, ranged: bool) -> u32 {
+        match (self, ranged) {
+            (WeatherKind::Clear, _) => 0,
+            (WeatherKind::Rain, false) => 0,
+            (WeatherKind::Rain, true) => 1,
+            (WeatherKind::Storm, false) => 1,
+            (WeatherKind::Storm, true) => 2,
+            (WeatherKind::DustStorm, false) => 2,
+            (WeatherKind::DustStorm, true) => 3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Weather {
+    /// zone or astro body
+    pub id: ObjId,
+    pub states: Vec<WeatherKind>,
+    pub current: WeatherKind,
+    pub delay_min: DeltaTime,
+    pub delay_max: DeltaTime,
+    pub next_change: TotalTime,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Weathers {
+    index: HashMap<ObjId, Weather>,
+    /// added weathers that need to be scheduled
+    added: Vec<ObjId>,
+}
+
+impl Weathers {
+    pub fn new() -> Self {
+        Weathers {
+            index: HashMap::new(),
+            added: vec![],
+        }
+    }
+
+    pub fn add(&mut self, weather: Weather) -> Result<()> {
+        if self.index.contains_key(&weather.id) {
+            return Err(Error::ConflictException);
+        }
+        self.added.push(weather.id);
+        self.index.insert(weather.id, weather);
+        Ok(())
+    }
+
+    pub fn get(&self, id: ObjId) -> Option<&Weather> {
+        self.index.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: ObjId) -> Option<&mut Weather> {
+        self.index.get_mut(&id)
+    }
+
+    pub fn take_added(&mut self) -> Vec<ObjId> {
+        std::mem::replace(&mut self.added, vec![])
+    }
+
+    pub fn remove(&mut self, id: ObjId) {
+        self.index.remove(&id);
+    }
+}
+
+/// Closest parent zone or astro body with weather
+pub fn find_weather_owner_at(
+    weathers: &Weathers,
+    locations: &Locations,
+    location_id: LocationId,
+) -> Option<ObjId> {
+    locations
+        .list_parents_inclusive(location_id)
+        .into_iter()
+        .find(|id| weathers.get(*id).is_some())
+}
+
+pub fn find_weather_at(
+    weathers: &Weathers,
+    locations: &Locations,
+    location_id: LocationId,
+) -> Option<WeatherKind> {
+    find_weather_owner_at(weathers, locations, location_id)
+        .and_then(|id| weathers.get(id))
+        .map(|weather| weather.current)
+}
+
+/// Current weather, None for locations that can not see the sky
+pub fn get_outdoor_weather(container: &Container, location_id: LocationId) -> Option<WeatherKind> {
+    if calendar::is_outdoor(container, location_id) {
+        find_weather_at(&container.weathers, &container.locations, location_id)
+    } else {
+        None
+    }
+}
+
+impl CanLoad for Weathers {
+    fn load(&mut self, _references: &LoadingCtx, obj_id: ObjId, data: &ObjData) -> Result<()> {
+        let weather_data = match &data.weather {
+            Some(weather_data) => weather_data,
+            None => return Ok(()),
+        };
+
+        let states = weather_data
+            .states
+            .iter()
+            .map(|value| WeatherKind::parse(value.as_str()))
+            .collect::<Result<Vec<_>>>()?;
+
+        if states.is_empty()
+            || weather_data.time_min <= 0.0
+            || weather_data.time_min > weather_data.time_max
+        {
+            return Err(Error::InvalidArgumentFailureStr(format!(
+                "{:?} invalid weather {:?}",
+                obj_id, weather_data
+            )));
+        }
+
+        let current = match &weather_data.current {
+            Some(value) => WeatherKind::parse(value.as_str())?,
+            None => states[0],
+        };
+
+        let is_dust_storm = |kind: &WeatherKind| *kind == WeatherKind::DustStorm;
+        if data.zone.is_some() && (is_dust_storm(&current) || states.iter().any(is_dust_storm)) {
+            return Err(Error::InvalidArgumentFailureStr(format!(
+                "{:?} dust storms are only allowed on planets",
+                obj_id
+            )));
+        }
+
+        self.add(Weather {
+            id: obj_id,
+            states,
+            current,
+            delay_min: DeltaTime(weather_data.time_min),
+            delay_max: DeltaTime(weather_data.time_max),
+            next_change: TotalTime(weather_data.next_change.unwrap_or(0.0)),
+        })
+    }
+}
+
+impl CanSnapshot for Weathers {
+    fn snapshot(&self, obj_id: ObjId, data: &mut ObjData) -> Result<()> {
+        let weather = match self.index.get(&obj_id) {
+            Some(weather) => weather,
+            None => return Ok(()),
+        };
+
+        data.weather = Some(WeatherData {
+            states: weather
+                .states
+                .iter()
+                .map(|kind| kind.as_str().to_string())
+                .collect(),
+            current: Some(weather.current.as_str().to_string()),
+            time_min: weather.delay_min.as_seconds_f32(),
+            time_max: weather.delay_max.as_seconds_f32(),
+            next_change: Some(weather.next_change.as_seconds_f64()),
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::loader::Loader;
+
+    #[test]
+    fn test_find_weather_at_should_use_closest_parent() {
+        let mut locations = Locations::new();
+        locations.set(ObjId(1), ObjId(0));
+        locations.set(ObjId(2), ObjId(1));
+        locations.set(ObjId(3), ObjId(2));
+
+        let mut weathers = Weathers::new();
+        for (id, current) in vec![(0, WeatherKind::Clear), (1, WeatherKind::Storm)] {
+            weathers
+                .add(Weather {
+                    id: ObjId(id),
+                    states: vec![current],
+                    current,
+                    delay_min: DeltaTime(1.0),
+                    delay_max: DeltaTime(1.0),
+                    next_change: TotalTime(0.0),
+                })
+                .unwrap();
+        }
+
+        assert_eq!(
+            Some(WeatherKind::Storm),
+            find_weather_at(&weathers, &locations, ObjId(3))
+        );
+        assert_eq!(
+            Some(WeatherKind::Clear),
+            find_weather_at(&weathers, &locations, ObjId(0))
+        );
+        assert_eq!(None, find_weather_at(&weathers, &locations, ObjId(4)));
+    }
+
+    #[test]
+    fn test_load_should_fail_for_dust_storm_in_zones() {
+        let zone = r##"
+objects.0 {
+  id: 0
+  label: "valley"
+  zone {}
+  weather {
+    states: ["storm"]
+    time_min: 5.0
+    time_max: 5.0
+  }
+}
+"##;
+
+        let mut container = Container::new();
+        assert!(Loader::load_hocon(&mut container, zone).is_ok());
+
+        let mut container = Container::new();
+        let invalid = zone.replace("\"storm\"", "\"dust_storm\"");
+        assert!(Loader::load_hocon(&mut container, &invalid).is_err());
+
+        let mut container = Container::new();
+        let invalid = zone.replace("time_min", "current: \"dust_storm\"\n    time_min");
+        assert!(Loader::load_hocon(&mut container, &invalid).is_err());
+    }
+}
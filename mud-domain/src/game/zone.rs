@@ -1,4 +1,5 @@
 use crate::errors::{Error, Result};
+use crate::game::pvp::PvpRules;
 use commons::ObjId;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -10,11 +11,18 @@ pub type ZoneId = ObjId;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Zone {
     pub id: ObjId,
+    pub pvp: Option<PvpRules>,
+    /// Rooms inside can see the sky, rooms can overwrite it
+    pub outdoor: Option<bool>,
 }
 
 impl Zone {
     pub fn new(id: ObjId) -> Self {
-        Zone { id }
+        Zone {
+            id,
+            pvp: None,
+            outdoor: None,
+        }
     }
 }
 
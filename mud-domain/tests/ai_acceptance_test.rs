@@ -0,0 +1,347 @@
+extern crate mud_domain;
+
+mod common;
+
+use common::TestScenery;
+use commons::ObjId;
+use mud_domain::game::actions;
+use mud_domain::game::domain::Dir;
+use mud_domain::game::mob::MobCommand;
+use std::collections::HashSet;
+
+#[test]
+fn test_aggressive_patrol_home_should_keep_moving_in_distance_of_spawn() {
+    let mut scenery = TestScenery::new(
+        r##"
+objects.10 {
+  id: 10
+  label: "guard"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 0
+  ai.command_aggressive_patrol_home.distance: 1
+}
+"##,
+    );
+
+    let mut visited = HashSet::new();
+    for _ in 0..200 {
+        scenery.tick();
+        visited.insert(scenery.location(10));
+    }
+
+    let expected: HashSet<ObjId> = vec![scenery.id(0), scenery.id(1)].into_iter().collect();
+    assert_eq!(expected, visited);
+}
+
+#[test]
+fn test_aggressive_patrol_home_should_attack_enemies_in_the_room() {
+    let mut scenery = TestScenery::new(
+        r##"
+objects.10 {
+  id: 10
+  label: "guard"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 0
+  ai.command_aggressive_patrol_home.distance: 0
+}
+objects.11 {
+  id: 11
+  label: "thief"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 0
+}
+"##,
+    );
+
+    scenery.tick();
+    assert_eq!(
+        MobCommand::Kill {
+            target_id: scenery.id(11)
+        },
+        scenery.command(10)
+    );
+}
+
+#[test]
+fn test_roam_should_keep_moving_in_distance_of_spawn_without_attacking() {
+    let mut scenery = TestScenery::new(
+        r##"
+objects.10 {
+  id: 10
+  label: "deer"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 0
+  ai.command_roam.distance: 1
+}
+objects.11 {
+  id: 11
+  label: "hunter"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 1
+}
+"##,
+    );
+
+    let mut visited = HashSet::new();
+    for _ in 0..200 {
+        scenery.tick();
+        visited.insert(scenery.location(10));
+        assert_eq!(MobCommand::None, scenery.command(10));
+    }
+
+    let expected: HashSet<ObjId> = vec![scenery.id(0), scenery.id(1)].into_iter().collect();
+    assert_eq!(expected, visited);
+}
+
+#[test]
+fn test_passive_should_ignore_others_until_attacked() {
+    let mut scenery = TestScenery::new(
+        r##"
+objects.10 {
+  id: 10
+  label: "farmer"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 0
+  ai.command_passive: true
+}
+objects.11 {
+  id: 11
+  label: "thief"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 0
+}
+"##,
+    );
+
+    for _ in 0..10 {
+        scenery.tick();
+    }
+    assert_eq!(MobCommand::None, scenery.command(10));
+
+    let farmer_id = scenery.id(10);
+    let thief_id = scenery.id(11);
+    actions::attack(&mut scenery.game.container, thief_id, farmer_id).unwrap();
+
+    let mut fight_back = false;
+    for _ in 0..10 {
+        scenery.tick();
+        fight_back |= scenery.command(10)
+            == MobCommand::Kill {
+                target_id: thief_id,
+            };
+    }
+    assert!(fight_back);
+}
+
+#[test]
+fn test_follow_and_protect_should_follow_owner_and_join_combat() {
+    let mut scenery = TestScenery::new(
+        r##"
+objects.10 {
+  id: 10
+  label: "knight"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 0
+}
+objects.11 {
+  id: 11
+  label: "squire"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 0
+  ai.command_follow_and_protect: 10
+}
+objects.12 {
+  id: 12
+  label: "bandit"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 1
+}
+"##,
+    );
+
+    let knight_id = scenery.id(10);
+    let bandit_id = scenery.id(12);
+
+    actions::move_dir(&mut scenery.game.container, knight_id, Dir::S).unwrap();
+    scenery.tick();
+    assert_eq!(scenery.id(1), scenery.location(11));
+
+    actions::attack(&mut scenery.game.container, bandit_id, knight_id).unwrap();
+
+    let mut protected = false;
+    for _ in 0..10 {
+        scenery.tick();
+        protected |= scenery.command(11)
+            == MobCommand::Kill {
+                target_id: bandit_id,
+            };
+    }
+    assert!(protected);
+}
+
+#[test]
+fn test_follow_and_protect_should_become_idle_when_target_is_gone() {
+    let mut scenery = TestScenery::new(
+        r##"
+objects.10 {
+  id: 10
+  label: "knight"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 0
+}
+objects.11 {
+  id: 11
+  label: "squire"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 0
+  ai.command_follow_and_protect: 10
+}
+"##,
+    );
+
+    let knight_id = scenery.id(10);
+    scenery.game.container.remove(knight_id);
+    scenery.tick();
+
+    let squire_id = scenery.id(11);
+    let ai = scenery.game.container.ai.get(squire_id).unwrap();
+    assert_eq!(mud_domain::game::ai::AiCommand::Idle, ai.command);
+}
+
+#[test]
+fn test_hauler_should_move_items_from_container_to_distant_room() {
+    let mut scenery = TestScenery::new(
+        r##"
+objects.10 {
+  id: 10
+  label: "mule"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 1
+  inventory.max_weight: 10.0
+  ai.command_haul {
+    from_id: 20
+    to_id: 2
+    targets: []
+  }
+}
+objects.20 {
+  id: 20
+  label: "crate"
+  parent: 0
+  item {
+    flags.inventory: true
+    flags.stuck: true
+  }
+}
+objects.21 {
+  id: 21
+  label: "ore"
+  parent: 20
+  item.weight: 4.0
+}
+objects.22 {
+  id: 22
+  label: "ore"
+  parent: 20
+  item.weight: 4.0
+}
+objects.23 {
+  id: 23
+  label: "ore"
+  parent: 20
+  item.weight: 4.0
+}
+"##,
+    );
+
+    let forest_id = scenery.id(2);
+    let crate_id = scenery.id(20);
+
+    for _ in 0..50 {
+        scenery.tick();
+    }
+
+    assert_eq!(
+        0,
+        scenery.game.container.locations.list_at(crate_id).count()
+    );
+    for item_static_id in 21..24 {
+        assert_eq!(forest_id, scenery.location(item_static_id));
+    }
+}
+
+#[test]
+fn test_hauler_should_keep_carried_money() {
+    let mut scenery = TestScenery::new(
+        r##"
+objects.10 {
+  id: 10
+  label: "mule"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 1
+  inventory.max_weight: 10.0
+  ai.command_haul {
+    from_id: 20
+    to_id: 2
+    targets: []
+  }
+}
+objects.11 {
+  id: 11
+  label: "gold"
+  parent: 10
+  item {
+    amount: 10
+    flags.money: true
+  }
+}
+objects.20 {
+  id: 20
+  label: "crate"
+  parent: 0
+  item {
+    flags.inventory: true
+    flags.stuck: true
+  }
+}
+objects.21 {
+  id: 21
+  label: "ore"
+  parent: 20
+  item.weight: 4.0
+}
+"##,
+    );
+
+    for _ in 0..50 {
+        scenery.tick();
+    }
+
+    assert_eq!(scenery.id(2), scenery.location(21));
+    assert_eq!(scenery.id(10), scenery.location(11));
+}
+
+#[test]
+fn test_patrol_should_be_deterministic() {
+    let guard = r##"
+objects.10 {
+  id: 10
+  label: "guard"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+  parent: 1
+  ai.command_roam.distance: 2
+}
+"##;
+
+    let mut scenery1 = TestScenery::new(guard);
+    let mut scenery2 = TestScenery::new(guard);
+    let mut moved = false;
+    for _ in 0..100 {
+        scenery1.tick();
+        scenery2.tick();
+        assert_eq!(scenery1.location(10), scenery2.location(10));
+        moved |= scenery1.location(10) != scenery1.id(1);
+    }
+
+    assert!(moved);
+}
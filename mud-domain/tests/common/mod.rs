@@ -0,0 +1,119 @@
+//! Scenery shared by ai and spawn acceptance tests, each test file use a different subset of it.
+#![allow(dead_code)]
+
+use commons::{DeltaTime, ObjId};
+use mud_domain::game::container::Container;
+use mud_domain::game::loader::{Loader, LoadingCtx};
+use mud_domain::game::mob::MobCommand;
+use mud_domain::game::{Game, GameCfg};
+use std::collections::HashSet;
+
+//
+// { 90 } valley zone
+//
+// [ 0 ] village
+//   |
+// [ 1 ] road
+//   |
+// [ 2 ] forest
+//   |
+// [ 3 ] cave, outside of the zone
+//
+const SCENERY: &str = r##"
+objects.90 {
+  id: 90
+  label: "valley"
+  zone {}
+}
+objects.0 {
+  id: 0
+  label: "village"
+  parent: 90
+  room.exits: [{dir: "s", to: 1}]
+}
+objects.1 {
+  id: 1
+  label: "road"
+  parent: 90
+  room.exits: [{dir: "n", to: 0}, {dir: "s", to: 2}]
+}
+objects.2 {
+  id: 2
+  label: "forest"
+  parent: 90
+  room.exits: [{dir: "n", to: 1}, {dir: "s", to: 3}]
+}
+objects.3 {
+  id: 3
+  label: "cave"
+  room.exits: [{dir: "n", to: 2}]
+}
+"##;
+
+pub struct TestScenery {
+    pub game: Game,
+    pub lc: LoadingCtx,
+}
+
+impl TestScenery {
+    pub fn new(objects: &str) -> Self {
+        let mut container = Container::new();
+        let lc = Loader::load_hocon(&mut container, &format!("{}\n{}", SCENERY, objects)).unwrap();
+
+        TestScenery {
+            game: Game::new(GameCfg::new(), container),
+            lc,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.game.tick(DeltaTime(0.5));
+    }
+
+    pub fn id(&self, static_id: u32) -> ObjId {
+        self.lc.get(static_id)
+    }
+
+    pub fn ids(&self, static_ids: Vec<u32>) -> HashSet<ObjId> {
+        static_ids.into_iter().map(|id| self.id(id)).collect()
+    }
+
+    pub fn location(&self, static_id: u32) -> ObjId {
+        self.game
+            .container
+            .locations
+            .get(self.id(static_id))
+            .unwrap()
+    }
+
+    pub fn command(&self, static_id: u32) -> MobCommand {
+        self.game
+            .container
+            .mobs
+            .get(self.id(static_id))
+            .unwrap()
+            .command
+            .clone()
+    }
+
+    /// mobs with the label and their location
+    pub fn find_mobs(&self, label: &str) -> Vec<(ObjId, ObjId)> {
+        let container = &self.game.container;
+        let mut mobs: Vec<(ObjId, ObjId)> = container
+            .mobs
+            .list()
+            .map(|mob| mob.id)
+            .filter(|id| container.labels.get_label(*id) == Some(label))
+            .map(|id| (id, container.locations.get(id).unwrap()))
+            .collect();
+        mobs.sort();
+        mobs
+    }
+
+    pub fn locations(&self, label: &str) -> HashSet<ObjId> {
+        self.find_mobs(label)
+            .into_iter()
+            .map(|(_, location_id)| location_id)
+            .collect()
+    }
+}
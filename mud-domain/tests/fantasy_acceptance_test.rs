@@ -223,6 +223,208 @@ fn test_fantasy_hire_mercenary_and_fight() {
     scenery.wait_until(vec!["mercenary", "attack", "wolf"], vec![]);
 }
 
+#[test]
+fn test_fantasy_command_mercenary() {
+    let mut scenery = new_scenery(vec![
+        "../data/tests/scenery_fantasy_forest_wolf.conf",
+        "../data/tests/scenery_fantasy_forest_wolf_mercenary.conf",
+    ]);
+
+    scenery.give_money(100);
+    hire_mercenary(&mut scenery);
+
+    scenery.input_and_wait("command", "mercenary");
+    scenery.input_and_wait("command mercenary", "guard");
+    scenery.input_and_wait("command wolf: wait", "you have no");
+    scenery.input_and_wait("command mercenary: dance", "unknown command");
+    scenery.input_and_wait("command mercenary: haul 0 99", "can not be found");
+
+    // stay in the village when we move
+    scenery.input_and_wait("command mercenary: wait", "is waiting");
+    scenery.input_and_wait("s", "forest");
+    scenery.input("look");
+    scenery.wait_until(vec!["forest"], vec!["mercenary"]);
+
+    // come to guard the forest
+    scenery.input_and_wait("command mercenary: guard", "is guarding");
+    scenery.repeat_command_until("look", "mercenary");
+
+    // haul between known rooms by label
+    scenery.input_and_wait("command mercenary: haul forest village", "is hauling from");
+
+    // follow back into village
+    scenery.input_and_wait("command mercenary: follow me", "is now following you");
+    scenery.input_and_wait("n", "village");
+    scenery.input_and_wait("look", "mercenary");
+}
+
+#[test]
+fn test_fantasy_talk_with_npc() {
+    let mut scenery = new_scenery(vec![
+        "../data/tests/scenery_fantasy_forest_wolf.conf",
+        "../data/tests/scenery_fantasy_forest_wolf_dialogue.conf",
+    ]);
+
+    scenery.input_and_wait("talk wolf", "there is no wolf");
+    scenery.input_and_wait("talk hermit", "1) Can you spare a weapon?");
+    scenery.input_and_wait("7", "7 is not a option");
+    scenery.input("1");
+    scenery.wait_until(vec!["you receive sword", "Take this old sword"], vec![]);
+    scenery.input("1");
+    scenery.wait_until(
+        vec!["1) Tell me about the forest.\n2) Goodbye."],
+        vec!["Can you spare a weapon?"],
+    );
+    scenery.input_and_wait("2", "you stop to talk");
+
+    // the weapon is given only once
+    scenery.input("talk hermit");
+    scenery.wait_until(
+        vec!["1) Tell me about the forest."],
+        vec!["Can you spare a weapon?"],
+    );
+
+    // the conversation ends in a node without options
+    scenery.input_and_wait("1", "Only wolves live south");
+    scenery.input_and_wait("1", "unknown command");
+}
+
+#[test]
+fn test_fantasy_quest_hunt_wolf() {
+    let mut scenery = new_scenery(vec![
+        "../data/tests/scenery_fantasy_forest_wolf.conf",
+        "../data/tests/scenery_fantasy_forest_wolf_quest.conf",
+    ]);
+
+    scenery.input_and_wait("quests", "you have no quests");
+    scenery.input_and_wait("quest abandon wolf hunt", "you have no quest wolf hunt");
+
+    // accept and abandon
+    scenery.input_and_wait("talk ranger", "1) I will hunt one.");
+    scenery.input_and_wait("1", "reach forest 0/1");
+    scenery.input_and_wait("talk ranger", "1) I will hunt one.");
+    scenery.input_and_wait("1", "you are already doing wolf hunt");
+    scenery.input_and_wait("quest abandon wolf hunt", "you abandon wolf hunt");
+    scenery.input_and_wait("quests", "you have no quests");
+
+    // accept again and complete it
+    scenery.input_and_wait("talk ranger", "1) I will hunt one.");
+    scenery.input_and_wait("1", "kill wolf 0/1");
+    scenery.input_and_wait("s", "reach forest 1/1");
+
+    // replace the static wolf by one from prefab
+    let container = &mut scenery.game.container;
+    let player_id = container.players.list_players()[0];
+    let mob_id = container.players.get_mob(player_id).unwrap();
+    let forest_id = container.locations.get(mob_id).unwrap();
+    let wolves: Vec<ObjId> = container
+        .locations
+        .list_at(forest_id)
+        .filter(|id| container.labels.get_label(*id) == Some("wolf"))
+        .collect();
+    for wolf_id in wolves {
+        container.remove(wolf_id);
+    }
+    Loader::spawn_at(container, StaticId(10), forest_id).unwrap();
+
+    scenery.input("kill wolf");
+    scenery.wait_until(
+        vec![
+            "kill wolf 1/1",
+            "quest wolf hunt completed",
+            "you receive 50 xp",
+            "you receive 5 of money",
+            "you receive sword",
+        ],
+        vec![],
+    );
+
+    scenery.input_and_wait("quests", "you have no quests");
+    scenery.input_and_wait("n", "village");
+    scenery.input_and_wait("talk ranger", "1) I will hunt one.");
+    scenery.input_and_wait("1", "you already completed wolf hunt");
+}
+
+#[test]
+fn test_fantasy_scripts_shrine_puzzle() {
+    let mut scenery = new_scenery(vec![
+        "../data/tests/scenery_fantasy_forest_wolf.conf",
+        "../data/tests/scenery_fantasy_forest_wolf_script.conf",
+    ]);
+
+    scenery.input_and_wait("use vendor", "you can not find a vendor to use");
+    scenery.input_and_wait("use shrine", "the shrine does not answer");
+
+    // say hook set a tag in the player
+    scenery.input_and_wait("say bless me", "you feel blessed by the shrine");
+    scenery.input_and_wait("say bless me", "the shrine stays silent");
+
+    // use hook move the player into the cave
+    scenery.input("use shrine");
+    scenery.wait_until(vec!["the shrine opens a path", "cave"], vec![]);
+
+    // enter and leave hooks of the cave, triggered in the tick after the move
+    scenery.input("s");
+    scenery.wait_for("tunnel");
+    scenery.wait_for("you are glad to leave the cave");
+    scenery.input("n");
+    scenery.wait_until(vec!["cold air comes from the cave"], vec![]);
+
+    // death hook spawn a prefab
+    scenery.input("kill bat");
+    scenery.wait_for("something falls from the bat");
+    scenery.input_and_wait("look", "meat");
+}
+
+#[test]
+fn test_fantasy_calendar_day_and_night() {
+    let mut scenery = new_scenery(vec![
+        "../data/tests/scenery_fantasy_forest_wolf.conf",
+        "../data/tests/scenery_fantasy_forest_wolf_calendar.conf",
+    ]);
+
+    scenery.input_and_wait("time", "night");
+    scenery.input_and_wait("date", "it is day 1 of dry, year 1");
+
+    // night only spawn and night description
+    scenery.wait_for("owl");
+    scenery.input("look");
+    scenery.wait_until(
+        vec!["sleeping in the dark", "full of stars", "owl"],
+        vec!["rooster"],
+    );
+
+    // sunrise is announced and day spawns start
+    scenery.wait_for("the sun rises in the horizon");
+    scenery.input_and_wait("time", "day");
+    let outputs = scenery.repeat_command_until("look", "rooster");
+    assert!(check_output(
+        &outputs,
+        &vec!["under the sun", "the sun shines in the sky"],
+        &vec!["stars"]
+    ));
+
+    // indoor rooms can not see the sky
+    scenery.input("s");
+    scenery.wait_until(vec!["forest"], vec!["the sun shines in the sky"]);
+}
+
+#[test]
+fn test_fantasy_weather_changes_in_outdoor_zone() {
+    let mut scenery = new_scenery(vec![
+        "../data/tests/scenery_fantasy_forest_wolf.conf",
+        "../data/tests/scenery_fantasy_forest_wolf_weather.conf",
+    ]);
+
+    scenery.input_and_wait("look", "it is raining");
+    scenery.wait_for("a storm begins");
+    scenery.input_and_wait("look", "a storm rages around");
+
+    // forest is outside of the zone
+    scenery.input("s");
+    scenery.wait_until(vec!["forest"], vec!["storm"]);
+}
+
 #[test]
 fn test_fantasy_show_map() {
     let mut scenery = TestScenery::new(load_fantasy());
@@ -239,6 +441,21 @@ fn test_fantasy_show_map() {
     // assert_eq!(Vec::<String>::new(), scenery.take_outputs());
 }
 
+#[test]
+fn test_fantasy_path_and_walk_to_known_room() {
+    let mut scenery = TestScenery::new(load_fantasy());
+    scenery.login();
+    scenery.input_and_wait("e", "Bar");
+    scenery.input_and_wait("w", "Village");
+    scenery.input_and_wait("w", "Temple");
+
+    scenery.input_and_wait("path dungeon", "you do not know any place");
+    scenery.input_and_wait("path bar", "path to Bar: e, e");
+    scenery.input_and_wait("walk to bar", "you start to walk to Bar, 2 steps away");
+    scenery.wait_for("you arrive at Bar");
+    scenery.input_and_wait("look", "Bar");
+}
+
 #[test]
 fn test_fantasy_random_rooms() {
     let mut scenery = TestScenery::new(load_fantasy());
@@ -1,9 +1,10 @@
 extern crate mud_domain;
 
-use commons::{ConnectionId, DeltaTime};
+use commons::{ConnectionId, DeltaTime, ObjId, TotalTime};
 use mud_domain::controller::strip_rich_text;
 use mud_domain::game::container::Container;
 use mud_domain::game::outputs::OMarker;
+use mud_domain::game::weather::{Weather, WeatherKind};
 use mud_domain::game::{loader, Game, GameCfg};
 
 pub struct TestScenery {
@@ -190,6 +191,71 @@ fn test_launch_and_land() {
     s.land();
 }
 
+#[test]
+fn test_land_should_be_aborted_by_severe_weather() {
+    let base = TestScenery::new(&vec![
+        "../data/tests/scenery_space_min.conf",
+        "../data/tests/scenery_space_min_weather.conf",
+    ]);
+    let mut s = SceneryMin { base };
+    s.base.login();
+    s.enter_ship_and_move_to_bridge();
+    s.launch();
+    s.move_to_sector2();
+    s.move_to_planet2();
+
+    s.base.send_input("land landing zone");
+    s.base
+        .wait_for("landing aborted, dust storm at the landing zone");
+
+    // ship is still in orbit
+    s.base.eventually("sm", "transport");
+}
+
+#[test]
+fn test_land_should_hold_once_while_severe_weather() {
+    let mut s = SceneryMin::new();
+    s.enter_ship_and_move_to_bridge();
+    s.launch();
+    s.move_to_sector2();
+    s.move_to_planet2();
+
+    s.base.send_input("land landing zone");
+    s.base.wait_for("retro-burn started");
+
+    // a dust storm starts at planet2 while landing
+    let planet_id = ObjId(4);
+    let container = &mut s.base.game.container;
+    assert_eq!(Some("planet2"), container.labels.get_label(planet_id));
+    container
+        .weathers
+        .add(Weather {
+            id: planet_id,
+            states: vec![WeatherKind::DustStorm],
+            current: WeatherKind::DustStorm,
+            delay_min: DeltaTime(1000.0),
+            delay_max: DeltaTime(1000.0),
+            next_change: TotalTime(0.0),
+        })
+        .unwrap();
+
+    let mut outputs = s.base.wait_for("holding position");
+    for _ in 0..20 {
+        s.base.tick();
+        outputs.extend(s.base.take_outputs());
+    }
+
+    let holding_count = outputs
+        .iter()
+        .filter(|msg| msg.contains("holding position"))
+        .count();
+    assert_eq!(1, holding_count);
+
+    // landing continues once the weather is clear
+    s.base.game.container.weathers.remove(planet_id);
+    s.base.wait_for("landing complete");
+}
+
 #[test]
 fn test_mine_ore() {
     let mut s = TestScenery::new(&vec![
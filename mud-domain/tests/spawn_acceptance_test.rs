@@ -0,0 +1,242 @@
+extern crate mud_domain;
+
+mod common;
+
+use common::TestScenery;
+use mud_domain::game::ai::AiCommand;
+use mud_domain::game::combat;
+use std::collections::HashSet;
+
+const PREFABS: &str = r##"
+prefabs.100 {
+  id: 100
+  label: "goblin"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+}
+prefabs.101 {
+  id: 101
+  label: "wolf"
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+}
+"##;
+
+fn new_scenery(objects: &str) -> TestScenery {
+    TestScenery::new(&format!("{}\n{}", PREFABS, objects))
+}
+
+#[test]
+fn test_spawn_group_should_create_followers_with_the_leader() {
+    let mut scenery = new_scenery(
+        r##"
+objects.40 {
+  id: 40
+  label: "goblin camp"
+  parent: 0
+  spawn { prefab_id: 100, max: 1, time_min: 0.1, time_max: 0.2, followers: [101, 101] }
+}
+"##,
+    );
+
+    for _ in 0..10 {
+        scenery.tick();
+    }
+
+    // followers do not count for the spawn max
+    let goblins = scenery.find_mobs("goblin");
+    let wolves = scenery.find_mobs("wolf");
+    assert_eq!(1, goblins.len());
+    assert_eq!(2, wolves.len());
+
+    let (goblin_id, goblin_location_id) = goblins[0];
+    let container = &scenery.game.container;
+    for (wolf_id, wolf_location_id) in wolves {
+        assert_eq!(goblin_location_id, wolf_location_id);
+        assert_eq!(Some(goblin_id), container.ownership.get_owner(wolf_id));
+        assert_eq!(
+            AiCommand::FollowAndProtect {
+                target_id: goblin_id
+            },
+            container.ai.get(wolf_id).unwrap().command
+        );
+    }
+}
+
+#[test]
+fn test_spawn_group_followers_should_count_for_max_after_leader_is_killed() {
+    let mut scenery = new_scenery(
+        r##"
+objects.40 {
+  id: 40
+  label: "goblin camp"
+  parent: 0
+  spawn { prefab_id: 100, max: 1, time_min: 0.1, time_max: 0.2, followers: [101, 101] }
+}
+"##,
+    );
+
+    for _ in 0..10 {
+        scenery.tick();
+    }
+
+    let (goblin_id, _) = scenery.find_mobs("goblin")[0];
+    combat::kill_mob(&mut scenery.game.container, goblin_id, None).unwrap();
+
+    for _ in 0..10 {
+        scenery.tick();
+    }
+
+    // no new group while the followers are alive
+    let spawn_id = scenery.id(40);
+    let wolves = scenery.find_mobs("wolf");
+    assert!(scenery.find_mobs("goblin").is_empty());
+    assert_eq!(2, wolves.len());
+    for (wolf_id, _) in &wolves {
+        assert_eq!(
+            Some(spawn_id),
+            scenery.game.container.ownership.get_owner(*wolf_id)
+        );
+    }
+
+    for (wolf_id, _) in wolves {
+        scenery.game.container.remove(wolf_id);
+    }
+
+    for _ in 0..10 {
+        scenery.tick();
+    }
+
+    assert_eq!(1, scenery.find_mobs("goblin").len());
+    assert_eq!(2, scenery.find_mobs("wolf").len());
+}
+
+#[test]
+fn test_spawn_in_zone_should_use_any_room_of_the_zone() {
+    let mut scenery = new_scenery(
+        r##"
+objects.40 {
+  id: 40
+  label: "goblin camp"
+  parent: 0
+  spawn { prefab_id: 100, max: 30, time_min: 0.1, time_max: 0.2, zone_id: 90 }
+}
+"##,
+    );
+
+    for _ in 0..60 {
+        scenery.tick();
+    }
+
+    assert_eq!(scenery.ids(vec![0, 1, 2]), scenery.locations("goblin"));
+}
+
+#[test]
+fn test_spawn_in_radius_should_use_rooms_near_the_spawn() {
+    let mut scenery = new_scenery(
+        r##"
+objects.40 {
+  id: 40
+  label: "goblin camp"
+  parent: 1
+  spawn { prefab_id: 100, max: 30, time_min: 0.1, time_max: 0.2, radius: 1 }
+}
+objects.41 {
+  id: 41
+  label: "wolf den"
+  parent: 3
+  spawn { prefab_id: 101, max: 30, time_min: 0.1, time_max: 0.2, radius: 0 }
+}
+"##,
+    );
+
+    for _ in 0..60 {
+        scenery.tick();
+    }
+
+    assert_eq!(scenery.ids(vec![0, 1, 2]), scenery.locations("goblin"));
+    assert_eq!(scenery.ids(vec![3]), scenery.locations("wolf"));
+}
+
+#[test]
+fn test_spawn_should_avoid_rooms_with_players() {
+    let mut scenery = new_scenery(
+        r##"
+objects.30 {
+  id: 30
+  label: "avatar"
+  parent: 0
+  mob { attack: 1, damage_max: 1, damage_min: 0, defense: 1, pv: 1000, pv_max: 1000, xp: 0 }
+}
+objects.31 {
+  id: 31
+  label: "player"
+  player { id: 31, avatar_id: 30, login: "player" }
+}
+objects.40 {
+  id: 40
+  label: "goblin camp"
+  spawn { prefab_id: 100, max: 30, time_min: 0.1, time_max: 0.2, locations_id: [0, 1], avoid_players: true }
+}
+"##,
+    );
+
+    for _ in 0..60 {
+        scenery.tick();
+    }
+
+    assert_eq!(scenery.ids(vec![1]), scenery.locations("goblin"));
+}
+
+#[test]
+fn test_spawn_should_apply_ai_overwrite_to_roam() {
+    let mut scenery = new_scenery(
+        r##"
+objects.40 {
+  id: 40
+  label: "goblin camp"
+  parent: 0
+  spawn {
+    prefab_id: 100
+    max: 1
+    time_min: 0.1
+    time_max: 0.2
+    ai_overwrite.command_roam.distance: 1
+  }
+}
+"##,
+    );
+
+    let mut visited = HashSet::new();
+    for _ in 0..200 {
+        scenery.tick();
+        visited.extend(scenery.locations("goblin"));
+    }
+
+    let (goblin_id, _) = scenery.find_mobs("goblin")[0];
+    assert_eq!(
+        AiCommand::Roam { distance: 1 },
+        scenery.game.container.ai.get(goblin_id).unwrap().command
+    );
+    assert_eq!(scenery.ids(vec![0, 1]), visited);
+}
+
+#[test]
+fn test_spawn_should_be_deterministic() {
+    let spawn = r##"
+objects.40 {
+  id: 40
+  label: "goblin camp"
+  parent: 0
+  spawn { prefab_id: 100, max: 10, time_min: 0.1, time_max: 2.0, zone_id: 90 }
+}
+"##;
+
+    let mut scenery1 = new_scenery(spawn);
+    let mut scenery2 = new_scenery(spawn);
+    for _ in 0..30 {
+        scenery1.tick();
+        scenery2.tick();
+        assert_eq!(scenery1.find_mobs("goblin"), scenery2.find_mobs("goblin"));
+    }
+
+    assert!(scenery1.find_mobs("goblin").len() > 1);
+}
@@ -1,7 +1,7 @@
 use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ItemConf {
     pub code: String,
     pub kind: String,
@@ -15,7 +15,7 @@ pub struct ItemConf {
     pub rarity: f32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MaterialConf {
     pub code: String,
     pub name: String,
@@ -23,7 +23,7 @@ pub struct MaterialConf {
     pub bonus: f32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ItemsConf {
     pub items: Vec<ItemConf>,
     pub attacks: commons::prob::RDistrib,
@@ -34,13 +34,13 @@ pub struct ItemsConf {
     pub damage_max: commons::prob::RDistrib,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Conf {
     pub items: ItemsConf,
     pub materials: Vec<MaterialConf>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Item {
     pub name: String,
     pub item_code: String,